@@ -0,0 +1,83 @@
+//! Headless timing of the `box_stack_stress` scene (no renderer needed), with the number
+//! of heap allocations per step.
+//!
+//! cargo run --release --example bench_box_stack -- [rows] [steps]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::prelude::*;
+
+/// The system allocator, counting allocations.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let rows: usize = args.next().and_then(|s| s.parse().ok()).unwrap_or(14);
+    let steps: usize = args.next().and_then(|s| s.parse().ok()).unwrap_or(1000);
+
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.solver.params.restitution = 0.1;
+    world.solver.params.friction = 0.8;
+    world.solver.params.velocity_iterations = 5;
+
+    let ground_w = rows as Scalar * 1.1 + 4.0;
+    let ground = RigidBody::box_xy(Vec2::new(0.0, -6.0), 0.0, 0.0, ground_w, 1.0);
+    world.add(Box::new(ground));
+
+    let box_w = 1.0;
+    let box_h = 0.5;
+    let gap = 0.02;
+    let start_y = -6.0 + 1.0 * 0.5 + box_h * 0.5 + 0.02;
+    for row in 0..rows {
+        let count = rows - row;
+        let y = start_y + (box_h + gap) * row as Scalar;
+        let row_width = count as Scalar * box_w + (count as Scalar - 1.0) * gap;
+        let x_start = -0.5 * row_width + 0.5 * box_w;
+        for i in 0..count {
+            let x = x_start + i as Scalar * (box_w + gap);
+            let mut rb = RigidBody::box_xy(Vec2::new(x, y), 0.0, 1.0, box_w, box_h);
+            rb.angle = (row as Scalar * 0.001) - 0.007;
+            world.add(Box::new(rb));
+        }
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..steps {
+        world.step(1.0 / 30.0);
+    }
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+    println!(
+        "{} bodies, {} steps: {:.3?} total, {:.3?} per step, {} allocations per step",
+        world.entities.len(),
+        steps,
+        elapsed,
+        elapsed / steps as u32,
+        allocations / steps.max(1)
+    );
+}
@@ -0,0 +1,32 @@
+//! Headless timing of a spring cloth made of particles (no renderer needed).
+//!
+//! cargo run --release --example bench_particle_cloth -- [side] [steps]
+
+use std::time::Instant;
+
+use tiny_physics_engine::forces::cloth::{GridCloth, build_grid_cloth};
+use tiny_physics_engine::prelude::*;
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let side: usize = args.next().and_then(|s| s.parse().ok()).unwrap_or(45);
+    let steps: usize = args.next().and_then(|s| s.parse().ok()).unwrap_or(1000);
+
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    let cloth = GridCloth::new(Vec2::zero(), side, side, 0.1, 400.0, 0.5, 0.02);
+    build_grid_cloth(&mut world, &cloth);
+
+    let start = Instant::now();
+    for _ in 0..steps {
+        world.step(1.0 / 120.0);
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{} particles, {} springs, {} steps: {:.3?} total, {:.3?} per step",
+        world.entities.len(),
+        world.forces.len(),
+        steps,
+        elapsed,
+        elapsed / steps as u32
+    );
+}
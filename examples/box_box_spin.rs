@@ -1,12 +1,8 @@
 use macroquad::prelude as mq;
 
-use tiny_physics_engine::core::{Integrator, RigidBody, World};
-use tiny_physics_engine::math::vec::Vec2;
-
-#[cfg(not(feature = "visualize"))]
-compile_error!("Enable this example with: cargo run --example box_box_spin --features visualize");
-#[cfg(feature = "visualize")]
-use tiny_physics_engine::utils::visualize::draw_world;
+use tiny_physics_engine::forces::drag::AngularDrag;
+use tiny_physics_engine::prelude::*;
+use tiny_physics_engine::utils::visualize::{Camera, draw_world};
 
 #[macroquad::main("Tiny Physics — Box/Box Spin Impact")]
 async fn main() {
@@ -15,26 +11,42 @@ async fn main() {
     let mut world = World::new(gravity, Integrator::SemiImplicitEuler);
     world.solver.params.restitution = 0.2;
     world.solver.params.friction = 0.6;
-    world.solver.iterations = 16;
+    world.solver.params.velocity_iterations = 16;
+
+    // A little rotational air drag so both boxes eventually stop spinning.
+    world.add_force(Box::new(AngularDrag {
+        k: 0.3,
+        scale_by_radius: true,
+    }));
 
-    let scale = 80.0;
+    let mut camera = Camera::new(80.0);
     let fixed_dt = 1.0 / 240.0;
-    let mut accumulator = 0.0f32;
+    let mut accumulator: Scalar = 0.0;
 
     // A heavier spinning box moving right.
-    let mut a = RigidBody::box_xy(Vec2::new(-4.0, 0.0), 0.35, 3.0, 1.6, 0.8);
-    a.vel = Vec2::new(7.0, 0.0);
-    a.omega = 6.0;
+    let a = RigidBodyBuilder::new()
+        .position(Vec2::new(-4.0, 0.0))
+        .angle(0.35)
+        .velocity(Vec2::new(7.0, 0.0))
+        .angular_velocity(6.0)
+        .mass(3.0)
+        .box_shape(1.6, 0.8)
+        .build();
     world.add(Box::new(a));
 
     // A lighter box moving left.
-    let mut b = RigidBody::box_xy(Vec2::new(4.0, 0.3), -0.2, 1.0, 1.2, 1.0);
-    b.vel = Vec2::new(-5.5, 0.0);
-    b.omega = -2.0;
+    let b = RigidBodyBuilder::new()
+        .position(Vec2::new(4.0, 0.3))
+        .angle(-0.2)
+        .velocity(Vec2::new(-5.5, 0.0))
+        .angular_velocity(-2.0)
+        .mass(1.0)
+        .box_shape(1.2, 1.0)
+        .build();
     world.add(Box::new(b));
 
     loop {
-        let mut frame_dt = mq::get_frame_time();
+        let mut frame_dt = mq::get_frame_time() as Scalar;
         if frame_dt > 1.0 / 30.0 {
             frame_dt = 1.0 / 30.0;
         }
@@ -44,7 +56,7 @@ async fn main() {
             accumulator -= fixed_dt;
         }
 
-        draw_world(&world, scale);
+        draw_world(&world, &mut camera);
         mq::next_frame().await;
     }
 }
@@ -1,14 +1,7 @@
 use macroquad::prelude as mq;
 
-use tiny_physics_engine::core::{Integrator, RigidBody, World};
-use tiny_physics_engine::math::vec::Vec2;
-
-#[cfg(not(feature = "visualize"))]
-compile_error!(
-    "Enable this example with: cargo run --example box_stack_stress --features visualize"
-);
-#[cfg(feature = "visualize")]
-use tiny_physics_engine::utils::visualize::draw_world;
+use tiny_physics_engine::prelude::*;
+use tiny_physics_engine::utils::visualize::{Camera, draw_world};
 
 #[macroquad::main("Tiny Physics — Box Stack Stress")]
 async fn main() {
@@ -16,11 +9,11 @@ async fn main() {
     let mut world = World::new(gravity, Integrator::SemiImplicitEuler);
     world.solver.params.restitution = 0.1;
     world.solver.params.friction = 0.8;
-    world.solver.iterations = 5;
+    world.solver.params.velocity_iterations = 5;
 
-    let scale = 60.0;
+    let mut camera = Camera::new(60.0);
     let fixed_dt = 1.0 / 30.0;
-    let mut accumulator = 0.0f32;
+    let mut accumulator: Scalar = 0.0;
 
     // Static ground
     let ground = RigidBody::box_xy(Vec2::new(0.0, -6.0), 0.0, 0.0, 30.0, 1.0);
@@ -36,21 +29,21 @@ async fn main() {
 
     for row in 0..rows {
         let count = rows - row;
-        let y = start_y + (box_h + gap) * row as f32;
+        let y = start_y + (box_h + gap) * row as Scalar;
 
-        let row_width = count as f32 * box_w + (count as f32 - 1.0) * gap;
+        let row_width = count as Scalar * box_w + (count as Scalar - 1.0) * gap;
         let x_start = -0.5 * row_width + 0.5 * box_w;
         for i in 0..count {
-            let x = x_start + i as f32 * (box_w + gap);
+            let x = x_start + i as Scalar * (box_w + gap);
             let mut rb = RigidBody::box_xy(Vec2::new(x, y), 0.0, mass, box_w, box_h);
             // Tiny perturbation to prevent perfectly symmetric stacking artifacts.
-            rb.angle = (row as f32 * 0.001) - 0.007;
+            rb.angle = (row as Scalar * 0.001) - 0.007;
             world.add(Box::new(rb));
         }
     }
 
     loop {
-        let mut frame_dt = mq::get_frame_time();
+        let mut frame_dt = mq::get_frame_time() as Scalar;
         if frame_dt > 1.0 / 30.0 {
             frame_dt = 1.0 / 30.0;
         }
@@ -60,7 +53,7 @@ async fn main() {
             accumulator -= fixed_dt;
         }
 
-        draw_world(&world, scale);
+        draw_world(&world, &mut camera);
         mq::next_frame().await;
     }
 }
@@ -1,14 +1,7 @@
 use macroquad::prelude as mq;
 
-use tiny_physics_engine::core::{Integrator, RigidBody, World};
-use tiny_physics_engine::math::vec::Vec2;
-
-#[cfg(not(feature = "visualize"))]
-compile_error!(
-    "Enable this example with: cargo run --example circle_circle_elastic --features visualize"
-);
-#[cfg(feature = "visualize")]
-use tiny_physics_engine::utils::visualize::draw_world;
+use tiny_physics_engine::prelude::*;
+use tiny_physics_engine::utils::visualize::{Camera, draw_world};
 
 #[macroquad::main("Tiny Physics — Circle/Circle Elastic")]
 async fn main() {
@@ -19,11 +12,11 @@ async fn main() {
     // Nearly elastic collision, no friction.
     world.solver.params.restitution = 1.0;
     world.solver.params.friction = 0.0;
-    world.solver.iterations = 12;
+    world.solver.params.velocity_iterations = 12;
 
-    let scale = 80.0;
+    let mut camera = Camera::new(80.0);
     let fixed_dt = 1.0 / 240.0;
-    let mut accumulator = 0.0f32;
+    let mut accumulator: Scalar = 0.0;
 
     let r = 0.45;
     let m = 1.0;
@@ -37,7 +30,7 @@ async fn main() {
     world.add(Box::new(b));
 
     loop {
-        let mut frame_dt = mq::get_frame_time();
+        let mut frame_dt = mq::get_frame_time() as Scalar;
         if frame_dt > 1.0 / 30.0 {
             frame_dt = 1.0 / 30.0;
         }
@@ -47,7 +40,7 @@ async fn main() {
             accumulator -= fixed_dt;
         }
 
-        draw_world(&world, scale);
+        draw_world(&world, &mut camera);
         mq::next_frame().await;
     }
 }
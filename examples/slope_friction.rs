@@ -1,12 +1,7 @@
 use macroquad::prelude as mq;
 
-use tiny_physics_engine::core::{Integrator, RigidBody, World};
-use tiny_physics_engine::math::vec::Vec2;
-
-#[cfg(not(feature = "visualize"))]
-compile_error!("Enable this example with: cargo run --example slope_friction --features visualize");
-#[cfg(feature = "visualize")]
-use tiny_physics_engine::utils::visualize::draw_world;
+use tiny_physics_engine::prelude::*;
+use tiny_physics_engine::utils::visualize::{Camera, draw_world};
 
 #[macroquad::main("Tiny Physics — Slope Friction")]
 async fn main() {
@@ -14,11 +9,11 @@ async fn main() {
     let mut world = World::new(gravity, Integrator::SemiImplicitEuler);
     world.solver.params.restitution = 0.0;
     world.solver.params.friction = 0.9;
-    world.solver.iterations = 18;
+    world.solver.params.velocity_iterations = 18;
 
-    let scale = 70.0;
+    let mut camera = Camera::new(70.0);
     let fixed_dt = 1.0 / 240.0;
-    let mut accumulator = 0.0f32;
+    let mut accumulator: Scalar = 0.0;
 
     // Static slope (mass=0) as a rotated box.
     let slope_angle = 0.35; // ~20°
@@ -40,7 +35,7 @@ async fn main() {
     world.add(Box::new(box2));
 
     loop {
-        let mut frame_dt = mq::get_frame_time();
+        let mut frame_dt = mq::get_frame_time() as Scalar;
         if frame_dt > 1.0 / 30.0 {
             frame_dt = 1.0 / 30.0;
         }
@@ -50,7 +45,7 @@ async fn main() {
             accumulator -= fixed_dt;
         }
 
-        draw_world(&world, scale);
+        draw_world(&world, &mut camera);
         mq::next_frame().await;
     }
 }
@@ -1,6 +1,9 @@
 use std::any::Any;
 
+use super::Material;
 use crate::core::collision::Collider2D;
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
 use crate::math::vec::Vec2;
 
 pub trait PhysicalEntity: Any {
@@ -9,27 +12,94 @@ pub trait PhysicalEntity: Any {
     fn pos_mut(&mut self) -> &mut Vec2;
     fn vel(&self) -> &Vec2;
     fn vel_mut(&mut self) -> &mut Vec2;
+    /// Force accumulated for the current step; after `World::step` it holds that step's
+    /// applied force until the next step clears it.
     fn force(&self) -> &Vec2;
     fn force_mut(&mut self) -> &mut Vec2;
-    fn inv_mass(&self) -> f32;
+    fn inv_mass(&self) -> Scalar;
+    /// Mass, or 0 for a static body. Entities that only store `inv_mass` get its reciprocal.
+    fn mass(&self) -> Scalar {
+        if self.inv_mass() > 0.0 {
+            1.0 / self.inv_mass()
+        } else {
+            0.0
+        }
+    }
     fn clear_forces(&mut self) {
         *self.force_mut() = Vec2::zero();
     }
 
     // rotation
-    fn angle(&self) -> f32;
-    fn angle_mut(&mut self) -> &mut f32;
-    fn omega(&self) -> f32;
-    fn omega_mut(&mut self) -> &mut f32;
-    fn torque(&self) -> f32;
-    fn torque_mut(&mut self) -> &mut f32;
-    fn inv_inertia(&self) -> f32;
+    fn angle(&self) -> Scalar;
+    fn angle_mut(&mut self) -> &mut Scalar;
+    fn omega(&self) -> Scalar;
+    fn omega_mut(&mut self) -> &mut Scalar;
+    fn torque(&self) -> Scalar;
+    fn torque_mut(&mut self) -> &mut Scalar;
+    fn inv_inertia(&self) -> Scalar;
+    /// Moment of inertia, or 0 if the entity can't rotate (same convention as `mass`).
+    fn inertia(&self) -> Scalar {
+        if self.inv_inertia() > 0.0 {
+            1.0 / self.inv_inertia()
+        } else {
+            0.0
+        }
+    }
     fn clear_torque(&mut self) {
         *self.torque_mut() = 0.0;
     }
+    /// Change the mass (`0` = static), re-deriving the inertia where the entity can.
+    /// Entities with fixed mass ignore this.
+    fn set_mass(&mut self, _mass: Scalar) {}
+
+    /// Body-local center of mass; the origin by default. The body turns about it: `vel` is
+    /// its velocity and the inertia is taken about it, so gravity pulls there too.
+    fn center_of_mass(&self) -> Vec2 {
+        Vec2::zero()
+    }
+    /// `center_of_mass` in world space.
+    fn world_center_of_mass(&self) -> Vec2 {
+        self.transform().apply_to_point(self.center_of_mass())
+    }
 
     // collision
     fn collider(&self) -> Option<&Collider2D> {
         None
     }
+    /// Surface material, with `defaults` (the solver's, `SolverParams::default_material`)
+    /// standing in for whatever the body doesn't set.
+    fn material(&self, defaults: Material) -> Material {
+        defaults
+    }
+    /// World-space "up" of a one-way platform: other bodies can pass through from the other
+    /// side but land on it from this side. `None` = collides normally.
+    fn one_way_normal(&self) -> Option<Vec2> {
+        None
+    }
+    /// Pose as a transform from body-local to world space (`pos`, rotated by `angle`).
+    fn transform(&self) -> Transform2D {
+        Transform2D::from_pose(*self.pos(), self.angle())
+    }
+    /// Per-body speculative margin; `None` uses `SimParams::speculative_distance`.
+    fn speculative_distance(&self) -> Option<Scalar> {
+        None
+    }
+    /// Per-body spin limit (rad/s), on top of `SimParams::max_angular_speed`.
+    fn max_angular_speed(&self) -> Option<Scalar> {
+        None
+    }
+    /// A sensor detects overlaps (see `World::trigger_events`) but has no collision
+    /// response: nothing ever pushes against it.
+    fn is_sensor(&self) -> bool {
+        false
+    }
+
+    /// Free slot for the game's handle of this body (an entity id, say), so an index from a
+    /// contact, trigger or ray hit leads back to the game object. The engine never reads it.
+    /// Entities without storage for it report 0.
+    fn user_data(&self) -> u64 {
+        0
+    }
+    /// Set `user_data`; ignored by entities without storage for it.
+    fn set_user_data(&mut self, _data: u64) {}
 }
@@ -0,0 +1,42 @@
+use crate::math::Scalar;
+
+/// Surface properties of a body, used when it touches another body.
+#[derive(Debug, Clone, Copy)]
+pub struct Material {
+    /// Coefficient of restitution (0 = no bounce, 1 = perfectly elastic).
+    pub restitution: Scalar,
+    /// Coulomb friction coefficient.
+    pub friction: Scalar,
+    /// Rolling resistance: the largest torque against rolling is this times the rolling
+    /// radius times the normal force. Only circles roll.
+    pub rolling_resistance: Scalar,
+}
+
+impl Material {
+    pub fn new(restitution: Scalar, friction: Scalar) -> Self {
+        Self {
+            restitution,
+            friction,
+            rolling_resistance: 0.0,
+        }
+    }
+
+    pub fn with_rolling_resistance(mut self, rolling_resistance: Scalar) -> Self {
+        self.rolling_resistance = rolling_resistance;
+        self
+    }
+
+    /// Material of a contact between `a` and `b`.
+    ///
+    /// Common engine conventions:
+    /// - restitution: max, so a bouncy ball still bounces on a dead floor
+    /// - friction: geometric mean, so a frictionless surface stays frictionless
+    /// - rolling resistance: max, so a ball slows down on a soft floor
+    pub fn combine(a: Material, b: Material) -> Material {
+        Material {
+            restitution: a.restitution.max(b.restitution),
+            friction: (a.friction * b.friction).sqrt(),
+            rolling_resistance: a.rolling_resistance.max(b.rolling_resistance),
+        }
+    }
+}
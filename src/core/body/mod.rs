@@ -1,7 +1,9 @@
 mod entity;
+mod material;
 mod particle;
 mod rigid_body;
 
 pub use entity::PhysicalEntity;
+pub use material::Material;
 pub use particle::Particle;
-pub use rigid_body::RigidBody;
+pub use rigid_body::{RigidBody, RigidBodyBuilder};
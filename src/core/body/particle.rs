@@ -1,28 +1,33 @@
 use super::entity::PhysicalEntity;
+use crate::math::Scalar;
 use crate::math::vec::Vec2;
 
 pub struct Particle {
     pos: Vec2,
     vel: Vec2,
     force: Vec2,
-    inv_mass: f32,
-    angle: f32,
-    omega: f32,
-    torque: f32,
-    inv_inertia: f32,
+    mass: Scalar,
+    inv_mass: Scalar,
+    angle: Scalar,
+    omega: Scalar,
+    torque: Scalar,
+    inv_inertia: Scalar,
+    user_data: u64,
 }
 
 impl Particle {
-    pub fn new(pos: Vec2, vel: Vec2, inv_mass: f32) -> Self {
+    pub fn new(pos: Vec2, vel: Vec2, inv_mass: Scalar) -> Self {
         Self {
             pos,
             vel,
             force: Vec2::zero(),
+            mass: if inv_mass > 0.0 { 1.0 / inv_mass } else { 0.0 },
             inv_mass,
             angle: 0.0,
             omega: 0.0,
             torque: 0.0,
             inv_inertia: 0.0,
+            user_data: 0,
         }
     }
 }
@@ -46,28 +51,44 @@ impl PhysicalEntity for Particle {
     fn force_mut(&mut self) -> &mut Vec2 {
         &mut self.force
     }
-    fn inv_mass(&self) -> f32 {
+    fn inv_mass(&self) -> Scalar {
         self.inv_mass
     }
-    fn angle(&self) -> f32 {
+    fn angle(&self) -> Scalar {
         self.angle
     }
-    fn angle_mut(&mut self) -> &mut f32 {
+    fn angle_mut(&mut self) -> &mut Scalar {
         &mut self.angle
     }
-    fn omega(&self) -> f32 {
+    fn omega(&self) -> Scalar {
         self.omega
     }
-    fn omega_mut(&mut self) -> &mut f32 {
+    fn omega_mut(&mut self) -> &mut Scalar {
         &mut self.omega
     }
-    fn torque(&self) -> f32 {
+    fn torque(&self) -> Scalar {
         self.torque
     }
-    fn torque_mut(&mut self) -> &mut f32 {
+    fn torque_mut(&mut self) -> &mut Scalar {
         &mut self.torque
     }
-    fn inv_inertia(&self) -> f32 {
+    fn inv_inertia(&self) -> Scalar {
         self.inv_inertia
     }
+    fn mass(&self) -> Scalar {
+        self.mass
+    }
+    fn inertia(&self) -> Scalar {
+        0.0
+    }
+    fn set_mass(&mut self, mass: Scalar) {
+        self.mass = mass.max(0.0);
+        self.inv_mass = if mass > 0.0 { 1.0 / mass } else { 0.0 };
+    }
+    fn user_data(&self) -> u64 {
+        self.user_data
+    }
+    fn set_user_data(&mut self, data: u64) {
+        self.user_data = data;
+    }
 }
@@ -1,71 +1,370 @@
-use super::PhysicalEntity;
+use super::{Material, PhysicalEntity};
 use crate::core::collision::Collider2D;
+use crate::math::Scalar;
+use crate::math::mat::Mat2;
+use crate::math::transform::Transform2D;
 use crate::math::vec::Vec2;
 
 pub struct RigidBody {
     pub pos: Vec2,
     pub vel: Vec2,
     pub force: Vec2,
-    pub inv_mass: f32,
-    pub angle: f32,
-    pub omega: f32,
-    pub torque: f32,
-    pub inv_inertia: f32,
+    /// Mass (0 = static). Kept in step with `inv_mass` by `set_mass`.
+    pub mass: Scalar,
+    pub inv_mass: Scalar,
+    pub angle: Scalar,
+    pub omega: Scalar,
+    pub torque: Scalar,
+    /// Moment of inertia about the center of mass (0 = can't rotate).
+    pub inertia: Scalar,
+    pub inv_inertia: Scalar,
+    /// Hand-set moment of inertia that `set_mass` and `set_collider` keep instead of deriving
+    /// one from the collider; `None` = derived. See `with_inertia`.
+    pub inertia_override: Option<Scalar>,
     pub collider: Option<Collider2D>,
+    /// Surface material of the body; each `None` uses the solver's value for it
+    /// (`SolverParams::restitution` etc.). See `PhysicalEntity::material`.
+    pub restitution: Option<Scalar>,
+    pub friction: Option<Scalar>,
+    pub rolling_resistance: Option<Scalar>,
+    /// Lock the orientation: the body reports zero inverse inertia, so contacts and forces
+    /// can't spin it. `inv_inertia` keeps the real value for when the lock is released.
+    pub fixed_rotation: bool,
+    /// Speculative contact margin for this body; `None` = the global
+    /// `SimParams::speculative_distance`. Fast bodies want more, resting ones less.
+    pub speculative_distance: Option<Scalar>,
+    /// Largest angular speed (rad/s) this body may leave the solver with; `None` = only the
+    /// global `SimParams::max_angular_speed`. Like that one, the sign is ignored and NaN
+    /// means no limit.
+    pub max_angular_speed: Option<Scalar>,
+    /// Makes the body a one-way platform; the normal is in body-local space (unit length)
+    /// and rotates with the body. See `PhysicalEntity::one_way_normal`.
+    pub one_way_normal: Option<Vec2>,
+    /// Makes the body a sensor (trigger volume); see `PhysicalEntity::is_sensor`.
+    pub sensor: bool,
+    /// Center of mass in body-local space (a weighted base, say); see
+    /// `PhysicalEntity::center_of_mass`. The body turns about it, so the derived inertia is
+    /// taken about it too; call `set_mass` after moving it to re-derive.
+    pub center_of_mass: Vec2,
+    /// See `PhysicalEntity::user_data`.
+    pub user_data: u64,
 }
 
 impl RigidBody {
-    pub fn new(pos: Vec2, angle: f32, inv_mass: f32, inv_inertia: f32) -> Self {
+    pub fn new(pos: Vec2, angle: Scalar, inv_mass: Scalar, inv_inertia: Scalar) -> Self {
         Self {
             pos,
             vel: Vec2::zero(),
             force: Vec2::zero(),
+            mass: reciprocal(inv_mass),
             inv_mass,
             angle,
             omega: 0.0,
             torque: 0.0,
+            inertia: reciprocal(inv_inertia),
             inv_inertia,
+            inertia_override: None,
             collider: None,
+            restitution: None,
+            friction: None,
+            rolling_resistance: None,
+            fixed_rotation: false,
+            speculative_distance: None,
+            max_angular_speed: None,
+            one_way_normal: None,
+            sensor: false,
+            center_of_mass: Vec2::zero(),
+            user_data: 0,
         }
     }
 
-    pub fn box_xy(pos: Vec2, angle: f32, mass: f32, width: f32, height: f32) -> Self {
-        let inv_mass = if mass > 0.0 { 1.0 / mass } else { 0.0 };
+    /// A body with the given collider; inertia is derived from the collider and `mass`.
+    ///
+    /// `mass = 0` makes the body static (infinite mass and inertia).
+    pub fn from_collider(pos: Vec2, angle: Scalar, mass: Scalar, collider: Collider2D) -> Self {
+        let mut body = Self::new(pos, angle, 0.0, 0.0);
+        body.collider = Some(collider);
+        body.set_mass(mass);
+        body
+    }
+
+    pub fn box_xy(pos: Vec2, angle: Scalar, mass: Scalar, width: Scalar, height: Scalar) -> Self {
         let collider = Collider2D::Box {
             half_extents: Vec2::new(width * 0.5, height * 0.5),
         };
-        let inertia = collider.inertia_about_center(mass);
-        let inv_inertia = if inertia > 0.0 { 1.0 / inertia } else { 0.0 };
-        Self {
-            pos,
-            vel: Vec2::zero(),
-            force: Vec2::zero(),
-            inv_mass,
-            angle,
-            omega: 0.0,
-            torque: 0.0,
-            inv_inertia,
-            collider: Some(collider),
+        Self::from_collider(pos, angle, mass, collider)
+    }
+
+    pub fn circle(pos: Vec2, angle: Scalar, mass: Scalar, radius: Scalar) -> Self {
+        Self::from_collider(pos, angle, mass, Collider2D::Circle { radius })
+    }
+
+    /// A body made of several shapes, each placed relative to the body origin.
+    ///
+    /// Mass is spread over the parts by area; the body rotates about its origin.
+    pub fn compound(
+        pos: Vec2,
+        angle: Scalar,
+        mass: Scalar,
+        parts: Vec<(Transform2D, Collider2D)>,
+    ) -> Self {
+        Self::from_collider(pos, angle, mass, Collider2D::Compound { parts })
+    }
+
+    /// Set the mass and re-derive the inertia about the center of mass from the collider (no
+    /// collider = no rotational inertia), unless it's overridden. `mass = 0` makes the body
+    /// static.
+    pub fn set_mass(&mut self, mass: Scalar) {
+        self.mass = mass.max(0.0);
+        self.inv_mass = reciprocal(self.mass);
+        self.inertia = match self.inertia_override {
+            // A static body (`World::set_static`) stays unable to turn, override or not.
+            Some(inertia) if self.mass > 0.0 => inertia,
+            _ => self
+                .collider
+                .as_ref()
+                .map_or(0.0, |c| c.inertia_about(self.center_of_mass, self.mass)),
+        };
+        self.inv_inertia = reciprocal(self.inertia);
+    }
+
+    /// Override the moment of inertia the collider would give, e.g. for a small but heavy
+    /// flywheel. It's kept through later `set_mass` and collider changes, but a static body
+    /// (mass 0) doesn't rotate either way. `0` means the body can't rotate (infinite inertia,
+    /// like a static body), not a division by zero.
+    pub fn with_inertia(mut self, inertia: Scalar) -> Self {
+        self.set_inertia(Some(inertia));
+        self
+    }
+
+    /// Override the moment of inertia (see `with_inertia`), or go back to deriving it from
+    /// the collider with `None`.
+    ///
+    /// After changing a body that is already in a world, call `World::invalidate_body`.
+    pub fn set_inertia(&mut self, inertia: Option<Scalar>) {
+        self.inertia_override = inertia.map(|i| i.max(0.0));
+        self.set_mass(self.mass);
+    }
+
+    /// Make the collider a circle of radius `r`, keeping the mass (inertia is recomputed).
+    ///
+    /// After resizing a body that is already in a world, call `World::invalidate_body`.
+    pub fn set_radius(&mut self, r: Scalar) {
+        self.set_collider(Collider2D::Circle { radius: r });
+    }
+
+    /// Make the collider a box with these half extents, keeping the mass.
+    pub fn set_half_extents(&mut self, half_extents: Vec2) {
+        self.set_collider(Collider2D::Box { half_extents });
+    }
+
+    /// Scale the collider by `factor` about the body origin, keeping the mass.
+    pub fn rescale(&mut self, factor: Scalar) {
+        if let Some(collider) = self.collider.take() {
+            self.set_collider(collider.scaled(factor));
+        }
+    }
+
+    /// Swap in a new collider, keeping the mass (inertia is recomputed).
+    pub fn set_collider(&mut self, collider: Collider2D) {
+        self.collider = Some(collider);
+        self.set_mass(self.mass);
+    }
+
+    /// Lock or unlock the orientation. Locking also stops any current spin.
+    pub fn set_fixed_rotation(&mut self, fixed: bool) {
+        self.fixed_rotation = fixed;
+        if fixed {
+            self.omega = 0.0;
         }
     }
+}
+
+/// Chained construction of a `RigidBody`, so callers don't have to patch public fields after
+/// `new`. Inverse mass and inertia are computed from the mass and collider in `build`,
+/// unless `inertia` or `inv_inertia` overrides the inertia.
+pub struct RigidBodyBuilder {
+    pos: Vec2,
+    angle: Scalar,
+    vel: Vec2,
+    omega: Scalar,
+    mass: Scalar,
+    /// `None` = derived from the collider in `build`.
+    inertia: Option<Scalar>,
+    collider: Option<Collider2D>,
+    restitution: Option<Scalar>,
+    friction: Option<Scalar>,
+    rolling_resistance: Option<Scalar>,
+    fixed_rotation: bool,
+    speculative_distance: Option<Scalar>,
+    max_angular_speed: Option<Scalar>,
+    one_way_normal: Option<Vec2>,
+    sensor: bool,
+    center_of_mass: Vec2,
+    user_data: u64,
+}
+
+impl Default for RigidBodyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
-    pub fn circle(pos: Vec2, angle: f32, mass: f32, radius: f32) -> Self {
-        let inv_mass = if mass > 0.0 { 1.0 / mass } else { 0.0 };
-        let collider = Collider2D::Circle { radius };
-        let inertia = collider.inertia_about_center(mass);
-        let inv_inertia = if inertia > 0.0 { 1.0 / inertia } else { 0.0 };
+impl RigidBodyBuilder {
+    /// A 1 kg body at the origin with no collider.
+    pub fn new() -> Self {
         Self {
-            pos,
+            pos: Vec2::zero(),
+            angle: 0.0,
             vel: Vec2::zero(),
-            force: Vec2::zero(),
-            inv_mass,
-            angle,
             omega: 0.0,
-            torque: 0.0,
-            inv_inertia,
-            collider: Some(collider),
+            mass: 1.0,
+            inertia: None,
+            collider: None,
+            restitution: None,
+            friction: None,
+            rolling_resistance: None,
+            fixed_rotation: false,
+            speculative_distance: None,
+            max_angular_speed: None,
+            one_way_normal: None,
+            sensor: false,
+            center_of_mass: Vec2::zero(),
+            user_data: 0,
         }
     }
+
+    pub fn position(mut self, pos: Vec2) -> Self {
+        self.pos = pos;
+        self
+    }
+
+    pub fn angle(mut self, angle: Scalar) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    pub fn velocity(mut self, vel: Vec2) -> Self {
+        self.vel = vel;
+        self
+    }
+
+    pub fn angular_velocity(mut self, omega: Scalar) -> Self {
+        self.omega = omega;
+        self
+    }
+
+    /// `0` makes the body static.
+    pub fn mass(mut self, mass: Scalar) -> Self {
+        self.mass = mass;
+        self
+    }
+
+    /// Override the moment of inertia instead of deriving it from the collider (see
+    /// `RigidBody::with_inertia`); `0` can't rotate.
+    pub fn inertia(mut self, inertia: Scalar) -> Self {
+        self.inertia = Some(inertia);
+        self
+    }
+
+    /// `inertia` given as its inverse; `0` can't rotate.
+    pub fn inv_inertia(self, inv_inertia: Scalar) -> Self {
+        self.inertia(reciprocal(inv_inertia))
+    }
+
+    pub fn box_shape(self, width: Scalar, height: Scalar) -> Self {
+        self.collider(Collider2D::Box {
+            half_extents: Vec2::new(width * 0.5, height * 0.5),
+        })
+    }
+
+    pub fn circle(self, radius: Scalar) -> Self {
+        self.collider(Collider2D::Circle { radius })
+    }
+
+    /// Any collider, e.g. a compound or one built with `Collider2D::with_offset`.
+    pub fn collider(mut self, collider: Collider2D) -> Self {
+        self.collider = Some(collider);
+        self
+    }
+
+    pub fn restitution(mut self, restitution: Scalar) -> Self {
+        self.restitution = Some(restitution);
+        self
+    }
+
+    pub fn friction(mut self, friction: Scalar) -> Self {
+        self.friction = Some(friction);
+        self
+    }
+
+    /// See `Material::rolling_resistance`.
+    pub fn rolling_resistance(mut self, rolling_resistance: Scalar) -> Self {
+        self.rolling_resistance = Some(rolling_resistance);
+        self
+    }
+
+    /// Lock the orientation (see `RigidBody::fixed_rotation`).
+    pub fn fixed_rotation(mut self, fixed: bool) -> Self {
+        self.fixed_rotation = fixed;
+        self
+    }
+
+    /// Per-body speculative margin (see `RigidBody::speculative_distance`).
+    pub fn speculative_distance(mut self, distance: Scalar) -> Self {
+        self.speculative_distance = Some(distance);
+        self
+    }
+
+    /// Per-body spin limit (see `RigidBody::max_angular_speed`).
+    pub fn max_angular_speed(mut self, omega: Scalar) -> Self {
+        self.max_angular_speed = Some(omega);
+        self
+    }
+
+    /// Make the body a one-way platform (see `RigidBody::one_way_normal`).
+    pub fn one_way(mut self, normal: Vec2) -> Self {
+        self.one_way_normal = Some(normal);
+        self
+    }
+
+    /// Make the body a sensor (see `RigidBody::sensor`).
+    pub fn sensor(mut self, sensor: bool) -> Self {
+        self.sensor = sensor;
+        self
+    }
+
+    /// Move the center of mass off the origin (see `RigidBody::center_of_mass`).
+    pub fn center_of_mass(mut self, offset: Vec2) -> Self {
+        self.center_of_mass = offset;
+        self
+    }
+
+    /// Tag the body with the game's handle for it (see `PhysicalEntity::user_data`).
+    pub fn user_data(mut self, data: u64) -> Self {
+        self.user_data = data;
+        self
+    }
+
+    pub fn build(self) -> RigidBody {
+        let mut body = RigidBody::new(self.pos, self.angle, 0.0, 0.0);
+        body.collider = self.collider;
+        body.center_of_mass = self.center_of_mass;
+        body.inertia_override = self.inertia.map(|i| i.max(0.0));
+        body.set_mass(self.mass);
+        body.vel = self.vel;
+        body.omega = self.omega;
+        body.set_fixed_rotation(self.fixed_rotation);
+        body.speculative_distance = self.speculative_distance;
+        body.max_angular_speed = self.max_angular_speed;
+        body.one_way_normal = self.one_way_normal;
+        body.sensor = self.sensor;
+        body.user_data = self.user_data;
+        body.restitution = self.restitution;
+        body.friction = self.friction;
+        body.rolling_resistance = self.rolling_resistance;
+        body
+    }
 }
 
 impl PhysicalEntity for RigidBody {
@@ -87,31 +386,83 @@ impl PhysicalEntity for RigidBody {
     fn force_mut(&mut self) -> &mut Vec2 {
         &mut self.force
     }
-    fn inv_mass(&self) -> f32 {
+    fn inv_mass(&self) -> Scalar {
         self.inv_mass
     }
-    fn angle(&self) -> f32 {
+    fn mass(&self) -> Scalar {
+        self.mass
+    }
+    fn angle(&self) -> Scalar {
         self.angle
     }
-    fn angle_mut(&mut self) -> &mut f32 {
+    fn angle_mut(&mut self) -> &mut Scalar {
         &mut self.angle
     }
-    fn omega(&self) -> f32 {
+    fn omega(&self) -> Scalar {
         self.omega
     }
-    fn omega_mut(&mut self) -> &mut f32 {
+    fn omega_mut(&mut self) -> &mut Scalar {
         &mut self.omega
     }
-    fn torque(&self) -> f32 {
+    fn torque(&self) -> Scalar {
         self.torque
     }
-    fn torque_mut(&mut self) -> &mut f32 {
+    fn torque_mut(&mut self) -> &mut Scalar {
         &mut self.torque
     }
-    fn inv_inertia(&self) -> f32 {
+    fn inv_inertia(&self) -> Scalar {
+        if self.fixed_rotation {
+            return 0.0;
+        }
         self.inv_inertia
     }
+    fn inertia(&self) -> Scalar {
+        if self.fixed_rotation {
+            return 0.0;
+        }
+        self.inertia
+    }
     fn collider(&self) -> Option<&Collider2D> {
         self.collider.as_ref()
     }
+    fn material(&self, defaults: Material) -> Material {
+        Material::new(
+            self.restitution.unwrap_or(defaults.restitution),
+            self.friction.unwrap_or(defaults.friction),
+        )
+        .with_rolling_resistance(
+            self.rolling_resistance
+                .unwrap_or(defaults.rolling_resistance),
+        )
+    }
+    fn speculative_distance(&self) -> Option<Scalar> {
+        self.speculative_distance
+    }
+    fn max_angular_speed(&self) -> Option<Scalar> {
+        self.max_angular_speed
+    }
+    fn set_mass(&mut self, mass: Scalar) {
+        RigidBody::set_mass(self, mass);
+    }
+    fn center_of_mass(&self) -> Vec2 {
+        self.center_of_mass
+    }
+    fn one_way_normal(&self) -> Option<Vec2> {
+        self.one_way_normal
+            .map(|n| Mat2::rotation(self.angle).mul_vec2(n))
+    }
+    fn is_sensor(&self) -> bool {
+        self.sensor
+    }
+    fn user_data(&self) -> u64 {
+        self.user_data
+    }
+    fn set_user_data(&mut self, data: u64) {
+        self.user_data = data;
+    }
+}
+
+/// `1 / x`, with 0 standing in for "infinite" both ways.
+fn reciprocal(x: Scalar) -> Scalar {
+    if x > 0.0 { 1.0 / x } else { 0.0 }
 }
@@ -0,0 +1,183 @@
+use super::World;
+use super::collision::{Collider2D, ShapeHit};
+use crate::math::Scalar;
+use crate::math::vec::Vec2;
+
+/// What a `CharacterController::move_and_slide` did.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MoveResult {
+    /// How far the body actually moved.
+    pub moved: Vec2,
+    /// Whether it ended up standing on walkable ground (see `CharacterController::max_slope`).
+    pub grounded: bool,
+}
+
+/// Moves a body the way a game character walks: by a desired displacement, sliding along
+/// walls, stepping up small ledges, and reporting whether it stands on the ground.
+///
+/// Built on shape casts rather than the solver: the body is swept and placed directly
+/// (`World::set_transform`), so it should be static (mass 0), like a `PathMover` platform.
+/// Its collider is swept at angle 0. Gravity is up to the caller: add it to the
+/// displacement while the body isn't grounded.
+#[derive(Debug, Clone, Copy)]
+pub struct CharacterController {
+    /// Which way is up (unit length): ground faces it, steps rise along it.
+    pub up: Vec2,
+    /// Gap (meters) kept between the body and what it moves against, so the next move
+    /// doesn't start out touching.
+    pub skin: Scalar,
+    /// Tallest ledge (meters) the body steps up onto instead of stopping against it.
+    pub max_step_height: Scalar,
+    /// Steepest ground (radians from level) the body can stand on; anything steeper is a
+    /// wall, which it slides along but can't climb.
+    pub max_slope: Scalar,
+    /// How far below the body (past `skin`) the ground probe reaches.
+    pub ground_probe: Scalar,
+    /// Most sweeps per move: each hit uses one, and the rest of the move slides along the
+    /// surface.
+    pub max_slides: usize,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            up: Vec2::new(0.0, 1.0),
+            skin: 0.01,
+            max_step_height: 0.25,
+            max_slope: 0.8,
+            ground_probe: 0.05,
+            max_slides: 4,
+        }
+    }
+}
+
+/// Shape casts of one body's collider against everything but itself and sensors.
+struct Sweep<'a> {
+    world: &'a World,
+    body: usize,
+    collider: &'a Collider2D,
+}
+
+impl Sweep<'_> {
+    fn cast(&self, from: Vec2, to: Vec2) -> Option<ShapeHit> {
+        let entities = &self.world.entities;
+        self.world
+            .shape_cast_filtered(self.collider, from, to, |i| {
+                i != self.body && !entities[i].is_sensor()
+            })
+    }
+}
+
+impl CharacterController {
+    /// Move entity `body` by `displacement`, as far as the world lets it.
+    ///
+    /// The move slides along whatever it hits. Blocked by a wall while on the ground, it
+    /// also tries to rise by up to `max_step_height`, go over, and land on top; it keeps
+    /// whichever gets further. A body without a collider doesn't move.
+    pub fn move_and_slide(&self, world: &mut World, body: usize, displacement: Vec2) -> MoveResult {
+        let Some(e) = world.entities.get(body) else {
+            return MoveResult::default();
+        };
+        let Some(collider) = e.collider() else {
+            return MoveResult::default();
+        };
+        let (start, angle) = (*e.pos(), e.angle());
+        let sweep = Sweep {
+            world,
+            body,
+            collider,
+        };
+
+        let (mut end, wall) = self.slide(&sweep, start, displacement);
+        if wall
+            && self.ground(&sweep, start)
+            && let Some(stepped) = self.step_up(&sweep, start, displacement)
+        {
+            let along = self.horizontal(displacement);
+            if (stepped - start).dot(along) > (end - start).dot(along) + self.skin {
+                end = stepped;
+            }
+        }
+        let grounded = self.ground(&sweep, end);
+
+        world.set_transform(body, end, angle);
+        MoveResult {
+            moved: end - start,
+            grounded,
+        }
+    }
+
+    /// Whether there's walkable ground within the probe's reach below `pos`.
+    fn ground(&self, sweep: &Sweep, pos: Vec2) -> bool {
+        let reach = self.skin + self.ground_probe;
+        sweep
+            .cast(pos, pos - self.up * reach)
+            .is_some_and(|hit| self.walkable(hit.normal))
+    }
+
+    fn walkable(&self, normal: Vec2) -> bool {
+        normal.dot(self.up) >= self.max_slope.cos()
+    }
+
+    /// The part of `v` across `up`.
+    fn horizontal(&self, v: Vec2) -> Vec2 {
+        v - self.up * v.dot(self.up)
+    }
+
+    /// Move from `from` along `motion` until something is `skin` away: where it stops, and
+    /// what it hit.
+    fn advance(&self, sweep: &Sweep, from: Vec2, motion: Vec2) -> (Vec2, Option<ShapeHit>) {
+        let Some(dir) = motion.try_normalize() else {
+            return (from, None);
+        };
+        // Cast `skin` further, so stopping short by `skin` still covers the whole move.
+        let reach = motion.length() + self.skin;
+        let Some(hit) = sweep.cast(from, from + dir * reach) else {
+            return (from + motion, None);
+        };
+        // Back off until the surface is `skin` away along its normal, not along the move: a
+        // shallow approach has to back off further.
+        let approach = -dir.dot(hit.normal);
+        let back_off = if approach > Scalar::EPSILON {
+            self.skin / approach
+        } else {
+            0.0
+        };
+        let travel = (hit.toi * reach - back_off).clamp(0.0, motion.length());
+        (from + dir * travel, Some(hit))
+    }
+
+    /// Move along `motion`, sliding the rest of it along each surface hit. Returns where it
+    /// ends and whether a wall (too steep to walk on) got in the way.
+    fn slide(&self, sweep: &Sweep, from: Vec2, motion: Vec2) -> (Vec2, bool) {
+        let (mut pos, mut remaining, mut wall) = (from, motion, false);
+        for _ in 0..self.max_slides {
+            let (next, hit) = self.advance(sweep, pos, remaining);
+            remaining = remaining - (next - pos);
+            pos = next;
+            let Some(hit) = hit else {
+                break;
+            };
+            let mut normal = hit.normal;
+            if !self.walkable(normal) && normal.dot(self.up) >= 0.0 {
+                // Walls and steep slopes push straight back: sliding up them would climb.
+                wall = true;
+                normal = self.horizontal(normal).try_normalize().unwrap_or(normal);
+            }
+            let into = remaining.dot(normal);
+            if into < 0.0 {
+                remaining = remaining - normal * into;
+            }
+        }
+        (pos, wall)
+    }
+
+    /// Rise by up to `max_step_height`, move across, and settle back down onto walkable
+    /// ground. `None` if there's nothing to land on within the height risen.
+    fn step_up(&self, sweep: &Sweep, from: Vec2, displacement: Vec2) -> Option<Vec2> {
+        let (risen, _) = self.advance(sweep, from, self.up * self.max_step_height);
+        let (over, _) = self.slide(sweep, risen, self.horizontal(displacement));
+        let (landed, hit) = self.advance(sweep, over, from - risen);
+        hit.filter(|hit| self.walkable(hit.normal)).map(|_| landed)
+    }
+}
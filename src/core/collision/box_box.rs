@@ -1,70 +1,157 @@
 use super::manifold::ContactPoint;
+use crate::math::Scalar;
 use crate::math::mat::Mat2;
+use crate::math::transform::Transform2D;
 use crate::math::vec::Vec2;
 
-fn clip_segment_to_line(v_in: &[Vec2], normal: Vec2, offset: f32) -> Vec<Vec2> {
-    let mut v_out = Vec::new();
-    if v_in.len() < 2 {
-        return v_out;
-    }
+/// Box faces in local space: 0 = +x, 1 = -x, 2 = +y, 3 = -y.
+///
+/// Faces double as edge indices for the incident edge.
+const FACE_POS_X: u32 = 0;
+const FACE_NEG_X: u32 = 1;
+const FACE_POS_Y: u32 = 2;
+const FACE_NEG_Y: u32 = 3;
+
+/// Which feature produced a clipped point: one of the two incident edge vertices,
+/// or the intersection with one of the two reference side planes.
+const INCIDENT_VERTEX_0: u32 = 0;
+const INCIDENT_VERTEX_1: u32 = 1;
+const SIDE_PLANE_1: u32 = 2;
+const SIDE_PLANE_2: u32 = 3;
+
+/// Reference-axis hysteresis (Box2D Lite): a later axis only takes over if its separation
+/// beats the current best by more than this relative / absolute (times half extent) margin.
+/// Without it two flat boxes flip between reference faces from step to step, which changes
+/// every feature id and throws away the warm start.
+const AXIS_RELATIVE_TOLERANCE: Scalar = 0.95;
+const AXIS_ABSOLUTE_TOLERANCE: Scalar = 0.01;
+
+/// Largest tilt (radians) between the incident edge and the reference face for which both
+/// clipped points are kept, even if one is beyond the speculative distance.
+const FLAT_CONTACT_MAX_TILT: Scalar = 0.1;
 
-    let d0 = normal.dot(v_in[0]) - offset;
-    let d1 = normal.dot(v_in[1]) - offset;
+/// How far (meters) past a side plane an incident vertex may lie and still give the clipped
+/// point its feature id. On equal-width stacked boxes the vertices sit right on the side
+/// planes; without this a hair of sideways drift swaps a point's id between the vertex and
+/// the plane, which throws away its warm start.
+const CLIP_TOLERANCE: Scalar = 1e-3;
+
+/// Pack the features that generated a contact point into one id (Box2D-style).
+///
+/// The id is stable as long as the same reference face / incident edge / vertex
+/// stay in contact, which is exactly what warm starting needs.
+fn feature_id(ref_idx: u32, ref_face: u32, incident_edge: u32, vertex: u32) -> u32 {
+    (ref_idx << 12) | (ref_face << 8) | (incident_edge << 4) | vertex
+}
+
+#[derive(Clone, Copy)]
+struct ClipVertex {
+    v: Vec2,
+    /// One of `INCIDENT_VERTEX_*` / `SIDE_PLANE_*`.
+    feature: u32,
+}
+
+/// Clip the segment `v_in` to the half-plane `normal . v <= offset`; `None` if it's all
+/// outside.
+fn clip_segment_to_line(
+    v_in: [ClipVertex; 2],
+    normal: Vec2,
+    offset: Scalar,
+    plane_feature: u32,
+) -> Option<[ClipVertex; 2]> {
+    let d0 = normal.dot(v_in[0].v) - offset;
+    let d1 = normal.dot(v_in[1].v) - offset;
 
     if d0 <= 0.0 && d1 <= 0.0 {
-        v_out.push(v_in[0]);
-        v_out.push(v_in[1]);
+        Some(v_in)
     } else if d0 > 0.0 && d1 > 0.0 {
         // both outside
+        None
     } else {
         let t = d0 / (d0 - d1);
-        let intersect = v_in[0] + (v_in[1] - v_in[0]) * t;
+        // The new point was created by this side plane, so it takes the plane's feature,
+        // unless the vertex it replaces was only just outside (see `CLIP_TOLERANCE`).
+        let outside = if d0 > 0.0 {
+            (v_in[0], d0)
+        } else {
+            (v_in[1], d1)
+        };
+        let intersect = ClipVertex {
+            v: v_in[0].v + (v_in[1].v - v_in[0].v) * t,
+            feature: if outside.1 <= CLIP_TOLERANCE {
+                outside.0.feature
+            } else {
+                plane_feature
+            },
+        };
         if d0 <= 0.0 {
-            v_out.push(v_in[0]);
-            v_out.push(intersect);
+            Some([v_in[0], intersect])
         } else {
-            v_out.push(intersect);
-            v_out.push(v_in[1]);
+            Some([intersect, v_in[1]])
         }
     }
-    v_out
 }
 
-fn compute_incident_edge(center: Vec2, rot: &Mat2, half: Vec2, ref_normal: Vec2) -> [Vec2; 2] {
+/// Returns the incident edge in world space together with its face index.
+fn compute_incident_edge(
+    center: Vec2,
+    rot: &Mat2,
+    half: Vec2,
+    ref_normal: Vec2,
+) -> ([Vec2; 2], u32) {
     let inv_rot = rot.transpose();
     let local_n = inv_rot.mul_vec2(ref_normal);
 
-    let (v1_local, v2_local) = if local_n.x.abs() > local_n.y.abs() {
+    let (v1_local, v2_local, edge) = if local_n.x.abs() > local_n.y.abs() {
         if local_n.x > 0.0 {
-            (Vec2::new(-half.x, half.y), Vec2::new(-half.x, -half.y))
+            (
+                Vec2::new(-half.x, half.y),
+                Vec2::new(-half.x, -half.y),
+                FACE_NEG_X,
+            )
         } else {
-            (Vec2::new(half.x, -half.y), Vec2::new(half.x, half.y))
+            (
+                Vec2::new(half.x, -half.y),
+                Vec2::new(half.x, half.y),
+                FACE_POS_X,
+            )
         }
     } else {
         if local_n.y > 0.0 {
-            (Vec2::new(half.x, -half.y), Vec2::new(-half.x, -half.y))
+            (
+                Vec2::new(half.x, -half.y),
+                Vec2::new(-half.x, -half.y),
+                FACE_NEG_Y,
+            )
         } else {
-            (Vec2::new(-half.x, half.y), Vec2::new(half.x, half.y))
+            (
+                Vec2::new(-half.x, half.y),
+                Vec2::new(half.x, half.y),
+                FACE_POS_Y,
+            )
         }
     };
 
-    [
-        rot.mul_vec2(v1_local) + center,
-        rot.mul_vec2(v2_local) + center,
-    ]
+    (
+        [
+            rot.mul_vec2(v1_local) + center,
+            rot.mul_vec2(v2_local) + center,
+        ],
+        edge,
+    )
 }
 
+/// Box vs. box contact: the normal (A→B), with the contact points pushed onto `out`.
 pub fn detect(
-    center_a: Vec2,
-    angle_a: f32,
+    xf_a: &Transform2D,
     half_a: Vec2,
-    center_b: Vec2,
-    angle_b: f32,
+    xf_b: &Transform2D,
     half_b: Vec2,
-    speculative_distance: f32,
-) -> Option<(Vec2, Vec<ContactPoint>)> {
-    let rot_a = Mat2::rotation(angle_a);
-    let rot_b = Mat2::rotation(angle_b);
+    speculative_distance: Scalar,
+    out: &mut Vec<ContactPoint>,
+) -> Option<Vec2> {
+    let (center_a, rot_a) = (xf_a.translation, xf_a.rotation);
+    let (center_b, rot_b) = (xf_b.translation, xf_b.rotation);
     let rot_a_t = rot_a.transpose();
     let rot_b_t = rot_b.transpose();
 
@@ -101,45 +188,56 @@ pub fn detect(
         return None;
     }
 
-    let (best_axis, _) = [(0, face_a_x), (1, face_a_y), (2, face_b_x), (3, face_b_y)]
+    let axes = [
+        (0, face_a_x, half_a.x),
+        (1, face_a_y, half_a.y),
+        (2, face_b_x, half_b.x),
+        (3, face_b_y, half_b.y),
+    ];
+    let (best_axis, _) = axes[1..]
         .iter()
-        .fold((0, f32::NEG_INFINITY), |(idx, max), &(i, sep)| {
-            if sep > max { (i, sep) } else { (idx, max) }
+        .fold((0, face_a_x), |(idx, best), &(i, sep, h)| {
+            if sep > AXIS_RELATIVE_TOLERANCE * best + AXIS_ABSOLUTE_TOLERANCE * h {
+                (i, sep)
+            } else {
+                (idx, best)
+            }
         });
 
-    let (ref_idx, ref_normal, inc_half, inc_center, inc_rot) = if best_axis < 2 {
-        let normal = if best_axis == 0 {
+    let (ref_idx, ref_face, ref_normal, inc_half, inc_center, inc_rot) = if best_axis < 2 {
+        let (face, normal) = if best_axis == 0 {
             if dp_a.x > 0.0 {
-                Vec2::new(rot_a.m00, rot_a.m10)
+                (FACE_POS_X, Vec2::new(rot_a.m00, rot_a.m10))
             } else {
-                Vec2::new(-rot_a.m00, -rot_a.m10)
+                (FACE_NEG_X, Vec2::new(-rot_a.m00, -rot_a.m10))
             }
         } else {
             if dp_a.y > 0.0 {
-                Vec2::new(rot_a.m01, rot_a.m11)
+                (FACE_POS_Y, Vec2::new(rot_a.m01, rot_a.m11))
             } else {
-                Vec2::new(-rot_a.m01, -rot_a.m11)
+                (FACE_NEG_Y, Vec2::new(-rot_a.m01, -rot_a.m11))
             }
         };
-        (0, normal, half_b, center_b, &rot_b)
+        (0, face, normal, half_b, center_b, &rot_b)
     } else {
-        let normal = if best_axis == 2 {
+        let (face, normal) = if best_axis == 2 {
             if dp_b.x > 0.0 {
-                Vec2::new(-rot_b.m00, -rot_b.m10)
+                (FACE_NEG_X, Vec2::new(-rot_b.m00, -rot_b.m10))
             } else {
-                Vec2::new(rot_b.m00, rot_b.m10)
+                (FACE_POS_X, Vec2::new(rot_b.m00, rot_b.m10))
             }
         } else {
             if dp_b.y > 0.0 {
-                Vec2::new(-rot_b.m01, -rot_b.m11)
+                (FACE_NEG_Y, Vec2::new(-rot_b.m01, -rot_b.m11))
             } else {
-                Vec2::new(rot_b.m01, rot_b.m11)
+                (FACE_POS_Y, Vec2::new(rot_b.m01, rot_b.m11))
             }
         };
-        (1, normal, half_a, center_a, &rot_a)
+        (1, face, normal, half_a, center_a, &rot_a)
     };
 
-    let incident_edge = compute_incident_edge(inc_center, inc_rot, inc_half, ref_normal);
+    let (incident_edge, inc_edge) =
+        compute_incident_edge(inc_center, inc_rot, inc_half, ref_normal);
 
     let (ref_center, ref_rot, ref_h) = if ref_idx == 0 {
         (center_a, &rot_a, half_a)
@@ -149,8 +247,14 @@ pub fn detect(
     let ref_rot_t = ref_rot.transpose();
 
     let incident_local = [
-        ref_rot_t.mul_vec2(incident_edge[0] - ref_center),
-        ref_rot_t.mul_vec2(incident_edge[1] - ref_center),
+        ClipVertex {
+            v: ref_rot_t.mul_vec2(incident_edge[0] - ref_center),
+            feature: INCIDENT_VERTEX_0,
+        },
+        ClipVertex {
+            v: ref_rot_t.mul_vec2(incident_edge[1] - ref_center),
+            feature: INCIDENT_VERTEX_1,
+        },
     ];
     let ref_normal_local = ref_rot_t.mul_vec2(ref_normal);
 
@@ -173,37 +277,37 @@ pub fn detect(
             )
         };
 
-    let clip1 = clip_segment_to_line(&incident_local, side_n1, off1);
-    if clip1.len() < 2 {
-        return None;
-    }
+    let clip1 = clip_segment_to_line(incident_local, side_n1, off1, SIDE_PLANE_1)?;
+    let clip2 = clip_segment_to_line(clip1, side_n2, off2, SIDE_PLANE_2)?;
 
-    let clip2 = clip_segment_to_line(&clip1, side_n2, off2);
-    if clip2.len() < 2 {
+    let separations = clip2.map(|cv| ref_normal_local.dot(cv.v) - front_off);
+    // Allow small separation for speculative contacts.
+    let in_range = separations.map(|sep| sep <= speculative_distance);
+    if !in_range[0] && !in_range[1] {
         return None;
     }
+    // A resting box tilted by a hair can have one end just beyond the speculative distance.
+    // Keeping that end as a (separated) speculative point gives the manifold its second
+    // point, so the box can't rock on the one that's left.
+    let edge = clip2[1].v - clip2[0].v;
+    let tilt = ref_normal_local.dot(edge).abs();
+    let flat = tilt <= FLAT_CONTACT_MAX_TILT.sin() * edge.length();
 
-    let mut contacts = Vec::new();
-    for v_local in clip2 {
-        let sep = ref_normal_local.dot(v_local) - front_off;
-        // Allow small separation for speculative contacts.
-        if sep <= speculative_distance {
-            contacts.push(ContactPoint {
-                point: ref_rot.mul_vec2(v_local) + ref_center,
+    for (cv, (sep, in_range)) in clip2.iter().zip(separations.into_iter().zip(in_range)) {
+        if in_range || flat {
+            out.push(ContactPoint {
+                point: ref_rot.mul_vec2(cv.v) + ref_center,
                 // sep>0 => separated (speculative), sep<0 => overlapping
                 penetration: -sep,
+                id: feature_id(ref_idx, ref_face, inc_edge, cv.feature),
             });
         }
     }
 
-    if contacts.is_empty() {
-        return None;
-    }
-
     let final_normal = if ref_idx == 0 {
         ref_normal
     } else {
         -ref_normal
     };
-    Some((final_normal, contacts))
+    Some(final_normal)
 }
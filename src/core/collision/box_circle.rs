@@ -1,18 +1,27 @@
 use super::manifold::ContactPoint;
-use crate::math::mat::Mat2;
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
 use crate::math::vec::Vec2;
 
+/// Box vs. circle contact.
+///
+/// Circle contacts are always single-point: a circle touches a convex shape in exactly one
+/// place, so one point carries the full normal response (a circle can't rock on one point).
+///
+/// The normal points from the box to the circle, and the contact point sits on the circle's
+/// surface (`circle_center - normal * radius`). For a speculative (separated) contact this is
+/// the point that will actually hit the box, so the solver's bias and lever arms act there
+/// rather than at the box surface.
 pub fn detect(
-    box_center: Vec2,
-    box_angle: f32,
+    box_xf: &Transform2D,
     half_extents: Vec2,
     circle_center: Vec2,
-    radius: f32,
-    speculative_distance: f32,
+    radius: Scalar,
+    speculative_distance: Scalar,
 ) -> Option<(Vec2, ContactPoint)> {
-    let rot = Mat2::rotation(box_angle);
+    let rot = box_xf.rotation;
     let inv_rot = rot.transpose();
-    let delta_world = circle_center - box_center;
+    let delta_world = circle_center - box_xf.translation;
     let delta_local = inv_rot.mul_vec2(delta_world);
 
     let closest_local = Vec2::new(
@@ -28,10 +37,10 @@ pub fn detect(
         return None;
     }
 
-    let (normal_local, contact_local, penetration) = if dist_sq > 1e-12 {
+    let (normal_local, penetration) = if dist_sq > 1e-12 {
         let dist = dist_sq.sqrt();
         // penetration can be negative => separated but within speculative distance
-        (diff / dist, closest_local, radius - dist)
+        (diff / dist, radius - dist)
     } else {
         let dx = half_extents.x - delta_local.x.abs();
         let dy = half_extents.y - delta_local.y.abs();
@@ -40,27 +49,28 @@ pub fn detect(
             let sign_x = delta_local.x.signum();
             (
                 Vec2::new(sign_x, 0.0),
-                Vec2::new(sign_x * half_extents.x, delta_local.y),
                 radius + dx, // inside box => overlap
             )
         } else {
             let sign_y = delta_local.y.signum();
             (
                 Vec2::new(0.0, sign_y),
-                Vec2::new(delta_local.x, sign_y * half_extents.y),
                 radius + dy, // inside box => overlap
             )
         }
     };
 
     let normal_world = rot.mul_vec2(normal_local);
-    let contact_world = rot.mul_vec2(contact_local) + box_center;
+    // Deepest point of the circle along -normal, i.e. the point on the circle facing the box.
+    let contact_world = circle_center - normal_world * radius;
 
     Some((
         normal_world,
         ContactPoint {
             point: contact_world,
             penetration,
+            // A box/circle pair only ever produces one point.
+            id: 0,
         },
     ))
 }
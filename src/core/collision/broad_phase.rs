@@ -1,12 +1,17 @@
-use super::Aabb;
+use super::{Aabb, Bvh, Collider2D};
 use crate::core::body::PhysicalEntity;
 use crate::core::params::SimParams;
+use crate::math::Scalar;
 use crate::math::vec::Vec2;
 
-fn entity_aabb(e: &dyn PhysicalEntity, params: SimParams) -> Aabb {
+/// World-space AABB of an entity, grown by `margin` on every side.
+///
+/// The broad phase passes the speculative distance as `margin` (fat AABB);
+/// region queries can pass `0.0` to get the tight collider bounds.
+pub fn entity_aabb(e: &dyn PhysicalEntity, margin: Scalar) -> Aabb {
     if let Some(col) = e.collider() {
-        let mut aabb = col.aabb(*e.pos(), e.angle());
-        let ext = Vec2::new(params.speculative_distance, params.speculative_distance);
+        let mut aabb = col.aabb(&e.transform());
+        let ext = Vec2::new(margin, margin);
         aabb.min = aabb.min - ext;
         aabb.max = aabb.max + ext;
         return aabb;
@@ -16,35 +21,172 @@ fn entity_aabb(e: &dyn PhysicalEntity, params: SimParams) -> Aabb {
     Aabb::new(*e.pos() - ext, *e.pos() + ext)
 }
 
-pub fn detect_sap(entities: &[Box<dyn PhysicalEntity>], params: SimParams) -> Vec<(usize, usize)> {
-    struct Entry {
-        index: usize,
-        aabb: Aabb,
-    }
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    index: usize,
+    aabb: Aabb,
+}
 
-    let mut entries: Vec<Entry> = entities
-        .iter()
-        .enumerate()
-        .map(|(i, e)| Entry {
+/// Working buffers of the sweep, kept between calls so a step doesn't allocate them anew.
+#[derive(Debug, Default)]
+pub struct SapScratch {
+    entries: Vec<Entry>,
+    active: Vec<Entry>,
+    hits: Vec<usize>,
+    static_pairs: Vec<(usize, usize)>,
+}
+
+/// Speculative margin of one entity: its own override, or the global distance, plus the
+/// arc its farthest corner sweeps in the step `dt` at its current spin (`|omega| * r * dt`),
+/// so a fast-spinning box still sees what its corners will hit during the step. `dt = 0`
+/// leaves out the spin.
+pub fn speculative_margin(e: &dyn PhysicalEntity, params: SimParams, dt: Scalar) -> Scalar {
+    let base = e
+        .speculative_distance()
+        .unwrap_or(params.speculative_distance);
+    let spin_radius = match e.collider() {
+        // A circle centered on the body looks the same at any angle.
+        None | Some(Collider2D::Circle { .. }) => 0.0,
+        Some(collider) => collider.bounding_radius(),
+    };
+    base + e.omega().abs() * spin_radius * dt
+}
+
+/// AABB as the broad phase sees it: grown by the entity's speculative margin.
+pub fn fat_aabb(e: &dyn PhysicalEntity, params: SimParams, dt: Scalar) -> Aabb {
+    entity_aabb(e, speculative_margin(e, params, dt))
+}
+
+/// Candidate pairs among `entities`, sorted, looking ahead over the step `dt`.
+pub fn detect_sap(
+    entities: &[Box<dyn PhysicalEntity>],
+    params: SimParams,
+    dt: Scalar,
+) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    detect_sap_into(entities, params, dt, &mut SapScratch::default(), &mut pairs);
+    pairs
+}
+
+/// `detect_sap` into a caller-owned `pairs` buffer (cleared first), reusing `scratch`.
+pub fn detect_sap_into(
+    entities: &[Box<dyn PhysicalEntity>],
+    params: SimParams,
+    dt: Scalar,
+    scratch: &mut SapScratch,
+    pairs: &mut Vec<(usize, usize)>,
+) {
+    scratch.entries.clear();
+    scratch
+        .entries
+        .extend(entities.iter().enumerate().map(|(i, e)| Entry {
             index: i,
-            aabb: entity_aabb(&**e, params),
-        })
-        .collect();
-
-    entries.sort_by(|a, b| {
-        a.aabb
-            .min
-            .x
-            .partial_cmp(&b.aabb.min.x)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
+            aabb: fat_aabb(&**e, params, dt),
+        }));
+    sweep_and_prune(scratch, pairs);
+}
+
+/// Broad phase with static scenery held in a prebuilt `Bvh`.
+///
+/// Entities outside the tree are swept against each other with SAP, and each of them is
+/// queried against the tree. Pairs of two tree members are never reported: static
+/// scenery can't collide with itself. Pairs come out sorted, like `detect_sap`'s.
+pub fn detect_sap_with_bvh(
+    entities: &[Box<dyn PhysicalEntity>],
+    params: SimParams,
+    dt: Scalar,
+    static_bvh: &Bvh,
+) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    detect_sap_with_bvh_into(
+        entities,
+        params,
+        dt,
+        static_bvh,
+        &mut SapScratch::default(),
+        &mut pairs,
+    );
+    pairs
+}
+
+/// `detect_sap_with_bvh` into a caller-owned `pairs` buffer (cleared first), reusing
+/// `scratch`.
+pub fn detect_sap_with_bvh_into(
+    entities: &[Box<dyn PhysicalEntity>],
+    params: SimParams,
+    dt: Scalar,
+    static_bvh: &Bvh,
+    scratch: &mut SapScratch,
+    pairs: &mut Vec<(usize, usize)>,
+) {
+    scratch.entries.clear();
+    scratch.entries.extend(
+        entities
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !static_bvh.contains(*i))
+            .map(|(i, e)| Entry {
+                index: i,
+                aabb: fat_aabb(&**e, params, dt),
+            }),
+    );
 
-    let mut active: Vec<Entry> = Vec::new();
-    let mut pairs: Vec<(usize, usize)> = Vec::new();
+    scratch.static_pairs.clear();
+    for entry in &scratch.entries {
+        scratch.hits.clear();
+        static_bvh.query(&entry.aabb, &mut scratch.hits);
+        for &s in &scratch.hits {
+            // The tree may be stale (built before entities were removed).
+            if s >= entities.len() {
+                continue;
+            }
+            scratch
+                .static_pairs
+                .push((s.min(entry.index), s.max(entry.index)));
+        }
+    }
+
+    sweep_and_prune(scratch, pairs);
+    pairs.extend_from_slice(&scratch.static_pairs);
+    pairs.sort_unstable();
+}
 
-    for cur in entries {
-        active.retain(|e| e.aabb.max.x >= cur.aabb.min.x);
-        for e in &active {
+/// Drop the pairs of two bodies that can't respond to a contact (`inv_mass` and
+/// `inv_inertia` both 0, e.g. a ground box and a slope): their constraints would be no-ops.
+/// Pairs with a sensor are kept, so a moving static sensor still reports its overlaps.
+pub fn retain_responsive(entities: &[Box<dyn PhysicalEntity>], pairs: &mut Vec<(usize, usize)>) {
+    let immovable = |i: usize| {
+        entities
+            .get(i)
+            .is_some_and(|e| e.inv_mass() == 0.0 && e.inv_inertia() == 0.0 && !e.is_sensor())
+    };
+    pairs.retain(|&(a, b)| !(immovable(a) && immovable(b)));
+}
+
+/// Sort-and-sweep along the axis the AABBs are most spread out on.
+///
+/// Sweeping a tall stack along x keeps every box in the active list at once (they all share
+/// the same x-extent), which is O(n²); along y each box only meets its neighbours. The axis
+/// is picked per call by the variance of the AABB centers. Candidates in the active list are
+/// still checked on both axes (`Aabb::overlaps`).
+///
+/// Pairs come out sorted, so the narrow phase and solver see the same order whichever axis
+/// was swept.
+fn sweep_and_prune(scratch: &mut SapScratch, pairs: &mut Vec<(usize, usize)>) {
+    let SapScratch {
+        entries, active, ..
+    } = scratch;
+    let axis = sweep_axis_of(entries.iter().map(|e| &e.aabb));
+    let lo = |aabb: &Aabb| axis.dot(aabb.min);
+    let hi = |aabb: &Aabb| axis.dot(aabb.max);
+    entries.sort_by(|a, b| lo(&a.aabb).total_cmp(&lo(&b.aabb)));
+
+    active.clear();
+    pairs.clear();
+
+    for &cur in entries.iter() {
+        active.retain(|e| hi(&e.aabb) >= lo(&cur.aabb));
+        for e in active.iter() {
             if e.aabb.overlaps(&cur.aabb) {
                 let (i, j) = if e.index < cur.index {
                     (e.index, cur.index)
@@ -57,5 +199,49 @@ pub fn detect_sap(entities: &[Box<dyn PhysicalEntity>], params: SimParams) -> Ve
         active.push(cur);
     }
 
-    pairs
+    pairs.sort_unstable();
+}
+
+/// Unit x or y, whichever the AABB centers vary more along.
+pub(super) fn sweep_axis_of<'a>(aabbs: impl Iterator<Item = &'a Aabb> + Clone) -> Vec2 {
+    let centers = aabbs.map(|aabb| (aabb.min + aabb.max) * 0.5);
+    let n = centers.clone().count();
+    if n == 0 {
+        return Vec2::new(1.0, 0.0);
+    }
+    let mean = centers.clone().fold(Vec2::zero(), |acc, c| acc + c) * (1.0 / n as Scalar);
+    let (var_x, var_y) = centers.fold((0.0, 0.0), |(vx, vy), c| {
+        let d = c - mean;
+        (vx + d.x * d.x, vy + d.y * d.y)
+    });
+    if var_y > var_x {
+        Vec2::new(0.0, 1.0)
+    } else {
+        Vec2::new(1.0, 0.0)
+    }
+}
+
+/// Indices of every entity whose AABB overlaps `region`.
+///
+/// With `Some((params, dt))` each AABB is fattened by the entity's speculative margin over
+/// the step `dt`, exactly as the broad phase sees it; `None` uses the tight collider bounds.
+///
+/// Read-only: this is a plain linear scan and does not touch any solver state.
+pub fn query_aabb(
+    entities: &[Box<dyn PhysicalEntity>],
+    region: &Aabb,
+    fattened: Option<(SimParams, Scalar)>,
+) -> Vec<usize> {
+    entities
+        .iter()
+        .enumerate()
+        .filter(|(_, e)| {
+            let aabb = match fattened {
+                Some((params, dt)) => fat_aabb(&***e, params, dt),
+                None => entity_aabb(&***e, 0.0),
+            };
+            aabb.overlaps(region)
+        })
+        .map(|(i, _)| i)
+        .collect()
 }
@@ -0,0 +1,156 @@
+use super::Aabb;
+use crate::math::Scalar;
+
+enum NodeKind {
+    Leaf(usize),
+    Internal { left: usize, right: usize },
+}
+
+struct Node {
+    aabb: Aabb,
+    kind: NodeKind,
+}
+
+/// Static bounding-volume hierarchy over entity AABBs.
+///
+/// Built once (top-down, surface-area-heuristic splits) and then only queried; it is meant for
+/// scenery that doesn't move. Rebuild it if the indexed entities change, or `refit` it if a
+/// few of them moved.
+pub struct Bvh {
+    nodes: Vec<Node>,
+    root: Option<usize>,
+    /// Sorted entity indices stored in the leaves.
+    members: Vec<usize>,
+}
+
+impl Bvh {
+    /// Build a tree over `(entity index, aabb)` items.
+    pub fn build(items: &[(usize, Aabb)]) -> Self {
+        let mut items = items.to_vec();
+        let mut members: Vec<usize> = items.iter().map(|&(i, _)| i).collect();
+        members.sort_unstable();
+        members.dedup();
+
+        let mut nodes = Vec::with_capacity(items.len() * 2);
+        let root = if items.is_empty() {
+            None
+        } else {
+            Some(build_node(&mut nodes, &mut items))
+        };
+        Self {
+            nodes,
+            root,
+            members,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Whether entity `index` is stored in the tree.
+    pub fn contains(&self, index: usize) -> bool {
+        self.members.binary_search(&index).is_ok()
+    }
+
+    /// Replace every leaf's AABB with `aabb_of(index)` and grow the nodes above to match,
+    /// keeping the tree's shape. Cheaper than a rebuild, but the splits only stay good while
+    /// the members stay roughly where they were built.
+    pub fn refit(&mut self, mut aabb_of: impl FnMut(usize) -> Aabb) {
+        // Children are pushed before their parent, so one pass in order sees them first.
+        for n in 0..self.nodes.len() {
+            self.nodes[n].aabb = match self.nodes[n].kind {
+                NodeKind::Leaf(index) => aabb_of(index),
+                NodeKind::Internal { left, right } => {
+                    self.nodes[left].aabb.merge(&self.nodes[right].aabb)
+                }
+            };
+        }
+    }
+
+    /// Push the index of every leaf whose AABB overlaps `region` onto `out`.
+    pub fn query(&self, region: &Aabb, out: &mut Vec<usize>) {
+        let Some(root) = self.root else {
+            return;
+        };
+        let mut stack = vec![root];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !node.aabb.overlaps(region) {
+                continue;
+            }
+            match node.kind {
+                NodeKind::Leaf(index) => out.push(index),
+                NodeKind::Internal { left, right } => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+    }
+}
+
+/// Build the subtree for `items` (non-empty) and return its node index.
+fn build_node(nodes: &mut Vec<Node>, items: &mut [(usize, Aabb)]) -> usize {
+    let bounds = items
+        .iter()
+        .map(|&(_, aabb)| aabb)
+        .reduce(|acc, aabb| acc.merge(&aabb))
+        .expect("build_node needs at least one item");
+
+    if let [(index, aabb)] = *items {
+        nodes.push(Node {
+            aabb,
+            kind: NodeKind::Leaf(index),
+        });
+        return nodes.len() - 1;
+    }
+
+    // Sort along the axis where the centers are most spread out.
+    let centers = items
+        .iter()
+        .map(|(_, aabb)| Aabb::new(aabb.center(), aabb.center()))
+        .reduce(|acc, c| acc.merge(&c))
+        .expect("build_node needs at least one item");
+    let spread = centers.half_extents();
+    let split_x = spread.x >= spread.y;
+    items.sort_by(|(_, a), (_, b)| {
+        let (ca, cb) = (a.center(), b.center());
+        if split_x {
+            ca.x.total_cmp(&cb.x)
+        } else {
+            ca.y.total_cmp(&cb.y)
+        }
+    });
+
+    // SAH: pick the split minimising area(left) * n_left + area(right) * n_right.
+    let n = items.len();
+    let mut right_area = vec![0.0; n];
+    let mut acc = items[n - 1].1;
+    for i in (1..n).rev() {
+        acc = acc.merge(&items[i].1);
+        right_area[i] = acc.area();
+    }
+    let mut best = (Scalar::INFINITY, n / 2);
+    let mut left = items[0].1;
+    for (i, &area) in right_area.iter().enumerate().skip(1) {
+        let cost = left.area() * i as Scalar + area * (n - i) as Scalar;
+        if cost < best.0 {
+            best = (cost, i);
+        }
+        left = left.merge(&items[i].1);
+    }
+
+    let (left_items, right_items) = items.split_at_mut(best.1);
+    let left = build_node(nodes, left_items);
+    let right = build_node(nodes, right_items);
+    nodes.push(Node {
+        aabb: bounds,
+        kind: NodeKind::Internal { left, right },
+    });
+    nodes.len() - 1
+}
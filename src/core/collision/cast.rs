@@ -0,0 +1,410 @@
+use super::distance::closest_points;
+use super::narrow_phase::shape_contacts;
+use super::shape::chain_segments;
+use super::{Aabb, Collider2D, broad_phase};
+use crate::core::body::PhysicalEntity;
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
+use crate::math::vec::Vec2;
+
+/// Distance at which a swept shape counts as touching.
+const CAST_TOLERANCE: Scalar = 1e-3;
+/// Conservative-advancement steps per candidate before giving up (grazing sweeps).
+const CAST_MAX_ITERATIONS: usize = 64;
+
+/// First body hit by a swept shape (`World::shape_cast`).
+#[derive(Debug, Clone, Copy)]
+pub struct ShapeHit {
+    /// Index of the body that was hit.
+    pub entity: usize,
+    /// Time of impact as a fraction of the sweep: the shape is at `from + (to - from) * toi`.
+    pub toi: Scalar,
+    /// World-space contact point.
+    pub point: Vec2,
+    /// Surface normal of the hit body, pointing back towards the swept shape.
+    pub normal: Vec2,
+}
+
+/// First body a ray touches during a step (`World::raycast_ccd`).
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit {
+    /// Index of the body that was hit.
+    pub entity: usize,
+    /// When the body reaches the ray, as a fraction of the step.
+    pub toi: Scalar,
+    /// Distance along the ray from its origin to `point`.
+    pub distance: Scalar,
+    /// Where the ray enters the body at `toi`.
+    pub point: Vec2,
+    /// Surface normal of the body at `point`, facing the ray origin (`-dir` if the origin is
+    /// inside the body).
+    pub normal: Vec2,
+}
+
+/// Cast a ray `max_dist` along `dir` from `origin`, against every body moving at its current
+/// velocity over `dt`, and report the earliest hit.
+///
+/// Each body is swept against the ray by conservative advancement, as in `shape_cast`, so a
+/// body that crosses the ray during the step can't slip past it. Only the linear motion is
+/// swept; spin is ignored. Of bodies hit at the same time (the ones already on the ray, with
+/// `dt = 0` or for static bodies) the one nearest the origin wins.
+pub fn raycast_ccd(
+    entities: &[Box<dyn PhysicalEntity>],
+    origin: Vec2,
+    dir: Vec2,
+    max_dist: Scalar,
+    dt: Scalar,
+) -> Option<RayHit> {
+    let dir = dir.try_normalize()?;
+    if max_dist <= 0.0 {
+        return None;
+    }
+    let end = origin + dir * max_dist;
+    let ray_box = Aabb::new(origin, origin).merge(&Aabb::new(end, end));
+    let mut best: Option<RayHit> = None;
+    for (index, entity) in entities.iter().enumerate() {
+        let Some(collider) = entity.collider() else {
+            continue;
+        };
+        let motion = *entity.vel() * dt;
+        let start = broad_phase::entity_aabb(&**entity, 0.0);
+        let swept = start.merge(&Aabb::new(start.min + motion, start.max + motion));
+        if !swept.overlaps(&ray_box) {
+            continue;
+        }
+        let Some(hit) = ray_against(
+            index,
+            collider,
+            &entity.transform(),
+            motion,
+            origin,
+            dir,
+            max_dist,
+        ) else {
+            continue;
+        };
+        if best.is_none_or(|b| (hit.toi, hit.distance) < (b.toi, b.distance)) {
+            best = Some(hit);
+        }
+    }
+    best
+}
+
+/// Earliest hit of the ray on a body moving by `motion` over the step.
+fn ray_against(
+    index: usize,
+    collider: &Collider2D,
+    xf: &Transform2D,
+    motion: Vec2,
+    origin: Vec2,
+    dir: Vec2,
+    max_dist: Scalar,
+) -> Option<RayHit> {
+    let ends = [origin, origin + dir * max_dist];
+    let mut t: Scalar = 0.0;
+    for _ in 0..CAST_MAX_ITERATIONS {
+        let at = Transform2D::new(xf.rotation, xf.translation + motion * t);
+        if let Some((distance, normal)) = ray_hit(collider, &at, origin, dir, max_dist) {
+            return Some(RayHit {
+                entity: index,
+                toi: t,
+                distance,
+                point: origin + dir * distance,
+                normal,
+            });
+        }
+        if t >= 1.0 {
+            return None;
+        }
+        t = (t + ray_advance(collider, &at, ends, motion)?).min(1.0);
+    }
+    None
+}
+
+/// Distance along the ray to where it enters `collider`, and the surface normal there.
+fn ray_hit(
+    collider: &Collider2D,
+    xf: &Transform2D,
+    origin: Vec2,
+    dir: Vec2,
+    max_dist: Scalar,
+) -> Option<(Scalar, Vec2)> {
+    let nearest = |hits: &mut dyn Iterator<Item = (Scalar, Vec2)>| {
+        hits.min_by(|(s, _), (t, _)| s.total_cmp(t))
+    };
+    match collider {
+        Collider2D::Circle { radius } => ray_circle(xf.translation, *radius, origin, dir, max_dist),
+        Collider2D::Box { half_extents } => ray_box(xf, *half_extents, origin, dir, max_dist),
+        // A rounded box is two crossed boxes and a circle at each corner.
+        Collider2D::RoundedBox {
+            half_extents,
+            radius,
+        } => {
+            let (hx, hy) = (half_extents.x, half_extents.y);
+            let boxes = [Vec2::new(hx + radius, hy), Vec2::new(hx, hy + radius)]
+                .map(|half| ray_box(xf, half, origin, dir, max_dist));
+            let corners = [(-hx, -hy), (hx, -hy), (hx, hy), (-hx, hy)].map(|(x, y)| {
+                let center = xf.apply_to_point(Vec2::new(x, y));
+                ray_circle(center, *radius, origin, dir, max_dist)
+            });
+            nearest(&mut boxes.into_iter().chain(corners).flatten())
+        }
+        Collider2D::Segment { a, b } => {
+            ray_segment(segment_ends(xf, *a, *b), origin, dir, max_dist)
+        }
+        Collider2D::Compound { parts } => nearest(
+            &mut parts
+                .iter()
+                .filter_map(|(local, part)| ray_hit(part, &local.then(xf), origin, dir, max_dist)),
+        ),
+        Collider2D::Chain { vertices } => nearest(
+            &mut chain_segments(vertices)
+                .filter_map(|segment| ray_hit(&segment, xf, origin, dir, max_dist)),
+        ),
+    }
+}
+
+fn ray_circle(
+    center: Vec2,
+    radius: Scalar,
+    origin: Vec2,
+    dir: Vec2,
+    max_dist: Scalar,
+) -> Option<(Scalar, Vec2)> {
+    let m = origin - center;
+    let c = m.length_squared() - radius * radius;
+    if c <= 0.0 {
+        return Some((0.0, -dir));
+    }
+    let b = m.dot(dir);
+    let disc = b * b - c;
+    if disc < 0.0 {
+        return None;
+    }
+    let s = -b - disc.sqrt();
+    if !(0.0..=max_dist).contains(&s) {
+        return None;
+    }
+    let normal = (origin + dir * s - center).try_normalize().unwrap_or(-dir);
+    Some((s, normal))
+}
+
+/// Slab test in the box's frame.
+fn ray_box(
+    xf: &Transform2D,
+    half_extents: Vec2,
+    origin: Vec2,
+    dir: Vec2,
+    max_dist: Scalar,
+) -> Option<(Scalar, Vec2)> {
+    let to_local = xf.rotation.transpose();
+    let o = to_local.mul_vec2(origin - xf.translation);
+    let d = to_local.mul_vec2(dir);
+    let (mut near, mut far) = (0.0, max_dist);
+    let mut normal = None;
+    for (o, d, h, axis) in [
+        (o.x, d.x, half_extents.x, Vec2::new(1.0, 0.0)),
+        (o.y, d.y, half_extents.y, Vec2::new(0.0, 1.0)),
+    ] {
+        if d.abs() <= Scalar::EPSILON {
+            if o.abs() > h {
+                return None;
+            }
+            continue;
+        }
+        let (enter, exit, face) = if d > 0.0 {
+            ((-h - o) / d, (h - o) / d, -axis)
+        } else {
+            ((h - o) / d, (-h - o) / d, axis)
+        };
+        if enter > near {
+            near = enter;
+            normal = Some(face);
+        }
+        far = far.min(exit);
+        if near > far {
+            return None;
+        }
+    }
+    // No face crossed: the origin is inside.
+    Some((near, normal.map_or(-dir, |n| xf.apply_to_vector(n))))
+}
+
+fn ray_segment(
+    [a, b]: [Vec2; 2],
+    origin: Vec2,
+    dir: Vec2,
+    max_dist: Scalar,
+) -> Option<(Scalar, Vec2)> {
+    let edge = b - a;
+    let denom = dir.cross(edge);
+    // Parallel (or a point): a ray along a segment doesn't hit its face.
+    if denom.abs() <= Scalar::EPSILON {
+        return None;
+    }
+    let w = a - origin;
+    let s = w.cross(edge) / denom;
+    let u = w.cross(dir) / denom;
+    if !(0.0..=max_dist).contains(&s) || !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let normal = edge.perp().normalized();
+    Some((
+        s,
+        if normal.dot(dir) > 0.0 {
+            -normal
+        } else {
+            normal
+        },
+    ))
+}
+
+/// How far the body can move along `motion`, as a fraction of it, before it may touch the
+/// ray (as the segment `ends`): its gap to the ray over the speed at which it closes it.
+/// `None` if it isn't moving towards the ray.
+///
+/// The gap of a convex shape shrinks no faster than that, so the step never skips past the
+/// hit, however obliquely the body moves. Compounds and chains take their parts' smallest
+/// step.
+fn ray_advance(
+    collider: &Collider2D,
+    xf: &Transform2D,
+    ends: [Vec2; 2],
+    motion: Vec2,
+) -> Option<Scalar> {
+    let smallest = |steps: &mut dyn Iterator<Item = Option<Scalar>>| {
+        steps.flatten().min_by(|s, t| s.total_cmp(t))
+    };
+    match collider {
+        Collider2D::Compound { parts } => smallest(
+            &mut parts
+                .iter()
+                .map(|(local, part)| ray_advance(part, &local.then(xf), ends, motion)),
+        ),
+        Collider2D::Chain { vertices } => smallest(
+            &mut chain_segments(vertices).map(|segment| ray_advance(&segment, xf, ends, motion)),
+        ),
+        _ => {
+            let ray = Collider2D::Segment {
+                a: ends[0],
+                b: ends[1],
+            };
+            let (on_body, on_ray, gap) =
+                closest_points(collider, xf, &ray, &Transform2D::identity());
+            if gap <= CAST_TOLERANCE {
+                // Grazing: no direction to close along; creep past it.
+                let speed = motion.length();
+                return (speed > Scalar::EPSILON).then(|| CAST_TOLERANCE / speed);
+            }
+            let closing = motion.dot(on_ray - on_body) / gap;
+            (closing > Scalar::EPSILON).then(|| gap / closing)
+        }
+    }
+}
+
+/// Sweep `collider` (at angle 0) from `from` to `to` and report the earliest hit.
+///
+/// Candidates come from the AABB of the whole sweep. Each one is found by conservative
+/// advancement: the shape moves forward by its exact distance to the body (`closest_points`),
+/// which can never skip past it, until the two are within `CAST_TOLERANCE`. A shape that already overlaps a
+/// body at `from` hits it at `toi` 0, with the normal pushing it out.
+///
+/// Only bodies for which `filter(index)` is true are considered, e.g. to leave out the body
+/// whose collider is being swept.
+pub fn shape_cast(
+    entities: &[Box<dyn PhysicalEntity>],
+    collider: &Collider2D,
+    from: Vec2,
+    to: Vec2,
+    mut filter: impl FnMut(usize) -> bool,
+) -> Option<ShapeHit> {
+    let sweep = collider
+        .aabb(&Transform2D::from_translation(from))
+        .merge(&collider.aabb(&Transform2D::from_translation(to)));
+    let mut best: Option<ShapeHit> = None;
+    for index in broad_phase::query_aabb(entities, &sweep, None) {
+        if !filter(index) {
+            continue;
+        }
+        let entity = &*entities[index];
+        let Some(body) = entity.collider() else {
+            continue;
+        };
+        let Some(hit) = cast_against(index, body, &entity.transform(), collider, from, to) else {
+            continue;
+        };
+        if best.is_none_or(|b| hit.toi < b.toi) {
+            best = Some(hit);
+        }
+    }
+    best
+}
+
+fn cast_against(
+    index: usize,
+    body: &Collider2D,
+    body_xf: &Transform2D,
+    collider: &Collider2D,
+    from: Vec2,
+    to: Vec2,
+) -> Option<ShapeHit> {
+    // Starting inside: report the deepest contact's depenetration normal.
+    if let Some((hit, penetration)) = deepest_contact(index, body, body_xf, collider, from, 0.0)
+        && penetration > 0.0
+    {
+        return Some(hit);
+    }
+
+    let delta = to - from;
+    let len = delta.length();
+    let mut t = 0.0;
+    for _ in 0..CAST_MAX_ITERATIONS {
+        let at = from + delta * t;
+        let (_, _, dist) =
+            closest_points(body, body_xf, collider, &Transform2D::from_translation(at));
+        if dist <= CAST_TOLERANCE {
+            let (mut hit, _) =
+                deepest_contact(index, body, body_xf, collider, at, 2.0 * CAST_TOLERANCE)?;
+            hit.toi = t;
+            return Some(hit);
+        }
+        if len <= Scalar::EPSILON {
+            return None;
+        }
+        t += dist / len;
+        if t > 1.0 {
+            return None;
+        }
+    }
+    None
+}
+
+/// Hit data (with `toi` 0) from the deepest contact point of the shape at `at`, and that
+/// point's penetration.
+fn deepest_contact(
+    index: usize,
+    body: &Collider2D,
+    body_xf: &Transform2D,
+    collider: &Collider2D,
+    at: Vec2,
+    speculative_distance: Scalar,
+) -> Option<(ShapeHit, Scalar)> {
+    let at = Transform2D::from_translation(at);
+    shape_contacts(body, body_xf, collider, &at, speculative_distance)
+        .iter()
+        .flat_map(|(normal, points)| points.iter().map(move |p| (normal, p)))
+        .max_by(|(_, p), (_, q)| p.penetration.total_cmp(&q.penetration))
+        .map(|(normal, p)| {
+            let hit = ShapeHit {
+                entity: index,
+                toi: 0.0,
+                point: p.point,
+                normal,
+            };
+            (hit, p.penetration)
+        })
+}
+
+fn segment_ends(xf: &Transform2D, a: Vec2, b: Vec2) -> [Vec2; 2] {
+    [xf.apply_to_point(a), xf.apply_to_point(b)]
+}
@@ -1,12 +1,14 @@
 use super::manifold::ContactPoint;
+use crate::math::Scalar;
 use crate::math::vec::Vec2;
 
+/// Circle vs. circle contact: always a single point, on A's surface along the A→B normal.
 pub fn detect(
     center_a: Vec2,
-    radius_a: f32,
+    radius_a: Scalar,
     center_b: Vec2,
-    radius_b: f32,
-    speculative_distance: f32,
+    radius_b: Scalar,
+    speculative_distance: Scalar,
 ) -> Option<(Vec2, ContactPoint)> {
     let delta = center_b - center_a;
     let dist_sq = delta.length_squared();
@@ -28,6 +30,8 @@ pub fn detect(
         ContactPoint {
             point: contact_point,
             penetration,
+            // Circles only ever touch at one point.
+            id: 0,
         },
     ))
 }
@@ -0,0 +1,51 @@
+use super::manifold::ContactPoint;
+use crate::math::Scalar;
+use crate::math::vec::Vec2;
+
+/// Segment vs. circle contact, for a segment from `a` to `b` in world space.
+///
+/// Two-sided: the normal points from the closest point of the segment to the circle center,
+/// so it can be hit from either side or on its end points. The contact point sits on the
+/// circle's surface, as for `box_circle::detect`.
+pub fn detect(
+    a: Vec2,
+    b: Vec2,
+    circle_center: Vec2,
+    radius: Scalar,
+    speculative_distance: Scalar,
+) -> Option<(Vec2, ContactPoint)> {
+    let edge = b - a;
+    let len_sq = edge.length_squared();
+    let t = if len_sq > 0.0 {
+        ((circle_center - a).dot(edge) / len_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let closest = a + edge * t;
+
+    let delta = circle_center - closest;
+    let dist_sq = delta.length_squared();
+    let max_r = radius + speculative_distance;
+    if dist_sq > max_r * max_r {
+        return None;
+    }
+
+    // Center exactly on the segment: push out along its left-hand normal.
+    let (normal, penetration) = delta
+        .try_normalize()
+        .map(|n| (n, radius - dist_sq.sqrt())) // penetration can be negative => separation
+        .unwrap_or_else(|| {
+            let side = edge.perp().try_normalize().unwrap_or(Vec2::new(0.0, 1.0));
+            (side, radius)
+        });
+
+    Some((
+        normal,
+        ContactPoint {
+            point: circle_center - normal * radius,
+            penetration,
+            // A circle touches a segment in one place.
+            id: 0,
+        },
+    ))
+}
@@ -0,0 +1,415 @@
+use super::Collider2D;
+use super::narrow_phase::shape_contacts;
+use super::shape::chain_segments;
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
+use crate::math::vec::Vec2;
+
+/// GJK iterations before giving up (it converges in a handful for boxes and circles).
+const GJK_MAX_ITERATIONS: usize = 20;
+/// EPA expansions before giving up, and how close (meters) the polytope must get to the true
+/// boundary.
+const EPA_MAX_ITERATIONS: usize = 32;
+const EPA_TOLERANCE: Scalar = 1e-4;
+
+/// A convex shape as GJK sees it: the convex hull of `vertices` (world space), grown by
+/// `radius`. A circle is one vertex with its radius, a rounded box its inner box.
+struct Proxy {
+    vertices: Vec<Vec2>,
+    radius: Scalar,
+}
+
+impl Proxy {
+    fn new(collider: &Collider2D, xf: &Transform2D) -> Option<Self> {
+        let box_vertices = |half: Vec2| {
+            [
+                Vec2::new(-half.x, -half.y),
+                Vec2::new(half.x, -half.y),
+                Vec2::new(half.x, half.y),
+                Vec2::new(-half.x, half.y),
+            ]
+            .map(|v| xf.apply_to_point(v))
+            .to_vec()
+        };
+        match collider {
+            Collider2D::Circle { radius } => Some(Self {
+                vertices: vec![xf.translation],
+                radius: *radius,
+            }),
+            Collider2D::Box { half_extents } => Some(Self {
+                vertices: box_vertices(*half_extents),
+                radius: 0.0,
+            }),
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => Some(Self {
+                vertices: box_vertices(*half_extents),
+                radius: *radius,
+            }),
+            Collider2D::Segment { a, b } => Some(Self {
+                vertices: vec![xf.apply_to_point(*a), xf.apply_to_point(*b)],
+                radius: 0.0,
+            }),
+            Collider2D::Chain { .. } | Collider2D::Compound { .. } => None,
+        }
+    }
+
+    fn center(&self) -> Vec2 {
+        let sum = self.vertices.iter().fold(Vec2::zero(), |acc, &v| acc + v);
+        sum * (1.0 / self.vertices.len() as Scalar)
+    }
+
+    /// Index of the vertex furthest along `d`.
+    fn support(&self, d: Vec2) -> usize {
+        let mut best = 0;
+        for i in 1..self.vertices.len() {
+            if self.vertices[i].dot(d) > self.vertices[best].dot(d) {
+                best = i;
+            }
+        }
+        best
+    }
+}
+
+/// A point of the Minkowski difference `B - A` and the vertices that made it.
+#[derive(Clone, Copy)]
+struct SimplexVertex {
+    a: Vec2,
+    b: Vec2,
+    index_a: usize,
+    index_b: usize,
+    /// Barycentric weight in the current closest point.
+    weight: Scalar,
+}
+
+impl SimplexVertex {
+    fn new(pa: &Proxy, index_a: usize, pb: &Proxy, index_b: usize) -> Self {
+        Self {
+            a: pa.vertices[index_a],
+            b: pb.vertices[index_b],
+            index_a,
+            index_b,
+            weight: 1.0,
+        }
+    }
+
+    fn w(&self) -> Vec2 {
+        self.b - self.a
+    }
+}
+
+/// Reduce the simplex to the sub-simplex closest to the origin and set its weights.
+/// Returns `false` if the origin is inside the triangle (the shapes overlap).
+fn solve_simplex(simplex: &mut Vec<SimplexVertex>) -> bool {
+    match simplex.len() {
+        1 => {
+            simplex[0].weight = 1.0;
+            true
+        }
+        2 => {
+            solve_segment(simplex);
+            true
+        }
+        _ => solve_triangle(simplex),
+    }
+}
+
+/// Closest point of segment `w1 w2` to the origin, as Voronoi regions of the two ends.
+fn solve_segment(simplex: &mut Vec<SimplexVertex>) {
+    let (w1, w2) = (simplex[0].w(), simplex[1].w());
+    let e12 = w2 - w1;
+    let d12_2 = -w1.dot(e12);
+    if d12_2 <= 0.0 {
+        simplex.truncate(1);
+        simplex[0].weight = 1.0;
+        return;
+    }
+    let d12_1 = w2.dot(e12);
+    if d12_1 <= 0.0 {
+        simplex.remove(0);
+        simplex[0].weight = 1.0;
+        return;
+    }
+    let inv = 1.0 / (d12_1 + d12_2);
+    simplex[0].weight = d12_1 * inv;
+    simplex[1].weight = d12_2 * inv;
+}
+
+/// Closest point of triangle `w1 w2 w3` to the origin (Box2D's `b2Simplex::Solve3`).
+fn solve_triangle(simplex: &mut Vec<SimplexVertex>) -> bool {
+    let (w1, w2, w3) = (simplex[0].w(), simplex[1].w(), simplex[2].w());
+
+    let e12 = w2 - w1;
+    let d12_1 = w2.dot(e12);
+    let d12_2 = -w1.dot(e12);
+    let e13 = w3 - w1;
+    let d13_1 = w3.dot(e13);
+    let d13_2 = -w1.dot(e13);
+    let e23 = w3 - w2;
+    let d23_1 = w3.dot(e23);
+    let d23_2 = -w2.dot(e23);
+
+    // Signed areas of the sub-triangles against the origin.
+    let n123 = e12.cross(e13);
+    let d123_1 = n123 * w2.cross(w3);
+    let d123_2 = n123 * w3.cross(w1);
+    let d123_3 = n123 * w1.cross(w2);
+
+    let keep = |simplex: &mut Vec<SimplexVertex>, indices: &[(usize, Scalar)]| {
+        let kept: Vec<SimplexVertex> = indices
+            .iter()
+            .map(|&(i, weight)| SimplexVertex {
+                weight,
+                ..simplex[i]
+            })
+            .collect();
+        *simplex = kept;
+    };
+
+    if d12_2 <= 0.0 && d13_2 <= 0.0 {
+        keep(simplex, &[(0, 1.0)]);
+    } else if d12_1 > 0.0 && d12_2 > 0.0 && d123_3 <= 0.0 {
+        let inv = 1.0 / (d12_1 + d12_2);
+        keep(simplex, &[(0, d12_1 * inv), (1, d12_2 * inv)]);
+    } else if d13_1 > 0.0 && d13_2 > 0.0 && d123_2 <= 0.0 {
+        let inv = 1.0 / (d13_1 + d13_2);
+        keep(simplex, &[(0, d13_1 * inv), (2, d13_2 * inv)]);
+    } else if d12_1 <= 0.0 && d23_2 <= 0.0 {
+        keep(simplex, &[(1, 1.0)]);
+    } else if d13_1 <= 0.0 && d23_1 <= 0.0 {
+        keep(simplex, &[(2, 1.0)]);
+    } else if d23_1 > 0.0 && d23_2 > 0.0 && d123_1 <= 0.0 {
+        let inv = 1.0 / (d23_1 + d23_2);
+        keep(simplex, &[(1, d23_1 * inv), (2, d23_2 * inv)]);
+    } else {
+        // Origin inside the triangle.
+        return false;
+    }
+    true
+}
+
+/// Closest points between the proxies' cores (radius ignored). If the cores overlap, the
+/// error holds the final simplex, for `epa`.
+fn gjk(pa: &Proxy, pb: &Proxy) -> Result<(Vec2, Vec2), Vec<SimplexVertex>> {
+    // Seed with a support point (not just any vertex pair) so every simplex vertex is on the
+    // hull of the Minkowski difference, which `epa` relies on.
+    let d = pb.center() - pa.center();
+    let d = if d.length_squared() > 0.0 {
+        d
+    } else {
+        Vec2::new(1.0, 0.0)
+    };
+    let mut simplex = vec![SimplexVertex::new(pa, pa.support(-d), pb, pb.support(d))];
+    for iteration in 0.. {
+        if !solve_simplex(&mut simplex) {
+            return Err(simplex);
+        }
+        if iteration == GJK_MAX_ITERATIONS {
+            break;
+        }
+        let closest = simplex
+            .iter()
+            .fold(Vec2::zero(), |acc, v| acc + v.w() * v.weight);
+        if closest.length_squared() <= Scalar::EPSILON * Scalar::EPSILON {
+            return Err(simplex);
+        }
+
+        // Search towards the origin: furthest point of B - A along -closest.
+        let d = -closest;
+        let next = SimplexVertex::new(pa, pa.support(-d), pb, pb.support(d));
+        let repeated = simplex
+            .iter()
+            .any(|v| v.index_a == next.index_a && v.index_b == next.index_b);
+        // No new vertex, or no real progress towards the origin: converged.
+        let progress = next.w().dot(d) - closest.dot(d);
+        if repeated || progress <= 1e-6 * closest.length_squared() {
+            break;
+        }
+        simplex.push(next);
+    }
+    let a = simplex
+        .iter()
+        .fold(Vec2::zero(), |acc, v| acc + v.a * v.weight);
+    let b = simplex
+        .iter()
+        .fold(Vec2::zero(), |acc, v| acc + v.b * v.weight);
+    Ok((a, b))
+}
+
+/// Grow a degenerate GJK simplex (origin on a vertex or edge) into a triangle, wound
+/// counter-clockwise. `None` if the Minkowski difference is too thin to span one.
+fn complete_triangle(
+    pa: &Proxy,
+    pb: &Proxy,
+    mut simplex: Vec<SimplexVertex>,
+) -> Option<Vec<SimplexVertex>> {
+    let spans = |simplex: &[SimplexVertex], v: &SimplexVertex| match simplex {
+        [p] => (v.w() - p.w()).length_squared() > EPA_TOLERANCE * EPA_TOLERANCE,
+        [p, q] => (q.w() - p.w()).cross(v.w() - p.w()).abs() > EPA_TOLERANCE * EPA_TOLERANCE,
+        _ => false,
+    };
+    while simplex.len() < 3 {
+        let dir = match simplex[..] {
+            [_] => Vec2::new(1.0, 0.0),
+            _ => (simplex[1].w() - simplex[0].w()).perp(),
+        };
+        let next = [dir, -dir]
+            .into_iter()
+            .map(|d| SimplexVertex::new(pa, pa.support(-d), pb, pb.support(d)))
+            .find(|v| spans(&simplex, v))?;
+        simplex.push(next);
+    }
+    let (w0, w1, w2) = (simplex[0].w(), simplex[1].w(), simplex[2].w());
+    if (w1 - w0).cross(w2 - w0) < 0.0 {
+        simplex.swap(1, 2);
+    }
+    Some(simplex)
+}
+
+/// Expanding polytope algorithm: from a simplex around the origin, push the boundary of the
+/// Minkowski difference outwards until its closest edge to the origin is on the true boundary.
+///
+/// Returns the normal (from A to B), the depth, and a point midway between the two deepest
+/// points of the cores.
+fn epa(pa: &Proxy, pb: &Proxy, simplex: Vec<SimplexVertex>) -> Option<(Vec2, Scalar, Vec2)> {
+    let mut polytope = complete_triangle(pa, pb, simplex)?;
+    for _ in 0..EPA_MAX_ITERATIONS {
+        // Closest edge to the origin and its outward normal.
+        let (edge, normal, dist) = (0..polytope.len())
+            .filter_map(|i| {
+                let (p, q) = (polytope[i].w(), polytope[(i + 1) % polytope.len()].w());
+                let e = q - p;
+                let n = Vec2::new(e.y, -e.x).try_normalize()?;
+                Some((i, n, n.dot(p)))
+            })
+            .min_by(|x, y| x.2.total_cmp(&y.2))?;
+
+        let next = SimplexVertex::new(pa, pa.support(-normal), pb, pb.support(normal));
+        if next.w().dot(normal) - dist > EPA_TOLERANCE {
+            polytope.insert(edge + 1, next);
+            continue;
+        }
+
+        // On the boundary: the origin's projection onto this edge gives the witnesses.
+        let (p, q) = (polytope[edge], polytope[(edge + 1) % polytope.len()]);
+        let e = q.w() - p.w();
+        let t = ((normal * dist - p.w()).dot(e) / e.length_squared()).clamp(0.0, 1.0);
+        let a = p.a + (q.a - p.a) * t;
+        let b = p.b + (q.b - p.b) * t;
+        // B separates from A by moving along -normal, so A→B is -normal.
+        return Some((-normal, dist, (a + b) * 0.5));
+    }
+    None
+}
+
+/// Penetration of two overlapping convex shapes via GJK + EPA: the normal (from A to B)
+/// along which they separate soonest, the depth along it, and a point in the overlap.
+///
+/// `None` if they don't overlap, or for chains and compounds.
+pub(crate) fn penetration(
+    a: &Collider2D,
+    xf_a: &Transform2D,
+    b: &Collider2D,
+    xf_b: &Transform2D,
+) -> Option<(Vec2, Scalar, Vec2)> {
+    let pa = Proxy::new(a, xf_a)?;
+    let pb = Proxy::new(b, xf_b)?;
+    match gjk(&pa, &pb) {
+        Ok((core_a, core_b)) => {
+            let between = core_b - core_a;
+            let depth = pa.radius + pb.radius - between.length();
+            let normal = between.try_normalize()?;
+            (depth > 0.0).then(|| {
+                let point = core_a + normal * (pa.radius - 0.5 * depth);
+                (normal, depth, point)
+            })
+        }
+        Err(simplex) => {
+            let (normal, depth, point) = epa(&pa, &pb, simplex)?;
+            Some((normal, depth + pa.radius + pb.radius, point))
+        }
+    }
+}
+
+/// Distance between two convex shapes whose cores are clear of each other: the normal
+/// (from A to B) along the line between the closest core points, the gap between the surfaces
+/// (negative where only the rounded parts overlap), and the point on A's surface.
+///
+/// `None` if the cores touch or overlap, or for chains and compounds.
+pub(crate) fn core_separation(
+    a: &Collider2D,
+    xf_a: &Transform2D,
+    b: &Collider2D,
+    xf_b: &Transform2D,
+) -> Option<(Vec2, Scalar, Vec2)> {
+    let pa = Proxy::new(a, xf_a)?;
+    let pb = Proxy::new(b, xf_b)?;
+    let (core_a, core_b) = gjk(&pa, &pb).ok()?;
+    let between = core_b - core_a;
+    let normal = between.try_normalize()?;
+    let gap = between.length() - pa.radius - pb.radius;
+    Some((normal, gap, core_a + normal * pa.radius))
+}
+
+/// Closest points on `a` and `b` and the distance between them.
+///
+/// Works at any distance, unlike the narrow phase (convex shapes via GJK, chains and
+/// compounds part by part). Overlapping shapes report distance 0, with both points at the
+/// deepest contact point.
+pub fn closest_points(
+    a: &Collider2D,
+    xf_a: &Transform2D,
+    b: &Collider2D,
+    xf_b: &Transform2D,
+) -> (Vec2, Vec2, Scalar) {
+    let (pos_a, pos_b) = (xf_a.translation, xf_b.translation);
+    if let Collider2D::Compound { parts } = a {
+        return parts
+            .iter()
+            .map(|(local, part)| closest_points(part, &local.then(xf_a), b, xf_b))
+            .min_by(|x, y| x.2.total_cmp(&y.2))
+            .unwrap_or((pos_a, pos_b, Scalar::INFINITY));
+    }
+    if let Collider2D::Compound { parts } = b {
+        return parts
+            .iter()
+            .map(|(local, part)| closest_points(a, xf_a, part, &local.then(xf_b)))
+            .min_by(|x, y| x.2.total_cmp(&y.2))
+            .unwrap_or((pos_a, pos_b, Scalar::INFINITY));
+    }
+    if let Collider2D::Chain { vertices } = a {
+        return chain_segments(vertices)
+            .map(|segment| closest_points(&segment, xf_a, b, xf_b))
+            .min_by(|x, y| x.2.total_cmp(&y.2))
+            .unwrap_or((pos_a, pos_b, Scalar::INFINITY));
+    }
+    if let Collider2D::Chain { vertices } = b {
+        return chain_segments(vertices)
+            .map(|segment| closest_points(a, xf_a, &segment, xf_b))
+            .min_by(|x, y| x.2.total_cmp(&y.2))
+            .unwrap_or((pos_a, pos_b, Scalar::INFINITY));
+    }
+    let (Some(pa), Some(pb)) = (Proxy::new(a, xf_a), Proxy::new(b, xf_b)) else {
+        return (pos_a, pos_b, Scalar::INFINITY);
+    };
+
+    if let Ok((core_a, core_b)) = gjk(&pa, &pb) {
+        let between = core_b - core_a;
+        let core_distance = between.length();
+        let distance = core_distance - pa.radius - pb.radius;
+        if distance > 0.0 {
+            // Move the core points out to the rounded surfaces.
+            let n = between * (1.0 / core_distance);
+            return (core_a + n * pa.radius, core_b - n * pb.radius, distance);
+        }
+    }
+
+    // Overlapping: report where they overlap most.
+    let deepest = shape_contacts(a, xf_a, b, xf_b, 0.0)
+        .iter()
+        .flat_map(|(_, points)| points)
+        .max_by(|p, q| p.penetration.total_cmp(&q.penetration))
+        .map_or((pos_a + pos_b) * 0.5, |p| p.point);
+    (deepest, deepest, 0.0)
+}
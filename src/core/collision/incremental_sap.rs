@@ -0,0 +1,160 @@
+use std::collections::BTreeSet;
+
+use super::Aabb;
+use super::broad_phase::{fat_aabb, sweep_axis_of};
+use crate::core::body::PhysicalEntity;
+use crate::core::params::SimParams;
+use crate::math::Scalar;
+use crate::math::vec::Vec2;
+
+/// One end of a body's AABB interval on the sweep axis.
+#[derive(Debug, Clone, Copy)]
+struct Endpoint {
+    value: Scalar,
+    index: usize,
+    is_min: bool,
+}
+
+impl Endpoint {
+    /// Sort key. At equal values mins go first, so touching intervals count as overlapping,
+    /// like `Aabb::overlaps`.
+    fn precedes(&self, other: &Endpoint) -> bool {
+        self.value < other.value || (self.value == other.value && self.is_min && !other.is_min)
+    }
+}
+
+/// Sweep and prune that keeps its sorted endpoints between steps (Baraff / Cohen et al.).
+///
+/// Bodies barely move from one step to the next, so last step's order is almost right:
+/// insertion sort repairs it in close to O(n). Every swap of a min past a max is an
+/// interval starting or stopping to overlap on the sweep axis, and updates the overlap set
+/// as it happens; nothing is recomputed from scratch.
+///
+/// The axis (x or y, whichever the bodies spread more along) is picked on a full rebuild,
+/// which happens whenever the number of entities changes.
+#[derive(Debug, Default)]
+pub struct IncrementalSap {
+    axis: Vec2,
+    endpoints: Vec<Endpoint>,
+    /// Fat AABB of every entity as of the last update.
+    aabbs: Vec<Aabb>,
+    /// Pairs whose intervals overlap on the sweep axis (the other axis is checked on output).
+    overlaps: BTreeSet<(usize, usize)>,
+}
+
+impl IncrementalSap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bring the endpoints up to date with `entities` and return the overlapping pairs,
+    /// sorted, exactly as `broad_phase::detect_sap` would.
+    pub fn update(
+        &mut self,
+        entities: &[Box<dyn PhysicalEntity>],
+        params: SimParams,
+        dt: Scalar,
+    ) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        self.update_into(entities, params, dt, &mut pairs);
+        pairs
+    }
+
+    /// `update` into a caller-owned `pairs` buffer (cleared first).
+    pub fn update_into(
+        &mut self,
+        entities: &[Box<dyn PhysicalEntity>],
+        params: SimParams,
+        dt: Scalar,
+        pairs: &mut Vec<(usize, usize)>,
+    ) {
+        self.aabbs.clear();
+        self.aabbs
+            .extend(entities.iter().map(|e| fat_aabb(&**e, params, dt)));
+
+        if self.endpoints.len() != 2 * entities.len() {
+            self.rebuild();
+        } else {
+            for ep in &mut self.endpoints {
+                let aabb = &self.aabbs[ep.index];
+                let corner = if ep.is_min { aabb.min } else { aabb.max };
+                ep.value = self.axis.dot(corner);
+            }
+            self.insertion_sort();
+        }
+
+        pairs.clear();
+        pairs.extend(
+            self.overlaps
+                .iter()
+                .copied()
+                .filter(|&(i, j)| self.aabbs[i].overlaps(&self.aabbs[j])),
+        );
+    }
+
+    /// Fresh sort and overlap set (first update, or the entity count changed).
+    fn rebuild(&mut self) {
+        self.axis = sweep_axis_of(self.aabbs.iter());
+        self.endpoints.clear();
+        for (index, aabb) in self.aabbs.iter().enumerate() {
+            self.endpoints.push(Endpoint {
+                value: self.axis.dot(aabb.min),
+                index,
+                is_min: true,
+            });
+            self.endpoints.push(Endpoint {
+                value: self.axis.dot(aabb.max),
+                index,
+                is_min: false,
+            });
+        }
+        // Same order as `precedes`: by value, mins first.
+        self.endpoints
+            .sort_by(|a, b| a.value.total_cmp(&b.value).then(b.is_min.cmp(&a.is_min)));
+
+        self.overlaps.clear();
+        let mut open: Vec<usize> = Vec::new();
+        for ep in &self.endpoints {
+            if ep.is_min {
+                for &other in &open {
+                    self.overlaps.insert(pair(ep.index, other));
+                }
+                open.push(ep.index);
+            } else {
+                open.retain(|&i| i != ep.index);
+            }
+        }
+    }
+
+    /// Repair the order after the values moved, turning every min/max swap into an overlap
+    /// change.
+    fn insertion_sort(&mut self) {
+        for k in 1..self.endpoints.len() {
+            let moving = self.endpoints[k];
+            let mut j = k;
+            while j > 0 && moving.precedes(&self.endpoints[j - 1]) {
+                let passed = self.endpoints[j - 1];
+                if moving.index != passed.index {
+                    match (moving.is_min, passed.is_min) {
+                        // A min moving left past a max: the intervals now overlap.
+                        (true, false) => {
+                            self.overlaps.insert(pair(moving.index, passed.index));
+                        }
+                        // A max moving left past a min: they no longer do.
+                        (false, true) => {
+                            self.overlaps.remove(&pair(moving.index, passed.index));
+                        }
+                        _ => {}
+                    }
+                }
+                self.endpoints[j] = passed;
+                j -= 1;
+            }
+            self.endpoints[j] = moving;
+        }
+    }
+}
+
+fn pair(i: usize, j: usize) -> (usize, usize) {
+    (i.min(j), i.max(j))
+}
@@ -1,5 +1,11 @@
+use crate::core::body::Material;
+use crate::math::Scalar;
 use crate::math::vec::Vec2;
 
+/// Points whose penetrations differ by less than this count as equally deep when
+/// `Manifold::reduce_points` picks the deepest one.
+const DEPTH_TIE_TOLERANCE: Scalar = 1e-3;
+
 /// A single contact point in a collision manifold.
 #[derive(Debug, Clone)]
 pub struct ContactPoint {
@@ -8,11 +14,16 @@ pub struct ContactPoint {
     /// Signed penetration depth.
     /// - **positive**: overlapping (penetration)
     /// - **negative**: separated (speculative contact / separation distance)
-    pub penetration: f32,
+    pub penetration: Scalar,
+    /// Feature id of the geometry pair that generated this point.
+    ///
+    /// Stays the same from frame to frame as long as the same features stay in contact,
+    /// so the solver can carry impulses over even when the point itself drifts.
+    pub id: u32,
 }
 
 /// Collision manifold containing contact information between two entities.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Manifold {
     /// Index of the first entity.
     pub a: usize,
@@ -24,10 +35,22 @@ pub struct Manifold {
     pub tangent: Vec2,
     /// Contact points.
     pub points: Vec<ContactPoint>,
+    /// Combined friction of the two bodies' materials (`Material::combine`).
+    pub friction: Scalar,
+    /// Combined restitution.
+    pub restitution: Scalar,
+    /// Combined rolling resistance.
+    pub rolling_resistance: Scalar,
 }
 
 impl Manifold {
-    pub fn new(a: usize, b: usize, normal: Vec2, points: Vec<ContactPoint>) -> Self {
+    pub fn new(
+        a: usize,
+        b: usize,
+        normal: Vec2,
+        points: Vec<ContactPoint>,
+        material: Material,
+    ) -> Self {
         let tangent = normal.perp();
         Self {
             a,
@@ -35,6 +58,58 @@ impl Manifold {
             normal,
             tangent,
             points,
+            friction: material.friction,
+            restitution: material.restitution,
+            rolling_resistance: material.rolling_resistance,
+        }
+    }
+
+    /// The combined material stored on this manifold.
+    pub fn material(&self) -> Material {
+        Material::new(self.restitution, self.friction)
+            .with_rolling_resistance(self.rolling_resistance)
+    }
+
+    /// Keep at most `max` of the points (at least one), in their original order.
+    ///
+    /// The deepest point stays, so the overlap is still pushed out. Of equally deep points
+    /// (a flat face lying on another) the outermost one along the tangent is taken. Each
+    /// further point is the one farthest from those already kept: for two, the ends of the
+    /// contact, which is what keeps the body from rocking about it.
+    pub fn reduce_points(&mut self, max: usize) {
+        let n = self.points.len();
+        let max = max.max(1);
+        if n <= max {
+            return;
+        }
+        let deepest = self
+            .points
+            .iter()
+            .map(|p| p.penetration)
+            .fold(Scalar::NEG_INFINITY, Scalar::max);
+        let along = |i: usize| self.points[i].point.dot(self.tangent);
+        let first = (0..n)
+            .filter(|&i| self.points[i].penetration >= deepest - DEPTH_TIE_TOLERANCE)
+            .min_by(|&i, &j| along(i).total_cmp(&along(j)))
+            .unwrap_or(0);
+
+        let mut keep = vec![false; n];
+        keep[first] = true;
+        for _ in 1..max {
+            let spread = |i: usize| {
+                (0..n)
+                    .filter(|&j| keep[j])
+                    .map(|j| (self.points[i].point - self.points[j].point).length_squared())
+                    .fold(Scalar::INFINITY, Scalar::min)
+            };
+            let next = (0..n)
+                .filter(|&i| !keep[i])
+                .max_by(|&i, &j| spread(i).total_cmp(&spread(j)));
+            if let Some(i) = next {
+                keep[i] = true;
+            }
         }
+        let mut kept = keep.into_iter();
+        self.points.retain(|_| kept.next() == Some(true));
     }
 }
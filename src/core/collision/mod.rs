@@ -1,11 +1,20 @@
 pub mod broad_phase;
+pub mod bvh;
+pub mod cast;
+pub mod distance;
+pub mod incremental_sap;
 pub mod narrow_phase;
 pub mod shape;
 
 mod box_box;
 mod box_circle;
 mod circle_circle;
+mod circle_segment;
 mod manifold;
 
+pub use bvh::Bvh;
+pub use cast::{RayHit, ShapeHit};
+pub use distance::closest_points;
+pub use incremental_sap::IncrementalSap;
 pub use manifold::{ContactPoint, Manifold};
 pub use shape::{Aabb, Collider2D};
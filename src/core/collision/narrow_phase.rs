@@ -1,80 +1,703 @@
-use super::manifold::Manifold;
-use super::{Collider2D, box_box, box_circle, circle_circle};
-use crate::core::body::PhysicalEntity;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::ops::Range;
+
+use super::broad_phase::speculative_margin;
+use super::manifold::{ContactPoint, Manifold};
+use super::shape::chain_segments;
+use super::{Collider2D, box_box, box_circle, circle_circle, circle_segment, distance};
+use crate::core::body::{Material, PhysicalEntity};
 use crate::core::params::SimParams;
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
+use crate::math::vec::Vec2;
 
-fn build_manifold_for_pair(
-    index_a: usize,
-    index_b: usize,
-    entity_a: &dyn PhysicalEntity,
-    entity_b: &dyn PhysicalEntity,
-    params: SimParams,
-) -> Option<Manifold> {
-    let collider_a = entity_a.collider()?;
-    let collider_b = entity_b.collider()?;
-    let angle_a = entity_a.angle();
-    let angle_b = entity_b.angle();
+/// Contacts for pairs of primitive shapes: a normal (A→B) per pair, with its points as a
+/// range of one shared list, so detecting a pair allocates nothing once the buffers have
+/// grown.
+#[derive(Debug, Default)]
+pub(crate) struct ShapeContacts {
+    entries: Vec<(Vec2, Range<usize>)>,
+    points: Vec<ContactPoint>,
+}
+
+impl ShapeContacts {
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.points.clear();
+    }
+
+    /// Normal and points of every pair.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (Vec2, &[ContactPoint])> {
+        self.entries
+            .iter()
+            .map(|(normal, range)| (*normal, &self.points[range.clone()]))
+    }
+
+    /// Keep only the entries from `first` on whose normal passes `keep` (in order). Their
+    /// dropped points stay in the list, unreferenced.
+    fn retain_from(&mut self, first: usize, keep: impl Fn(Vec2) -> bool) {
+        let mut kept = first;
+        for i in first..self.entries.len() {
+            if keep(self.entries[i].0) {
+                self.entries.swap(kept, i);
+                kept += 1;
+            }
+        }
+        self.entries.truncate(kept);
+    }
+}
+
+/// Feature-id bits reserved for compound part indices (part index + 1, so 0 means "no part").
+const PART_A_SHIFT: u32 = 16;
+const PART_B_SHIFT: u32 = 24;
+
+/// Box overlap (as a fraction of the smallest half extent of the pair) beyond which the
+/// clipper's axis is double-checked with EPA.
+const DEEP_PENETRATION_FRACTION: Scalar = 0.25;
+/// Cosine above which the EPA and clipper normals count as the same axis.
+const DEEP_NORMAL_AGREEMENT: Scalar = 0.999;
+/// Feature id of the single EPA contact point (clear of the box-box ids).
+const EPA_FEATURE_ID: u32 = 1 << 14;
+
+/// Cosine above which the GJK normal of a rounded box agrees with the clipper's, so the
+/// closest features are face to face rather than at a rounded corner.
+const CORNER_NORMAL_AGREEMENT: Scalar = 0.999;
+/// Feature id of the single point of a rounded corner contact (clear of the box-box and EPA
+/// ids).
+const ROUNDED_CORNER_FEATURE_ID: u32 = 1 << 13;
 
-    let (normal, contacts) = match (collider_a, collider_b) {
+/// Slack for the A→B normal check, for shapes whose centers (nearly) coincide.
+const NORMAL_CHECK_TOLERANCE: Scalar = 1e-4;
+
+/// How far (cosine) a chain contact normal may lean along the chain before it counts as
+/// belonging to the end vertex of its segment rather than to the segment's face.
+const CHAIN_NORMAL_TOLERANCE: Scalar = 1e-3;
+
+/// Contact normal between two primitive colliders at the given poses, with the contact
+/// points pushed onto `points` (nothing is pushed without a contact).
+fn detect_primitive(
+    collider_a: &Collider2D,
+    xf_a: &Transform2D,
+    collider_b: &Collider2D,
+    xf_b: &Transform2D,
+    speculative_distance: Scalar,
+    points: &mut Vec<ContactPoint>,
+) -> Option<Vec2> {
+    match (collider_a, collider_b) {
+        // Rounded boxes: collide the inner box, then account for the rounding radius.
+        (
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            },
+            _,
+        ) => {
+            let core = Collider2D::Box {
+                half_extents: *half_extents,
+            };
+            let start = points.len();
+            let normal = detect_primitive(
+                &core,
+                xf_a,
+                collider_b,
+                xf_b,
+                speculative_distance + radius,
+                points,
+            );
+            inflate(&mut points[start..], *radius);
+            if let Some((corner_normal, cp)) =
+                corner_contact(collider_a, xf_a, collider_b, xf_b, normal)
+            {
+                points.truncate(start);
+                if cp.penetration < -speculative_distance {
+                    return None;
+                }
+                points.push(cp);
+                return Some(corner_normal);
+            }
+            normal
+        }
+        (
+            _,
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            },
+        ) => {
+            let core = Collider2D::Box {
+                half_extents: *half_extents,
+            };
+            let start = points.len();
+            let normal = detect_primitive(
+                collider_a,
+                xf_a,
+                &core,
+                xf_b,
+                speculative_distance + radius,
+                points,
+            );
+            inflate(&mut points[start..], *radius);
+            if let Some((corner_normal, cp)) =
+                corner_contact(collider_a, xf_a, collider_b, xf_b, normal)
+            {
+                points.truncate(start);
+                if cp.penetration < -speculative_distance {
+                    return None;
+                }
+                points.push(cp);
+                return Some(corner_normal);
+            }
+            normal
+        }
         (Collider2D::Circle { radius: ra }, Collider2D::Circle { radius: rb }) => {
             let (n, c) = circle_circle::detect(
-                *entity_a.pos(),
+                xf_a.translation,
                 *ra,
-                *entity_b.pos(),
+                xf_b.translation,
                 *rb,
-                params.speculative_distance,
+                speculative_distance,
             )?;
-            (n, vec![c])
+            points.push(c);
+            Some(n)
         }
         (Collider2D::Box { half_extents }, Collider2D::Circle { radius }) => {
             let (n, c) = box_circle::detect(
-                *entity_a.pos(),
-                angle_a,
+                xf_a,
                 *half_extents,
-                *entity_b.pos(),
+                xf_b.translation,
                 *radius,
-                params.speculative_distance,
+                speculative_distance,
             )?;
-            (n, vec![c])
+            points.push(c);
+            Some(n)
         }
         (Collider2D::Circle { radius }, Collider2D::Box { half_extents }) => {
             let (n, cp) = box_circle::detect(
-                *entity_b.pos(),
-                angle_b,
+                xf_b,
                 *half_extents,
-                *entity_a.pos(),
+                xf_a.translation,
                 *radius,
-                params.speculative_distance,
+                speculative_distance,
             )?;
-            (-n, vec![cp])
+            points.push(cp);
+            Some(-n)
         }
-        (Collider2D::Box { half_extents: hea }, Collider2D::Box { half_extents: heb }) => {
+        (Collider2D::Segment { a, b }, Collider2D::Circle { radius }) => {
+            let (n, c) = circle_segment::detect(
+                xf_a.apply_to_point(*a),
+                xf_a.apply_to_point(*b),
+                xf_b.translation,
+                *radius,
+                speculative_distance,
+            )?;
+            points.push(c);
+            Some(n)
+        }
+        (Collider2D::Circle { radius }, Collider2D::Segment { a, b }) => {
+            let (n, c) = circle_segment::detect(
+                xf_b.apply_to_point(*a),
+                xf_b.apply_to_point(*b),
+                xf_a.translation,
+                *radius,
+                speculative_distance,
+            )?;
+            points.push(c);
+            Some(-n)
+        }
+        (Collider2D::Segment { a, b }, Collider2D::Box { half_extents }) => {
+            let (seg_xf, seg_half) = segment_as_box(*a, *b, xf_a);
+            box_box::detect(
+                &seg_xf,
+                seg_half,
+                xf_b,
+                *half_extents,
+                speculative_distance,
+                points,
+            )
+        }
+        (Collider2D::Box { half_extents }, Collider2D::Segment { a, b }) => {
+            let (seg_xf, seg_half) = segment_as_box(*a, *b, xf_b);
             box_box::detect(
-                *entity_a.pos(),
-                angle_a,
-                *hea,
-                *entity_b.pos(),
-                angle_b,
-                *heb,
-                params.speculative_distance,
-            )?
+                xf_a,
+                *half_extents,
+                &seg_xf,
+                seg_half,
+                speculative_distance,
+                points,
+            )
         }
+        // Segments are only for static geometry, which never collides with itself.
+        (Collider2D::Segment { .. }, Collider2D::Segment { .. }) => None,
+        (Collider2D::Box { half_extents: hea }, Collider2D::Box { half_extents: heb }) => {
+            let start = points.len();
+            let normal = box_box::detect(xf_a, *hea, xf_b, *heb, speculative_distance, points)?;
+            let smallest = hea.x.min(hea.y).min(heb.x).min(heb.y);
+            let deepest = points[start..]
+                .iter()
+                .map(|cp| cp.penetration)
+                .fold(0.0, Scalar::max);
+            if deepest > DEEP_PENETRATION_FRACTION * smallest
+                && let Some((deep_normal, cp)) =
+                    deep_contact(collider_a, xf_a, collider_b, xf_b, normal)
+            {
+                points.truncate(start);
+                points.push(cp);
+                return Some(deep_normal);
+            }
+            Some(normal)
+        }
+        // Compounds and chains are split into their parts by `detect_shapes` before we get here.
+        (Collider2D::Compound { .. } | Collider2D::Chain { .. }, _)
+        | (_, Collider2D::Compound { .. } | Collider2D::Chain { .. }) => None,
+    }
+}
+
+/// A segment as a box of zero height: centered on its midpoint, turned along it. The box
+/// clipper then gives it the same two-point manifolds a thin box would get.
+fn segment_as_box(a: Vec2, b: Vec2, xf: &Transform2D) -> (Transform2D, Vec2) {
+    let edge = b - a;
+    let local = Transform2D::from_pose((a + b) * 0.5, edge.y.atan2(edge.x));
+    (local.then(xf), Vec2::new(0.5 * edge.length(), 0.0))
+}
+
+/// Where the A→B normal check measures from: the shape's origin, or a segment's midpoint
+/// (terrain segments can be far from their body's origin).
+fn check_center(collider: &Collider2D, xf: &Transform2D) -> Vec2 {
+    match collider {
+        Collider2D::Segment { a, b } => xf.apply_to_point((*a + *b) * 0.5),
+        _ => xf.translation,
+    }
+}
+
+/// Contact for two deeply overlapping shapes along the EPA normal, the true direction of
+/// least penetration. A single point: the clipper takes over again once they're shallow.
+///
+/// `None` where EPA agrees with the clipper's normal (keep its full manifold), or gives no
+/// usable answer.
+fn deep_contact(
+    collider_a: &Collider2D,
+    xf_a: &Transform2D,
+    collider_b: &Collider2D,
+    xf_b: &Transform2D,
+    sat_normal: Vec2,
+) -> Option<(Vec2, ContactPoint)> {
+    let (normal, depth, point) = distance::penetration(collider_a, xf_a, collider_b, xf_b)?;
+    if normal.dot(sat_normal) > DEEP_NORMAL_AGREEMENT
+        || (xf_b.translation - xf_a.translation).dot(normal) < -NORMAL_CHECK_TOLERANCE
+    {
+        return None;
+    }
+    let cp = ContactPoint {
+        point,
+        penetration: depth,
+        id: EPA_FEATURE_ID,
+    };
+    Some((normal, cp))
+}
+
+/// Contact for a rounded box whose corner, not its face, is nearest the other shape: a
+/// single point along the line between the cores, at the true depth of the arc.
+///
+/// The clipper sees the core with the radius added on, which is only right face to face:
+/// near a corner its side planes clip away contacts the arc makes. `None` where the cores
+/// overlap, or GJK agrees with the clipper's normal (keep its full manifold).
+fn corner_contact(
+    collider_a: &Collider2D,
+    xf_a: &Transform2D,
+    collider_b: &Collider2D,
+    xf_b: &Transform2D,
+    sat_normal: Option<Vec2>,
+) -> Option<(Vec2, ContactPoint)> {
+    let (normal, gap, point) = distance::core_separation(collider_a, xf_a, collider_b, xf_b)?;
+    if sat_normal.is_some_and(|n| normal.dot(n) > CORNER_NORMAL_AGREEMENT) {
+        return None;
+    }
+    let cp = ContactPoint {
+        point,
+        penetration: -gap,
+        id: ROUNDED_CORNER_FEATURE_ID,
     };
+    Some((normal, cp))
+}
 
-    Some(Manifold::new(index_a, index_b, normal, contacts))
+/// Contact points found against a shape's core, moved out to a surface `radius` further
+/// along the normal. The points stay where the core contact put them.
+fn inflate(points: &mut [ContactPoint], radius: Scalar) {
+    for cp in points {
+        cp.penetration += radius;
+    }
 }
 
+/// Contacts between two colliders, splitting compounds into their parts.
+///
+/// Every colliding pair of primitive parts yields its own `(normal, points)` entry: a manifold
+/// has a single normal, and two parts of an L-shaped body can touch along different normals.
+/// The part indices are folded into the feature ids (`feature_tag`) so warm starting can tell
+/// the contacts of different parts apart.
+fn detect_shapes(
+    collider_a: &Collider2D,
+    xf_a: &Transform2D,
+    collider_b: &Collider2D,
+    xf_b: &Transform2D,
+    speculative_distance: Scalar,
+    feature_tag: u32,
+    out: &mut ShapeContacts,
+) {
+    if let Collider2D::Compound { parts } = collider_a {
+        for (i, (local, part)) in parts.iter().enumerate() {
+            let tag = feature_tag | ((i as u32 + 1) << PART_A_SHIFT);
+            detect_shapes(
+                part,
+                &local.then(xf_a),
+                collider_b,
+                xf_b,
+                speculative_distance,
+                tag,
+                out,
+            );
+        }
+        return;
+    }
+    if let Collider2D::Compound { parts } = collider_b {
+        for (j, (local, part)) in parts.iter().enumerate() {
+            let tag = feature_tag | ((j as u32 + 1) << PART_B_SHIFT);
+            detect_shapes(
+                collider_a,
+                xf_a,
+                part,
+                &local.then(xf_b),
+                speculative_distance,
+                tag,
+                out,
+            );
+        }
+        return;
+    }
+
+    if let Collider2D::Chain { vertices } = collider_a {
+        let to_local = xf_a.rotation.transpose();
+        for (i, segment) in chain_segments(vertices).enumerate() {
+            let tag = feature_tag | ((i as u32 + 1) << PART_A_SHIFT);
+            let first = out.entries.len();
+            detect_shapes(
+                &segment,
+                xf_a,
+                collider_b,
+                xf_b,
+                speculative_distance,
+                tag,
+                out,
+            );
+            out.retain_from(first, |normal| {
+                chain_normal_allowed(vertices, i, to_local.mul_vec2(normal))
+            });
+        }
+        return;
+    }
+    if let Collider2D::Chain { vertices } = collider_b {
+        let to_local = xf_b.rotation.transpose();
+        for (j, segment) in chain_segments(vertices).enumerate() {
+            let tag = feature_tag | ((j as u32 + 1) << PART_B_SHIFT);
+            let first = out.entries.len();
+            detect_shapes(
+                collider_a,
+                xf_a,
+                &segment,
+                xf_b,
+                speculative_distance,
+                tag,
+                out,
+            );
+            out.retain_from(first, |normal| {
+                chain_normal_allowed(vertices, j, to_local.mul_vec2(-normal))
+            });
+        }
+        return;
+    }
+
+    let start = out.points.len();
+    if let Some(normal) = detect_primitive(
+        collider_a,
+        xf_a,
+        collider_b,
+        xf_b,
+        speculative_distance,
+        &mut out.points,
+    ) {
+        // The normal must point from A to B, or the solver pulls the shapes together.
+        // Checked per primitive: a compound's origin need not be anywhere near the part
+        // that touches.
+        debug_assert!(
+            (check_center(collider_b, xf_b) - check_center(collider_a, xf_a)).dot(normal)
+                >= -NORMAL_CHECK_TOLERANCE,
+            "contact normal {normal:?} points from B to A"
+        );
+        for cp in &mut out.points[start..] {
+            cp.id |= feature_tag;
+        }
+        out.entries.push((normal, start..out.points.len()));
+    }
+}
+
+/// Whether a contact on segment `i` of a chain, with `normal` pointing away from the chain
+/// (chain-local), belongs to that segment.
+///
+/// Near a joint the segment's contact can lean back over its neighbour: a box sliding onto
+/// the next segment of a flat floor meets its end face-on and is pushed back, the "internal
+/// edge" snag. Such a normal must stay within the corner between the two segments' faces
+/// (`normal` not pointing back along the neighbour); otherwise the neighbour's own contact
+/// covers it. Only a corner that sticks out has room for it, so in a dip the segment whose
+/// face is touched always wins. The chain's two end points have no neighbour and keep all
+/// their contacts.
+fn chain_normal_allowed(vertices: &[Vec2], i: usize, normal: Vec2) -> bool {
+    let direction = |j: usize| (vertices[j + 1] - vertices[j]).try_normalize();
+    let Some(tangent) = direction(i) else {
+        // A zero-length segment is a point its neighbours already cover.
+        return false;
+    };
+    let along = normal.dot(tangent);
+    if along < -CHAIN_NORMAL_TOLERANCE
+        && i > 0
+        && let Some(prev) = direction(i - 1)
+    {
+        return normal.dot(prev) >= -CHAIN_NORMAL_TOLERANCE;
+    }
+    if along > CHAIN_NORMAL_TOLERANCE
+        && i + 2 < vertices.len()
+        && let Some(next) = direction(i + 1)
+    {
+        return normal.dot(next) <= CHAIN_NORMAL_TOLERANCE;
+    }
+    true
+}
+
+/// All contacts between two colliders at the given poses, compounds split into their parts.
+pub(crate) fn shape_contacts(
+    collider_a: &Collider2D,
+    xf_a: &Transform2D,
+    collider_b: &Collider2D,
+    xf_b: &Transform2D,
+    speculative_distance: Scalar,
+) -> ShapeContacts {
+    let mut contacts = ShapeContacts::default();
+    detect_shapes(
+        collider_a,
+        xf_a,
+        collider_b,
+        xf_b,
+        speculative_distance,
+        0,
+        &mut contacts,
+    );
+    contacts
+}
+
+fn build_manifolds_for_pair(
+    (index_a, index_b): (usize, usize),
+    (entity_a, entity_b): (&dyn PhysicalEntity, &dyn PhysicalEntity),
+    params: SimParams,
+    dt: Scalar,
+    default_material: Material,
+    buffers: &mut PairBuffers,
+    out: &mut Vec<Manifold>,
+) {
+    let (Some(collider_a), Some(collider_b)) = (entity_a.collider(), entity_b.collider()) else {
+        return;
+    };
+
+    // The pair uses the larger of the two bodies' margins.
+    let speculative_distance =
+        speculative_margin(entity_a, params, dt).max(speculative_margin(entity_b, params, dt));
+
+    let PairBuffers { contacts, spare } = buffers;
+    contacts.clear();
+    detect_shapes(
+        collider_a,
+        &entity_a.transform(),
+        collider_b,
+        &entity_b.transform(),
+        speculative_distance,
+        0,
+        contacts,
+    );
+
+    let material = Material::combine(
+        entity_a.material(default_material),
+        entity_b.material(default_material),
+    );
+    for (normal, points) in contacts.iter() {
+        let mut list = spare.pop().unwrap_or_default();
+        list.clear();
+        list.extend_from_slice(points);
+        let mut manifold = Manifold::new(index_a, index_b, normal, list, material);
+        manifold.reduce_points(params.max_contact_points);
+        out.push(manifold);
+    }
+}
+
+/// Direction `body_order` sorts moving bodies along. Skewed rather than straight up: boxes
+/// resting side by side sit at (almost) the same height, and a key that ties there would
+/// swap A and B (and lose the pair's warm start) whenever one of them dipped a hair lower.
+const BODY_ORDER_AXIS: Vec2 = Vec2 { x: 0.6, y: 0.8 };
+
+/// Bodies ordered by what and where they are, never by index: static bodies first, then
+/// along `BODY_ORDER_AXIS` (roughly bottom to top), then by height, left to right and
+/// angle. Ties only between bodies of the same kind at exactly the same pose.
+fn body_order(a: &dyn PhysicalEntity, b: &dyn PhysicalEntity) -> Ordering {
+    let (pa, pb) = (*a.pos(), *b.pos());
+    let moves = |e: &dyn PhysicalEntity| e.inv_mass() != 0.0;
+    moves(a)
+        .cmp(&moves(b))
+        .then(pa.dot(BODY_ORDER_AXIS).total_cmp(&pb.dot(BODY_ORDER_AXIS)))
+        .then(pa.y.total_cmp(&pb.y))
+        .then(pa.x.total_cmp(&pb.x))
+        .then(a.angle().total_cmp(&b.angle()))
+}
+
+/// What `detect_into` reuses from pair to pair: the contacts of the pair at hand, and the
+/// point lists of the manifolds it replaces.
+struct PairBuffers {
+    contacts: ShapeContacts,
+    spare: Vec<Vec<ContactPoint>>,
+}
+
+/// How closely a contact normal must match a one-way platform's up direction (cosine) for
+/// the other body to count as landing on top.
+const ONE_WAY_MIN_ALIGNMENT: Scalar = 0.7;
+
+/// Drop contacts with one-way platforms that a body is passing through.
+///
+/// A contact with a platform is kept only while the other body is on the platform's up side
+/// (contact normal within `ONE_WAY_MIN_ALIGNMENT` of the up direction) and not moving away
+/// along it. A body that touches the platform from the wrong side, or is already overlapping
+/// it from there, is remembered in `passing` and ignored until the two are fully apart, so it
+/// can't get caught half way through.
+pub fn filter_one_way(
+    entities: &[Box<dyn PhysicalEntity>],
+    manifolds: &mut Vec<Manifold>,
+    passing: &mut BTreeSet<(usize, usize)>,
+) {
+    let mut still_passing = BTreeSet::new();
+    manifolds.retain(|m| {
+        let (Some(a), Some(b)) = (entities.get(m.a), entities.get(m.b)) else {
+            return true;
+        };
+        // Platform up direction and contact normal / velocity of the other body relative
+        // to the platform, both seen from the platform.
+        let (up, normal, rel_vel) = match (a.one_way_normal(), b.one_way_normal()) {
+            (Some(up), _) => (up, m.normal, *b.vel() - *a.vel()),
+            (None, Some(up)) => (up, -m.normal, *a.vel() - *b.vel()),
+            (None, None) => return true,
+        };
+        let pair = (m.a.min(m.b), m.a.max(m.b));
+        let overlapping = m.points.iter().any(|p| p.penetration > 0.0);
+
+        if passing.contains(&pair) && overlapping {
+            still_passing.insert(pair);
+            return false;
+        }
+        if normal.dot(up) < ONE_WAY_MIN_ALIGNMENT {
+            // Wrong side: let it through, and keep letting it through while it overlaps.
+            if overlapping {
+                still_passing.insert(pair);
+            }
+            return false;
+        }
+        // On top but moving up and away: nothing to hold.
+        rel_vel.dot(up) <= 0.0
+    });
+    *passing = still_passing;
+}
+
+/// Remove the manifolds involving a sensor (see `PhysicalEntity::is_sensor`) and return the
+/// pairs among them that actually overlap (`(min, max)` index order). Speculative contacts
+/// of a sensor that isn't touching yet don't count.
+pub fn take_sensor_overlaps(
+    entities: &[Box<dyn PhysicalEntity>],
+    manifolds: &mut Vec<Manifold>,
+) -> BTreeSet<(usize, usize)> {
+    let is_sensor = |i: usize| entities.get(i).is_some_and(|e| e.is_sensor());
+    let mut overlaps = BTreeSet::new();
+    manifolds.retain(|m| {
+        if !is_sensor(m.a) && !is_sensor(m.b) {
+            return true;
+        }
+        if m.points.iter().any(|p| p.penetration > 0.0) {
+            overlaps.insert((m.a.min(m.b), m.a.max(m.b)));
+        }
+        false
+    });
+    overlaps
+}
+
+/// Contact manifolds for the candidate `pairs`. Each carries the combined material of its
+/// two bodies, with `default_material` standing in for bodies that have none.
+///
+/// Neither the order of `pairs` nor the body indices decide anything: each pair is detected
+/// with its bodies in `body_order` (so `(i, j)` and `(j, i)` give the same manifold), and the
+/// manifolds come out sorted the same way, by A then B. Within a manifold the points are
+/// sorted by feature id. The solver works through the contacts in this order, so every broad
+/// phase (full sweep, incremental sweep, static BVH) gives bit-identical steps, and so does
+/// the same scene with its bodies added in another order. Only bodies at exactly the same
+/// pose fall back to index order.
+///
+/// `dt` is the step the contacts look ahead over (see `broad_phase::speculative_margin`).
 pub fn detect(
     entities: &[Box<dyn PhysicalEntity>],
     pairs: &[(usize, usize)],
     params: SimParams,
+    dt: Scalar,
+    default_material: Material,
 ) -> Vec<Manifold> {
-    pairs
-        .iter()
-        .filter_map(|&(idx_a, idx_b)| {
-            let entity_a = entities.get(idx_a)?;
-            let entity_b = entities.get(idx_b)?;
-            build_manifold_for_pair(idx_a, idx_b, &**entity_a, &**entity_b, params)
-        })
-        .collect()
+    let mut manifolds = Vec::new();
+    detect_into(
+        entities,
+        pairs,
+        params,
+        dt,
+        default_material,
+        &mut manifolds,
+    );
+    manifolds
+}
+
+/// `detect` into a caller-owned `manifolds` buffer (cleared first), so its allocation is
+/// reused from one step to the next.
+pub fn detect_into(
+    entities: &[Box<dyn PhysicalEntity>],
+    pairs: &[(usize, usize)],
+    params: SimParams,
+    dt: Scalar,
+    default_material: Material,
+    manifolds: &mut Vec<Manifold>,
+) {
+    // Last step's point lists are refilled rather than allocated anew for every manifold.
+    let mut buffers = PairBuffers {
+        contacts: ShapeContacts::default(),
+        spare: manifolds.drain(..).map(|m| m.points).collect(),
+    };
+    // Only called with indices in range: out-of-range pairs are skipped first.
+    let before = |i: usize, j: usize| body_order(&*entities[i], &*entities[j]).then(i.cmp(&j));
+    for &(i, j) in pairs {
+        if i.max(j) >= entities.len() {
+            continue;
+        }
+        let (idx_a, idx_b) = if before(j, i).is_lt() { (j, i) } else { (i, j) };
+        let (entity_a, entity_b) = (&entities[idx_a], &entities[idx_b]);
+        build_manifolds_for_pair(
+            (idx_a, idx_b),
+            (&**entity_a, &**entity_b),
+            params,
+            dt,
+            default_material,
+            &mut buffers,
+            manifolds,
+        );
+    }
+    // Stable: the manifolds of one pair (compound parts) keep their part order.
+    manifolds.sort_by(|m, n| before(m.a, n.a).then_with(|| before(m.b, n.b)));
+    for m in manifolds.iter_mut() {
+        m.points.sort_by_key(|cp| cp.id);
+    }
 }
@@ -1,10 +1,51 @@
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
 use crate::math::vec::Vec2;
 
 pub enum Collider2D {
-    Circle { radius: f32 },
-    Box { half_extents: Vec2 },
+    Circle {
+        radius: Scalar,
+    },
+    Box {
+        half_extents: Vec2,
+    },
+    /// A box with rounded corners: the inner box swept by a circle of `radius`, so the
+    /// outer size is `half_extents + radius`. Stacks of these settle without the snagging
+    /// and contact spikes of sharp corners.
+    RoundedBox {
+        half_extents: Vec2,
+        radius: Scalar,
+    },
+    /// A zero-thickness line from `a` to `b` in body-local space, for thin walls and ground
+    /// lines; terrain is a compound of segments placed end to end.
+    ///
+    /// Segments are two-sided and collide with circles, boxes and rounded boxes (not with
+    /// each other). They have no area, so they belong on static bodies (mass 0): a thin box
+    /// is the shape for a wall that has to move.
+    Segment {
+        a: Vec2,
+        b: Vec2,
+    },
+    /// Segments joining `vertices` in order (body-local), for smooth terrain.
+    ///
+    /// Each segment collides like a `Segment`, but where two segments meet a contact is only
+    /// kept if its normal doesn't point into the neighbouring segment, so a body sliding over
+    /// the joint doesn't catch on it. Static bodies only, and on a body of their own rather
+    /// than in a compound (both number their pieces in the contact feature ids).
+    Chain {
+        vertices: Vec<Vec2>,
+    },
+    /// Several shapes welded into one rigid body (e.g. an L-piece made of two boxes).
+    ///
+    /// Each part is placed by its transform relative to the body origin. Parts should be
+    /// primitive (circle / box / rounded box / segment) shapes; non-convex bodies are built
+    /// from convex parts.
+    Compound {
+        parts: Vec<(Transform2D, Collider2D)>,
+    },
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct Aabb {
     pub min: Vec2,
     pub max: Vec2,
@@ -21,10 +62,246 @@ impl Aabb {
             || self.max.y < other.min.y
             || self.min.y > other.max.y)
     }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn half_extents(&self) -> Vec2 {
+        (self.max - self.min) * 0.5
+    }
+
+    /// Smallest box containing both `self` and `other`.
+    pub fn merge(&self, other: &Aabb) -> Aabb {
+        Aabb::new(
+            Vec2::new(self.min.x.min(other.min.x), self.min.y.min(other.min.y)),
+            Vec2::new(self.max.x.max(other.max.x), self.max.y.max(other.max.y)),
+        )
+    }
+
+    /// Overlapping part of the two boxes, or `None` if they don't overlap.
+    pub fn intersection(&self, other: &Aabb) -> Option<Aabb> {
+        if !self.overlaps(other) {
+            return None;
+        }
+        Some(Aabb::new(
+            Vec2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y)),
+            Vec2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y)),
+        ))
+    }
+
+    /// Point of the box closest to `p` (`p` itself if it is inside).
+    pub fn closest_point(&self, p: Vec2) -> Vec2 {
+        Vec2::new(
+            p.x.clamp(self.min.x, self.max.x),
+            p.y.clamp(self.min.y, self.max.y),
+        )
+    }
+
+    /// Points on the boundary count as inside (same convention as `overlaps`).
+    pub fn contains_point(&self, p: Vec2) -> bool {
+        p.x >= self.min.x && p.x <= self.max.x && p.y >= self.min.y && p.y <= self.max.y
+    }
+
+    /// Area of the box (the 2D "surface area" used by SAH tree building).
+    pub fn area(&self) -> Scalar {
+        let size = self.max - self.min;
+        size.x * size.y
+    }
+}
+
+/// Segments per quarter circle when `world_vertices` outlines a rounded box.
+const ROUNDED_CORNER_SEGMENTS: usize = 6;
+
+/// The segments of a chain, in order.
+pub(crate) fn chain_segments(vertices: &[Vec2]) -> impl Iterator<Item = Collider2D> + '_ {
+    vertices
+        .windows(2)
+        .map(|w| Collider2D::Segment { a: w[0], b: w[1] })
+}
+
+fn chain_length(vertices: &[Vec2]) -> Scalar {
+    vertices.windows(2).map(|w| (w[1] - w[0]).length()).sum()
+}
+
+/// Centroid of a compound part in the compound's frame.
+fn part_centroid(local: &Transform2D, part: &Collider2D) -> Vec2 {
+    local.apply_to_point(part.centroid())
 }
 
 impl Collider2D {
-    pub fn inertia_about_center(&self, mass: f32) -> f32 {
+    /// The same shape with its center moved to `offset` in body-local space
+    /// (e.g. an off-center wheel).
+    ///
+    /// The body still rotates about its origin, so the narrow phase, AABB and inertia
+    /// (parallel-axis shift) all see the shape displaced. Internally this is a one-part
+    /// compound; offsetting a compound shifts all of its parts.
+    pub fn with_offset(self, offset: Vec2) -> Self {
+        match self {
+            Collider2D::Compound { mut parts } => {
+                for (local, _) in &mut parts {
+                    local.translation = local.translation + offset;
+                }
+                Collider2D::Compound { parts }
+            }
+            shape => Collider2D::Compound {
+                parts: vec![(Transform2D::from_translation(offset), shape)],
+            },
+        }
+    }
+
+    /// The same shape uniformly scaled about the body origin (compound part offsets too).
+    pub fn scaled(self, factor: Scalar) -> Self {
+        match self {
+            Collider2D::Circle { radius } => Collider2D::Circle {
+                radius: radius * factor,
+            },
+            Collider2D::Box { half_extents } => Collider2D::Box {
+                half_extents: half_extents * factor,
+            },
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => Collider2D::RoundedBox {
+                half_extents: half_extents * factor,
+                radius: radius * factor,
+            },
+            Collider2D::Segment { a, b } => Collider2D::Segment {
+                a: a * factor,
+                b: b * factor,
+            },
+            Collider2D::Chain { vertices } => Collider2D::Chain {
+                vertices: vertices.into_iter().map(|v| v * factor).collect(),
+            },
+            Collider2D::Compound { parts } => Collider2D::Compound {
+                parts: parts
+                    .into_iter()
+                    .map(|(mut local, part)| {
+                        local.translation = local.translation * factor;
+                        (local, part.scaled(factor))
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /// Area of the shape (compound: sum of its parts).
+    pub fn area(&self) -> Scalar {
+        match self {
+            Collider2D::Circle { radius } => crate::math::consts::PI * radius * radius,
+            Collider2D::Box { half_extents } => 4.0 * half_extents.x * half_extents.y,
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => {
+                4.0 * half_extents.x * half_extents.y
+                    + 4.0 * radius * (half_extents.x + half_extents.y)
+                    + crate::math::consts::PI * radius * radius
+            }
+            Collider2D::Segment { .. } | Collider2D::Chain { .. } => 0.0,
+            Collider2D::Compound { parts } => parts.iter().map(|(_, c)| c.area()).sum(),
+        }
+    }
+
+    /// Distance from the body origin to the farthest point of the shape: how far out the
+    /// corners are that sweep an arc when the body spins.
+    pub fn bounding_radius(&self) -> Scalar {
+        match self {
+            Collider2D::Circle { radius } => *radius,
+            Collider2D::Box { half_extents } => half_extents.length(),
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => half_extents.length() + radius,
+            Collider2D::Segment { a, b } => a.length().max(b.length()),
+            Collider2D::Chain { vertices } => {
+                vertices.iter().map(|v| v.length()).fold(0.0, Scalar::max)
+            }
+            Collider2D::Compound { parts } => parts
+                .iter()
+                .map(|(local, part)| local.translation.length() + part.bounding_radius())
+                .fold(0.0, Scalar::max),
+        }
+    }
+
+    /// Farthest point of the shape along `dir_local`, in the shape's local frame (the support
+    /// function GJK-style algorithms are built on). Compounds report the farthest point of
+    /// any part, i.e. the support of their convex hull.
+    ///
+    /// Ties (e.g. `dir_local` along a box axis) resolve to the corner on the positive side.
+    /// A zero direction gives the shape's own origin for round shapes.
+    pub fn support(&self, dir_local: Vec2) -> Vec2 {
+        let round = |radius: Scalar| dir_local.try_normalize().unwrap_or(Vec2::zero()) * radius;
+        let corner = |half: Vec2| {
+            Vec2::new(
+                if dir_local.x >= 0.0 { half.x } else { -half.x },
+                if dir_local.y >= 0.0 { half.y } else { -half.y },
+            )
+        };
+        match self {
+            Collider2D::Circle { radius } => round(*radius),
+            Collider2D::Box { half_extents } => corner(*half_extents),
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => corner(*half_extents) + round(*radius),
+            Collider2D::Segment { a, b } => {
+                if b.dot(dir_local) > a.dot(dir_local) {
+                    *b
+                } else {
+                    *a
+                }
+            }
+            Collider2D::Chain { vertices } => vertices
+                .iter()
+                .copied()
+                .max_by(|p, q| p.dot(dir_local).total_cmp(&q.dot(dir_local)))
+                .unwrap_or(Vec2::zero()),
+            Collider2D::Compound { parts } => parts
+                .iter()
+                .map(|(local, part)| {
+                    let dir_part = local.rotation.transpose().mul_vec2(dir_local);
+                    local.apply_to_point(part.support(dir_part))
+                })
+                .max_by(|p, q| p.dot(dir_local).total_cmp(&q.dot(dir_local)))
+                .unwrap_or(Vec2::zero()),
+        }
+    }
+
+    /// Center of area in the shape's local frame (compound: area-weighted centroid of its
+    /// parts, which need not be at the body origin).
+    pub fn centroid(&self) -> Vec2 {
+        match self {
+            Collider2D::Circle { .. } | Collider2D::Box { .. } | Collider2D::RoundedBox { .. } => {
+                Vec2::zero()
+            }
+            Collider2D::Segment { a, b } => (*a + *b) * 0.5,
+            // Length-weighted midpoint of the segments.
+            Collider2D::Chain { vertices } => {
+                let length = chain_length(vertices);
+                if length <= 0.0 {
+                    return vertices.first().copied().unwrap_or(Vec2::zero());
+                }
+                vertices
+                    .windows(2)
+                    .map(|w| (w[0] + w[1]) * (0.5 * (w[1] - w[0]).length() / length))
+                    .fold(Vec2::zero(), |acc, c| acc + c)
+            }
+            Collider2D::Compound { parts } => {
+                let total_area = self.area();
+                if total_area <= 0.0 {
+                    return Vec2::zero();
+                }
+                parts
+                    .iter()
+                    .map(|(local, part)| part_centroid(local, part) * (part.area() / total_area))
+                    .fold(Vec2::zero(), |acc, c| acc + c)
+            }
+        }
+    }
+
+    /// Moment of inertia about the shape's `centroid` for a uniform density.
+    pub fn inertia_about_centroid(&self, mass: Scalar) -> Scalar {
         if mass <= 0.0 {
             return 0.0;
         }
@@ -35,23 +312,174 @@ impl Collider2D {
                 let h = half_extents.y * 2.0;
                 mass * (w * w + h * h) / 12.0
             }
+            // Approximated by the outer (sharp-cornered) box.
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => {
+                let w = (half_extents.x + radius) * 2.0;
+                let h = (half_extents.y + radius) * 2.0;
+                mass * (w * w + h * h) / 12.0
+            }
+            // A thin rod.
+            Collider2D::Segment { a, b } => mass * (*b - *a).length_squared() / 12.0,
+            // Mass split by length over the segments, as a compound splits it by area.
+            Collider2D::Chain { vertices } => {
+                let length = chain_length(vertices);
+                if length <= 0.0 {
+                    return 0.0;
+                }
+                let centroid = self.centroid();
+                vertices
+                    .windows(2)
+                    .map(|w| {
+                        let segment = Collider2D::Segment { a: w[0], b: w[1] };
+                        let part_mass = mass * (w[1] - w[0]).length() / length;
+                        let d = segment.centroid() - centroid;
+                        segment.inertia_about_centroid(part_mass) + part_mass * d.length_squared()
+                    })
+                    .sum()
+            }
+            Collider2D::Compound { parts } => {
+                // Split the mass by area, then shift each part's inertia to the compound's
+                // centroid with the parallel-axis theorem: I = I_part + m_part * d^2.
+                let total_area = self.area();
+                if total_area <= 0.0 {
+                    return 0.0;
+                }
+                let centroid = self.centroid();
+                parts
+                    .iter()
+                    .map(|(local, part)| {
+                        let part_mass = mass * part.area() / total_area;
+                        let d = part_centroid(local, part) - centroid;
+                        part.inertia_about_centroid(part_mass) + part_mass * d.length_squared()
+                    })
+                    .sum()
+            }
+        }
+    }
+
+    /// Moment of inertia about the body origin for a uniform density: the inertia about
+    /// the centroid, shifted out to the origin (parallel-axis theorem).
+    pub fn inertia_about_center(&self, mass: Scalar) -> Scalar {
+        self.inertia_about(Vec2::zero(), mass)
+    }
+
+    /// Moment of inertia about a body-local `point` (a body's center of mass, say) for a
+    /// uniform density, shifted out from the centroid like `inertia_about_center`.
+    pub fn inertia_about(&self, point: Vec2, mass: Scalar) -> Scalar {
+        if mass <= 0.0 {
+            return 0.0;
+        }
+        self.inertia_about_centroid(mass) + mass * (self.centroid() - point).length_squared()
+    }
+
+    /// Outline of the shape placed at `xf`, counter-clockwise, in world space.
+    ///
+    /// Boxes give their four corners. Rounded boxes give a polygon that follows the corner
+    /// arcs (`ROUNDED_CORNER_SEGMENTS` segments each). Segments give their two end points
+    /// and chains their vertices (an open outline).
+    /// Circles give nothing: their `radius`
+    /// is the whole geometry. Compounds give the outlines of all parts one after another; to
+    /// draw them separately, walk `parts` placing each at `local.then(xf)`.
+    pub fn world_vertices(&self, xf: &Transform2D) -> Vec<Vec2> {
+        let corners = |h: Vec2| {
+            [
+                Vec2::new(-h.x, -h.y),
+                Vec2::new(h.x, -h.y),
+                Vec2::new(h.x, h.y),
+                Vec2::new(-h.x, h.y),
+            ]
+        };
+        match self {
+            Collider2D::Circle { .. } => Vec::new(),
+            Collider2D::Box { half_extents } => corners(*half_extents)
+                .into_iter()
+                .map(|p| xf.apply_to_point(p))
+                .collect(),
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => {
+                // Each inner corner is swept by a quarter circle; corner `i` (starting at the
+                // bottom left) owns the arc from `(i + 2) * 90°` to `(i + 3) * 90°`.
+                let quarter = crate::math::consts::FRAC_PI_2;
+                corners(*half_extents)
+                    .into_iter()
+                    .enumerate()
+                    .flat_map(|(i, corner)| {
+                        (0..=ROUNDED_CORNER_SEGMENTS).map(move |k| {
+                            let t = (i as Scalar
+                                + 2.0
+                                + k as Scalar / ROUNDED_CORNER_SEGMENTS as Scalar)
+                                * quarter;
+                            corner + Vec2::new(t.cos(), t.sin()) * *radius
+                        })
+                    })
+                    .map(|p| xf.apply_to_point(p))
+                    .collect()
+            }
+            Collider2D::Segment { a, b } => vec![xf.apply_to_point(*a), xf.apply_to_point(*b)],
+            Collider2D::Chain { vertices } => {
+                vertices.iter().map(|&v| xf.apply_to_point(v)).collect()
+            }
+            Collider2D::Compound { parts } => parts
+                .iter()
+                .flat_map(|(local, part)| part.world_vertices(&local.then(xf)))
+                .collect(),
         }
     }
 
-    pub fn aabb(&self, pos: Vec2, angle: f32) -> Aabb {
+    /// World-space bounds of the shape placed at `xf`.
+    pub fn aabb(&self, xf: &Transform2D) -> Aabb {
+        let pos = xf.translation;
         match self {
             Collider2D::Circle { radius } => {
                 let ext = Vec2::new(*radius, *radius);
                 Aabb::new(pos - ext, pos + ext)
             }
             Collider2D::Box { half_extents } => {
-                let c = angle.cos().abs();
-                let s = angle.sin().abs();
-                let ex = c * half_extents.x + s * half_extents.y;
-                let ey = s * half_extents.x + c * half_extents.y;
+                let rot = xf.rotation;
+                let ex = rot.m00.abs() * half_extents.x + rot.m01.abs() * half_extents.y;
+                let ey = rot.m10.abs() * half_extents.x + rot.m11.abs() * half_extents.y;
                 let ext = Vec2::new(ex, ey);
                 Aabb::new(pos - ext, pos + ext)
             }
+            Collider2D::RoundedBox {
+                half_extents,
+                radius,
+            } => {
+                let core = Collider2D::Box {
+                    half_extents: *half_extents,
+                }
+                .aabb(xf);
+                let ext = Vec2::new(*radius, *radius);
+                Aabb::new(core.min - ext, core.max + ext)
+            }
+            Collider2D::Segment { a, b } => {
+                let (pa, pb) = (xf.apply_to_point(*a), xf.apply_to_point(*b));
+                Aabb::new(
+                    Vec2::new(pa.x.min(pb.x), pa.y.min(pb.y)),
+                    Vec2::new(pa.x.max(pb.x), pa.y.max(pb.y)),
+                )
+            }
+            Collider2D::Chain { vertices } => vertices
+                .iter()
+                .map(|&v| {
+                    let p = xf.apply_to_point(v);
+                    Aabb::new(p, p)
+                })
+                .reduce(|acc, aabb| acc.merge(&aabb))
+                .unwrap_or(Aabb::new(pos, pos)),
+            Collider2D::Compound { parts } => {
+                // Union of the transformed part AABBs.
+                parts
+                    .iter()
+                    .map(|(local, part)| part.aabb(&local.then(xf)))
+                    .reduce(|acc, aabb| acc.merge(&aabb))
+                    .unwrap_or(Aabb::new(pos, pos))
+            }
         }
     }
 }
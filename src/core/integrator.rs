@@ -1,4 +1,7 @@
 use super::body::PhysicalEntity;
+use crate::math::Scalar;
+use crate::math::mat::Mat2;
+use crate::math::vec::Vec2;
 
 #[derive(Clone, Copy)]
 pub enum Integrator {
@@ -10,7 +13,7 @@ pub enum Integrator {
 /// Integrate linear/angular velocity using accumulated force/torque.
 ///
 /// Note: this does NOT clear force/torque; the caller controls accumulator lifetime.
-pub fn integrate_velocity(entity: &mut dyn PhysicalEntity, dt: f32, integrator: Integrator) {
+pub fn integrate_velocity(entity: &mut dyn PhysicalEntity, dt: Scalar, integrator: Integrator) {
     if dt <= 0.0 {
         return;
     }
@@ -25,15 +28,30 @@ pub fn integrate_velocity(entity: &mut dyn PhysicalEntity, dt: f32, integrator:
 }
 
 /// Integrate position/orientation from the current velocity.
-pub fn integrate_position(entity: &mut dyn PhysicalEntity, dt: f32, _integrator: Integrator) {
+pub fn integrate_position(entity: &mut dyn PhysicalEntity, dt: Scalar, _integrator: Integrator) {
     if dt <= 0.0 {
         return;
     }
-    *entity.pos_mut() = *entity.pos() + *entity.vel() * dt;
-    *entity.angle_mut() = entity.angle() + entity.omega() * dt;
+    let (dp, da) = (*entity.vel() * dt, entity.omega() * dt);
+    move_body(entity, dp, da);
 }
 
-pub fn integrate(entity: &mut dyn PhysicalEntity, dt: f32, integrator: Integrator) {
+/// Move the center of mass by `dp` and turn the body about it by `da`.
+pub(crate) fn move_body(entity: &mut dyn PhysicalEntity, dp: Vec2, da: Scalar) {
+    let com = entity.center_of_mass();
+    let turned = if da != 0.0 && com.length_squared() > 0.0 {
+        // The origin swings around the center of mass.
+        let before = Mat2::rotation(entity.angle()).mul_vec2(com);
+        let after = Mat2::rotation(entity.angle() + da).mul_vec2(com);
+        before - after
+    } else {
+        Vec2::zero()
+    };
+    *entity.pos_mut() = *entity.pos() + dp + turned;
+    *entity.angle_mut() = entity.angle() + da;
+}
+
+pub fn integrate(entity: &mut dyn PhysicalEntity, dt: Scalar, integrator: Integrator) {
     match integrator {
         Integrator::ExplicitEuler => {
             // Euler: position from old velocity, then update velocity.
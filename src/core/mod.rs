@@ -1,13 +1,20 @@
 pub mod body;
+pub mod character;
 pub mod collision;
 pub mod integrator;
 pub mod params;
 pub mod solver;
 pub mod world;
 
-pub use body::{Particle, PhysicalEntity, RigidBody};
-pub use collision::{Aabb, Collider2D};
+pub use body::{Material, Particle, PhysicalEntity, RigidBody, RigidBodyBuilder};
+pub use character::{CharacterController, MoveResult};
+pub use collision::{Aabb, Collider2D, RayHit, ShapeHit};
 pub use integrator::Integrator;
 pub use params::SimParams;
-pub use solver::{ConstraintSolver, ContactConstraint};
-pub use world::World;
+pub use solver::{
+    BodyStore, Constraint, ConstraintSolver, ContactConstraint, ContactImpulse, ContactSoftness,
+    JointBreak, PulleyJoint, SoftCoefficients, SolverParams, WeldJoint,
+};
+pub use world::{
+    ContactView, Falloff, StepStats, TriggerEvents, World, WorldState, renumber_after_removal,
+};
@@ -1,3 +1,5 @@
+use crate::math::Scalar;
+
 /// Global simulation parameters that should be consistent across broad-phase, narrow-phase,
 /// and the solver.
 ///
@@ -9,13 +11,31 @@ pub struct SimParams {
     /// Used to:
     /// - expand broad-phase AABBs (fat AABB)
     /// - allow narrow-phase to emit contacts slightly before overlap
-    pub speculative_distance: f32,
+    pub speculative_distance: Scalar,
+
+    /// Largest linear speed (m/s) a body may leave the solver with; `Scalar::INFINITY` = no
+    /// limit. The sign is ignored, and NaN means no limit.
+    ///
+    /// A safety net for stress scenes: one bad contact can otherwise launch a body and wreck
+    /// the rest of the stack.
+    pub max_linear_speed: Scalar,
+    /// Largest angular speed (rad/s) a body may leave the solver with; `Scalar::INFINITY` =
+    /// no limit. The sign is ignored, and NaN means no limit.
+    pub max_angular_speed: Scalar,
+
+    /// Most contact points a manifold keeps (`Manifold::reduce_points`); at least one is
+    /// always kept. The box clipper gives at most 2, so for now only 1 makes a difference:
+    /// a single point per manifold is cheaper to solve, but lets a resting box rock.
+    pub max_contact_points: usize,
 }
 
 impl Default for SimParams {
     fn default() -> Self {
         Self {
             speculative_distance: 0.05,
+            max_linear_speed: Scalar::INFINITY,
+            max_angular_speed: Scalar::INFINITY,
+            max_contact_points: 2,
         }
     }
 }
@@ -0,0 +1,221 @@
+use crate::core::body::PhysicalEntity;
+use crate::math::Scalar;
+use crate::math::mat::Mat2;
+use crate::math::vec::Vec2;
+
+/// Solver-side copy of the body state as parallel arrays (structure of arrays).
+///
+/// The iteration loop reads and writes these directly instead of making virtual calls
+/// through `Box<dyn PhysicalEntity>`. `load` copies the entities in at the start of the
+/// solve and `store_velocities` writes the result back; nothing else touches the entities
+/// in between. The TGS predicted deltas live here as well.
+///
+/// Custom `Constraint`s read and write it through the indices of the entities they connect.
+#[derive(Default)]
+pub struct BodyStore {
+    /// World-space center of mass (`PhysicalEntity::world_center_of_mass`) at the start of
+    /// the solve: the point the body turns about, which anchors are measured from.
+    pub pos: Vec<Vec2>,
+    pub angle: Vec<Scalar>,
+    pub vel: Vec<Vec2>,
+    pub omega: Vec<Scalar>,
+    pub inv_mass: Vec<Scalar>,
+    pub inv_inertia: Vec<Scalar>,
+    /// Body-local centers of mass, to get back to the entities' origins.
+    pub center_of_mass: Vec<Vec2>,
+    /// Predicted position change over the step (velocity * dt, kept in sync by the solver).
+    /// With substeps: the change so far.
+    pub delta_pos: Vec<Vec2>,
+    /// Predicted angle change over the step.
+    pub delta_angle: Vec<Scalar>,
+    /// Push-out velocity the relax pass took back out of `vel` (see `record_push_out`).
+    pub push_vel: Vec<Vec2>,
+    /// Acceleration from the force / torque accumulators, for substepping.
+    pub accel: Vec<Vec2>,
+    pub alpha: Vec<Scalar>,
+    /// How far earlier substeps of this step have moved each body; zero without substeps.
+    pub moved_pos: Vec<Vec2>,
+    pub moved_angle: Vec<Scalar>,
+    /// Set by the solver for a substepped solve; see `sync_delta`.
+    pub substepping: bool,
+    /// Set by the solver during the relax iterations; see `record_push_out`.
+    pub relaxing: bool,
+}
+
+impl BodyStore {
+    pub fn len(&self) -> usize {
+        self.pos.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pos.is_empty()
+    }
+
+    pub fn load(&mut self, entities: &[Box<dyn PhysicalEntity>]) {
+        let n = entities.len();
+        self.pos.clear();
+        self.angle.clear();
+        self.vel.clear();
+        self.omega.clear();
+        self.inv_mass.clear();
+        self.inv_inertia.clear();
+        self.center_of_mass.clear();
+        self.accel.clear();
+        self.alpha.clear();
+        self.pos.reserve(n);
+        for e in entities {
+            self.pos.push(e.world_center_of_mass());
+            self.angle.push(e.angle());
+            self.vel.push(*e.vel());
+            self.omega.push(e.omega());
+            self.inv_mass.push(e.inv_mass());
+            self.inv_inertia.push(e.inv_inertia());
+            self.center_of_mass.push(e.center_of_mass());
+            self.accel.push(*e.force() * e.inv_mass());
+            self.alpha.push(e.torque() * e.inv_inertia());
+        }
+        self.delta_pos.resize(n, Vec2::zero());
+        self.delta_angle.resize(n, 0.0);
+        self.moved_pos.clear();
+        self.moved_pos.resize(n, Vec2::zero());
+        self.moved_angle.clear();
+        self.moved_angle.resize(n, 0.0);
+        self.clear_push_out();
+    }
+
+    /// Start of a substep: apply the step's accelerations for `h`.
+    pub fn integrate_velocities(&mut self, h: Scalar) {
+        for i in 0..self.len() {
+            self.vel[i] = self.vel[i] + self.accel[i] * h;
+            self.omega[i] += self.alpha[i] * h;
+        }
+    }
+
+    /// End of a substep: advance `moved_*` by `h`. With `push_out` the linear part adds the
+    /// recorded push-out, so it lands in the positions like `apply_push_out` does for a
+    /// single step.
+    pub fn integrate_positions(&mut self, h: Scalar, push_out: bool) {
+        for i in 0..self.len() {
+            let vel = if push_out {
+                self.vel[i] + self.push_vel[i]
+            } else {
+                self.vel[i]
+            };
+            self.moved_pos[i] = self.moved_pos[i] + vel * h;
+            self.moved_angle[i] += self.omega[i] * h;
+        }
+    }
+
+    /// Write the substepped poses back to the entities.
+    pub fn store_positions(&self, entities: &mut [Box<dyn PhysicalEntity>]) {
+        for (i, e) in entities.iter_mut().enumerate().take(self.len()) {
+            let angle = self.angle[i] + self.moved_angle[i];
+            let com = self.center_of_mass[i];
+            let offset = if com.length_squared() > 0.0 {
+                Mat2::rotation(angle).mul_vec2(com)
+            } else {
+                Vec2::zero()
+            };
+            *e.pos_mut() = self.pos[i] + self.moved_pos[i] - offset;
+            *e.angle_mut() = angle;
+        }
+    }
+
+    /// Start recording the push-out afresh, before the relax iterations.
+    pub fn clear_push_out(&mut self) {
+        self.push_vel.clear();
+        self.push_vel.resize(self.len(), Vec2::zero());
+    }
+
+    /// During the relax iterations: a contact the bias was pushing apart changed its
+    /// impulse by `impulse` along `dir` (A to B). What the relax pass takes back from such a
+    /// contact, normal or the friction resisting the push, was correcting the overlap, so
+    /// it still goes into the positions. The other contacts only lose velocity error there
+    /// and aren't recorded.
+    pub fn record_push_out(&mut self, a: usize, b: usize, dir: Vec2, impulse: Scalar) {
+        if !self.relaxing || !self.is_pair(a, b) {
+            return;
+        }
+        self.push_vel[a] = self.push_vel[a] + dir * (self.inv_mass[a] * impulse);
+        self.push_vel[b] = self.push_vel[b] - dir * (self.inv_mass[b] * impulse);
+    }
+
+    /// During the relax iterations: a joint changed the velocity of body `i` from `before`.
+    /// A joint always has a position bias, so all of that is push-out.
+    pub fn record_joint_push_out(&mut self, i: usize, before: Vec2) {
+        if self.relaxing && i < self.len() {
+            self.push_vel[i] = self.push_vel[i] + (before - self.vel[i]);
+        }
+    }
+
+    /// Move the entities by the recorded push-out over `dt` (split impulse: the correction
+    /// goes into the positions but not into the momentum).
+    ///
+    /// Linear only. The angular part of what the relax pass changes is mostly the
+    /// Gauss-Seidel imbalance between a manifold's points; turning that into rotation tips
+    /// over perfectly aligned columns.
+    pub fn apply_push_out(&self, entities: &mut [Box<dyn PhysicalEntity>], dt: Scalar) {
+        for (i, e) in entities.iter_mut().enumerate().take(self.push_vel.len()) {
+            *e.pos_mut() = *e.pos() + self.push_vel[i] * dt;
+        }
+    }
+
+    /// Write the solved velocities back to the entities.
+    pub fn store_velocities(&self, entities: &mut [Box<dyn PhysicalEntity>]) {
+        for (i, e) in entities.iter_mut().enumerate().take(self.len()) {
+            *e.vel_mut() = self.vel[i];
+            *e.omega_mut() = self.omega[i];
+        }
+    }
+
+    /// `i` and `j` are two different, loaded bodies.
+    #[inline]
+    pub fn is_pair(&self, i: usize, j: usize) -> bool {
+        i != j && i < self.len() && j < self.len()
+    }
+
+    /// Velocity of the point at offset `r` from body `i`'s center of mass.
+    #[inline]
+    pub fn velocity_at(&self, i: usize, r: Vec2) -> Vec2 {
+        let w = self.omega[i];
+        self.vel[i] + Vec2::new(-w * r.y, w * r.x)
+    }
+
+    /// Apply a linear impulse at offset `r`, plus a pure angular impulse, to body `i`.
+    #[inline]
+    pub fn apply_impulse(&mut self, i: usize, r: Vec2, impulse: Vec2, angular: Scalar) {
+        self.vel[i] = self.vel[i] + self.inv_mass[i] * impulse;
+        self.omega[i] += self.inv_inertia[i] * (r.cross(impulse) + angular);
+    }
+
+    /// Re-derive the deltas of body `i`.
+    ///
+    /// A single solve predicts the end-of-step pose from the current velocity. Substeps
+    /// integrate the positions for real in between, so there the delta is just what the
+    /// earlier substeps moved the body: the bias works on the current separation.
+    #[inline]
+    pub fn sync_delta(&mut self, i: usize, dt: Scalar) {
+        if dt <= 0.0 {
+            return;
+        }
+        if self.substepping {
+            self.delta_pos[i] = self.moved_pos[i];
+            self.delta_angle[i] = self.moved_angle[i];
+        } else {
+            self.delta_pos[i] = self.vel[i] * dt;
+            self.delta_angle[i] = self.omega[i] * dt;
+        }
+    }
+
+    /// Deltas for the start of a solve or substep (all zero if `dt <= 0`).
+    pub fn init_deltas(&mut self, dt: Scalar) {
+        for i in 0..self.len() {
+            if dt <= 0.0 {
+                self.delta_pos[i] = Vec2::zero();
+                self.delta_angle[i] = 0.0;
+            } else {
+                self.sync_delta(i, dt);
+            }
+        }
+    }
+}
@@ -1,7 +1,12 @@
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 
-use crate::core::body::PhysicalEntity;
-use crate::core::collision::{ContactPoint, Manifold};
+use super::body_store::BodyStore;
+use super::pipeline::Constraint;
+use super::rolling::RollingResistance;
+use crate::core::body::{Material, PhysicalEntity};
+use crate::core::collision::{Collider2D, ContactPoint, Manifold};
+use crate::core::world::renumber_after_removal;
+use crate::math::Scalar;
 use crate::math::mat::Mat2;
 use crate::math::vec::Vec2;
 
@@ -13,27 +18,76 @@ pub struct ContactConstraint {
     pub tangent: Vec2,
     pub local_anchor_a: Vec2,
     pub local_anchor_b: Vec2,
-    pub base_separation: f32,
-    pub normal_mass: f32,
-    pub tangent_mass: f32,
-    pub jn: f32,
-    pub jt: f32,
-    /// Initial relative normal velocity (for restitution)
-    pub relative_velocity: f32,
+    /// Feature id of the contact point (see `ContactPoint::id`), used to match warm-start impulses.
+    pub feature_id: u32,
+    /// World-space contact point when the constraint was built.
+    pub point: Vec2,
+    pub base_separation: Scalar,
+    pub normal_mass: Scalar,
+    pub tangent_mass: Scalar,
+    pub jn: Scalar,
+    pub jt: Scalar,
+    /// Combined friction coefficient of the two bodies' materials.
+    pub friction: Scalar,
+    /// Combined restitution coefficient of the two bodies' materials.
+    pub restitution: Scalar,
+    /// Relative normal velocity the bodies approached with, before this step's forces were
+    /// integrated; restitution reverses this. Sampled once per step, also with substeps.
+    pub relative_velocity: Scalar,
+    /// Consecutive steps this contact already pushed in before this one (0 = new).
+    ///
+    /// Only contacts that carried an impulse are matched from one step to the next, so a
+    /// speculative contact that hasn't touched yet is still new on the step it lands.
+    pub age: u32,
+    /// Separation the bias pushes towards: `-SolverParams::slop`, or 0 for a settled contact
+    /// (see `SolverParams::flush_after`).
+    pub target_separation: Scalar,
+    /// The bias pushed this contact, or another point of its manifold, apart during the
+    /// biased iterations of this (sub)step, so the relax iterations take back push-out from
+    /// it (see `BodyStore::record_push_out`).
+    pub pushing: bool,
 }
 
 impl ContactConstraint {
+    /// One constraint per point of `manifold`, for driving contacts with your own solver
+    /// loop (empty if either body isn't in `entities`). `ConstraintSolver` builds them the
+    /// same way, minus the warm-start impulses and settling it carries between steps.
+    ///
+    /// Build them before the step's forces are integrated: the bounce reverses the
+    /// velocity the bodies have now. Then, on a `BodyStore` loaded with the bodies (and
+    /// `init_deltas` called), per (sub)step:
+    /// 1) `Constraint::warm_start`
+    /// 2) `solve_normal` then `solve_tangent` on every constraint, for each iteration
+    /// 3) `apply_restitution`, once, after the last iteration
+    pub fn from_manifold(
+        manifold: &Manifold,
+        entities: &[Box<dyn PhysicalEntity>],
+        params: &SolverParams,
+    ) -> Vec<ContactConstraint> {
+        let (Some(a), Some(b)) = (entities.get(manifold.a), entities.get(manifold.b)) else {
+            return Vec::new();
+        };
+        manifold
+            .points
+            .iter()
+            .map(|cp| ContactConstraint {
+                target_separation: -params.slop,
+                ..Self::new(manifold, cp, &**a, &**b, 0.0)
+            })
+            .collect()
+    }
+
     fn new(
-        index_a: usize,
-        index_b: usize,
-        normal: Vec2,
+        manifold: &Manifold,
         cp: &ContactPoint,
         a: &dyn PhysicalEntity,
         b: &dyn PhysicalEntity,
+        force_dt: Scalar,
     ) -> Self {
-        // Store anchors in local space so warm-start matching is stable under rotation.
-        let r_a_world0 = cp.point - *a.pos();
-        let r_b_world0 = cp.point - *b.pos();
+        let normal = manifold.normal;
+        // Store anchors in local space so they follow the bodies as they rotate.
+        let r_a_world0 = cp.point - a.world_center_of_mass();
+        let r_b_world0 = cp.point - b.world_center_of_mass();
         let rot_a_t = Mat2::rotation(a.angle()).transpose(); // inverse rotation
         let rot_b_t = Mat2::rotation(b.angle()).transpose(); // inverse rotation
         let local_anchor_a = rot_a_t.mul_vec2(r_a_world0);
@@ -58,253 +112,623 @@ impl ContactConstraint {
         // `penetration` is positive when overlapping, negative when separated.
         let base_separation = -cp.penetration;
 
-        // Save relative velocity for restitution (computed once at constraint build time)
+        // Save relative velocity for restitution (computed once at constraint build time).
+        // It's the approach speed from before this step's forces acted on the bodies over
+        // `force_dt`: counting gravity would bounce every landing g * dt harder than it hit,
+        // and a bouncy ball would never come to rest.
+        let velocity_at = |r: Vec2, e: &dyn PhysicalEntity| {
+            let vel = *e.vel() - *e.force() * (e.inv_mass() * force_dt);
+            let omega = e.omega() - e.torque() * e.inv_inertia() * force_dt;
+            vel + Vec2::new(-omega * r.y, omega * r.x)
+        };
         let rel_vel = velocity_at(r_b, b) - velocity_at(r_a, a);
         let relative_velocity = rel_vel.dot(normal);
 
         Self {
-            index_a,
-            index_b,
+            index_a: manifold.a,
+            index_b: manifold.b,
             normal,
             tangent,
             local_anchor_a,
             local_anchor_b,
+            feature_id: cp.id,
+            point: cp.point,
             base_separation,
             normal_mass: eff_mass(normal),
             tangent_mass: eff_mass(tangent),
             jn: 0.0,
             jt: 0.0,
+            friction: manifold.friction,
+            restitution: manifold.restitution,
             relative_velocity,
+            age: 0,
+            target_separation: 0.0,
+            pushing: false,
         }
     }
 
     /// TGS-style normal constraint solve.
     /// Computes current separation using delta_pos/delta_angle, then applies bias.
+    ///
+    /// With `soft` set the push-out is a spring-damper instead of Baumgarte (see
+    /// `ContactSoftness::coefficients`).
     pub fn solve_normal(
         &mut self,
-        entities: &mut [Box<dyn PhysicalEntity>],
-        delta_pos: &mut [Vec2],
-        delta_angle: &mut [f32],
-        dt: f32,
+        bodies: &mut BodyStore,
+        dt: Scalar,
         params: &SolverParams,
+        soft: Option<SoftCoefficients>,
         use_bias: bool,
     ) {
-        let Some((a, b)) = get_pair_mut(entities, self.index_a, self.index_b) else {
+        let (a, b) = (self.index_a, self.index_b);
+        if !bodies.is_pair(a, b) {
             return;
-        };
+        }
 
         // World-space anchors at the start of the step from local anchors.
-        let r_a0 = Mat2::rotation(a.angle()).mul_vec2(self.local_anchor_a);
-        let r_b0 = Mat2::rotation(b.angle()).mul_vec2(self.local_anchor_b);
+        let (r_a0, r_b0) = self.anchors(bodies);
+        let bias = self.normal_bias(bodies, (r_a0, r_b0), dt, params, soft, use_bias);
+
+        // Relative normal velocity at contact
+        let vn = (bodies.velocity_at(b, r_b0) - bodies.velocity_at(a, r_a0)).dot(self.normal);
 
-        // Keep separation linearization consistent with the velocity Jacobian:
-        // r(θ + dθ) ≈ r(θ) + dθ * perp(r(θ))
-        let dr_a = r_a0.perp() * delta_angle[self.index_a];
-        let dr_b = r_b0.perp() * delta_angle[self.index_b];
+        // Incremental normal impulse (soft: scaled mass, and part of the accumulated
+        // impulse is given back, which is what makes the contact a spring).
+        let impulse = -self.normal_mass * bias.mass_scale * (vn + bias.velocity)
+            - bias.impulse_scale * self.jn;
+
+        // Clamp the accumulated impulse
+        let jn_old = self.jn;
+        self.jn = (jn_old + impulse).max(0.0);
+        self.apply_normal(bodies, (r_a0, r_b0), self.jn - jn_old);
+
+        bodies.sync_delta(a, dt);
+        bodies.sync_delta(b, dt);
+    }
+
+    /// Bias of the normal solve at anchors `r0`, from the predicted separation.
+    fn normal_bias(
+        &mut self,
+        bodies: &BodyStore,
+        (r_a0, r_b0): (Vec2, Vec2),
+        dt: Scalar,
+        params: &SolverParams,
+        soft: Option<SoftCoefficients>,
+        use_bias: bool,
+    ) -> NormalBias {
+        let (a, b) = (self.index_a, self.index_b);
+
+        // How far each anchor turned: exactly for what earlier substeps already rotated,
+        // then the rest of the predicted turn linearized consistently with the velocity
+        // Jacobian, r(θ + dθ) ≈ r(θ) + dθ * perp(r(θ)). Without substeps it's all the
+        // linear part. (Linearizing the substeps' turn too put a spinning corner below
+        // where it really was, so it hit surfaces it only passed close to.)
+        let turned = |i: usize, r_start: Vec2, local: Vec2| {
+            let r_now = Mat2::rotation(bodies.angle[i] + bodies.moved_angle[i]).mul_vec2(local);
+            (r_now - r_start) + r_start.perp() * (bodies.delta_angle[i] - bodies.moved_angle[i])
+        };
+        let dr_a = turned(a, r_a0, self.local_anchor_a);
+        let dr_b = turned(b, r_b0, self.local_anchor_b);
 
         // Predicted separation along normal.
-        let dp = delta_pos[self.index_b] - delta_pos[self.index_a];
+        let dp = bodies.delta_pos[b] - bodies.delta_pos[a];
         let separation = self.base_separation + (dp + dr_b - dr_a).dot(self.normal);
 
-        let velocity_bias = if dt <= 0.0 {
-            0.0
+        // Without substeps the separation is predicted from the velocity being solved.
+        let follows = if bodies.substepping { 0.0 } else { dt };
+        let hard = |velocity: Scalar, slope: Scalar| NormalBias {
+            velocity,
+            slope,
+            mass_scale: 1.0,
+            impulse_scale: 0.0,
+        };
+        if dt <= 0.0 {
+            hard(0.0, 0.0)
         } else if separation > 0.0 {
-            separation / dt
+            // Speculative: allow closing the gap, no more. A large bias (fast approach or
+            // wide gap) needs no clamp: the accumulated impulse can't go below zero, so it
+            // only ever slows an approach and never pulls the bodies together.
+            hard(separation / dt, follows / dt)
         } else if use_bias {
-            let c = (separation + params.slop).min(0.0);
-            (params.bias_rate * c / dt).max(-params.max_bias_velocity)
+            let c = (separation - self.target_separation).min(0.0);
+            self.pushing |= c < 0.0;
+            // The cap is per step; `dt` is one substep of it.
+            let step_dt = dt * params.substeps.max(1) as Scalar;
+            let max_bias = params
+                .max_bias_velocity
+                .min(params.max_correction_per_step / step_dt);
+            let rate = match soft {
+                Some(s) => s.bias_rate,
+                None => params.bias_rate / dt,
+            };
+            let velocity = rate * c;
+            let slope = if c < 0.0 && velocity > -max_bias {
+                rate * follows
+            } else {
+                0.0
+            };
+            let (mass_scale, impulse_scale) =
+                soft.map_or((1.0, 0.0), |s| (s.mass_scale, s.impulse_scale));
+            NormalBias {
+                velocity: velocity.max(-max_bias),
+                slope,
+                mass_scale,
+                impulse_scale,
+            }
         } else {
-            0.0
-        };
-
-        // Relative normal velocity at contact
-        let vn = (velocity_at(r_b0, b) - velocity_at(r_a0, a)).dot(self.normal);
-
-        // Incremental normal impulse
-        let impulse = -self.normal_mass * (vn + velocity_bias);
+            hard(0.0, 0.0)
+        }
+    }
 
-        // Clamp the accumulated impulse
-        let jn_old = self.jn;
-        self.jn = (jn_old + impulse).max(0.0);
-        let delta = self.jn - jn_old;
+    /// Apply a change `delta` of the accumulated normal impulse (the deltas aren't synced).
+    fn apply_normal(&self, bodies: &mut BodyStore, (r_a0, r_b0): (Vec2, Vec2), delta: Scalar) {
+        let (a, b) = (self.index_a, self.index_b);
+        apply_impulse_pair(bodies, a, b, r_a0, r_b0, self.normal, delta);
+        if self.pushing {
+            bodies.record_push_out(a, b, self.normal, delta);
+        }
+    }
 
-        apply_impulse_pair(a, b, r_a0, r_b0, self.normal, delta);
+    /// Speed at which the two surfaces slide past each other at this point.
+    pub(super) fn slip_speed(&self, bodies: &BodyStore) -> Scalar {
+        let (r_a0, r_b0) = self.anchors(bodies);
+        let v = bodies.velocity_at(self.index_b, r_b0) - bodies.velocity_at(self.index_a, r_a0);
+        v.dot(self.tangent).abs()
+    }
 
-        sync_pair_deltas(a, b, self.index_a, self.index_b, delta_pos, delta_angle, dt);
+    /// Per-point Coulomb friction: `|jt| <= friction * jn` for this point alone.
+    pub fn solve_tangent(&mut self, bodies: &mut BodyStore, dt: Scalar) {
+        let max_jt = self.friction * self.jn;
+        self.solve_tangent_within(bodies, dt, -max_jt, max_jt);
     }
 
-    pub fn solve_tangent(
+    /// Tangent solve with the accumulated impulse clamped to `[min_jt, max_jt]`.
+    fn solve_tangent_within(
         &mut self,
-        entities: &mut [Box<dyn PhysicalEntity>],
-        delta_pos: &mut [Vec2],
-        delta_angle: &mut [f32],
-        dt: f32,
-        friction: f32,
+        bodies: &mut BodyStore,
+        dt: Scalar,
+        min_jt: Scalar,
+        max_jt: Scalar,
     ) {
-        let Some((a, b)) = get_pair_mut(entities, self.index_a, self.index_b) else {
+        let (a, b) = (self.index_a, self.index_b);
+        if !bodies.is_pair(a, b) {
             return;
-        };
+        }
 
-        let r_a0 = Mat2::rotation(a.angle()).mul_vec2(self.local_anchor_a);
-        let r_b0 = Mat2::rotation(b.angle()).mul_vec2(self.local_anchor_b);
-        let vt = (velocity_at(r_b0, b) - velocity_at(r_a0, a)).dot(self.tangent);
+        let (r_a0, r_b0) = self.anchors(bodies);
+        let vt = (bodies.velocity_at(b, r_b0) - bodies.velocity_at(a, r_a0)).dot(self.tangent);
         let lambda = -self.tangent_mass * vt;
 
-        let max_jt = friction * self.jn;
         let jt_old = self.jt;
-        self.jt = (jt_old + lambda).clamp(-max_jt, max_jt);
+        self.jt = (jt_old + lambda).clamp(min_jt, max_jt);
         let delta = self.jt - jt_old;
 
-        apply_impulse_pair(a, b, r_a0, r_b0, self.tangent, delta);
+        apply_impulse_pair(bodies, a, b, r_a0, r_b0, self.tangent, delta);
+        if self.pushing {
+            bodies.record_push_out(a, b, self.tangent, delta);
+        }
 
-        sync_pair_deltas(a, b, self.index_a, self.index_b, delta_pos, delta_angle, dt);
+        bodies.sync_delta(a, dt);
+        bodies.sync_delta(b, dt);
     }
 
     /// Apply restitution impulse (separate pass, like Box2D).
-    pub fn apply_restitution(
-        &mut self,
-        entities: &mut [Box<dyn PhysicalEntity>],
-        delta_pos: &mut [Vec2],
-        delta_angle: &mut [f32],
-        dt: f32,
-        restitution: f32,
-        threshold: f32,
-    ) {
-        if restitution == 0.0 {
+    ///
+    /// Only new contacts bounce: one that was already pushing last step is resting, and
+    /// bouncing it would feed small kicks into a settling stack.
+    pub fn apply_restitution(&mut self, bodies: &mut BodyStore, dt: Scalar, params: &SolverParams) {
+        if self.restitution == 0.0 || self.age > 0 {
             return;
         }
+        let threshold = (params.restitution_threshold * (1.0 - self.restitution).max(0.0))
+            .max(params.restitution_velocity_floor);
         if self.relative_velocity > -threshold || self.jn == 0.0 {
             return;
         }
 
-        let Some((a, b)) = get_pair_mut(entities, self.index_a, self.index_b) else {
+        let (a, b) = (self.index_a, self.index_b);
+        if !bodies.is_pair(a, b) {
             return;
-        };
+        }
 
-        let r_a0 = Mat2::rotation(a.angle()).mul_vec2(self.local_anchor_a);
-        let r_b0 = Mat2::rotation(b.angle()).mul_vec2(self.local_anchor_b);
-        let vn = (velocity_at(r_b0, b) - velocity_at(r_a0, a)).dot(self.normal);
-        let impulse = -self.normal_mass * (vn + restitution * self.relative_velocity);
+        let (r_a0, r_b0) = self.anchors(bodies);
+        let vn = (bodies.velocity_at(b, r_b0) - bodies.velocity_at(a, r_a0)).dot(self.normal);
+        let impulse = -self.normal_mass * (vn + self.restitution * self.relative_velocity);
 
         let jn_old = self.jn;
         self.jn = (jn_old + impulse).max(0.0);
         let delta = self.jn - jn_old;
 
-        apply_impulse_pair(a, b, r_a0, r_b0, self.normal, delta);
+        apply_impulse_pair(bodies, a, b, r_a0, r_b0, self.normal, delta);
 
-        sync_pair_deltas(a, b, self.index_a, self.index_b, delta_pos, delta_angle, dt);
+        bodies.sync_delta(a, dt);
+        bodies.sync_delta(b, dt);
     }
 
-    fn apply_warm_start(&self, entities: &mut [Box<dyn PhysicalEntity>]) {
+    fn apply_warm_start(&self, bodies: &mut BodyStore) {
         if self.jn == 0.0 && self.jt == 0.0 {
             return;
         }
-        let Some((a, b)) = get_pair_mut(entities, self.index_a, self.index_b) else {
+        let (a, b) = (self.index_a, self.index_b);
+        if !bodies.is_pair(a, b) {
             return;
-        };
-        let r_a0 = Mat2::rotation(a.angle()).mul_vec2(self.local_anchor_a);
-        let r_b0 = Mat2::rotation(b.angle()).mul_vec2(self.local_anchor_b);
-        apply_impulse_pair(a, b, r_a0, r_b0, self.normal, self.jn);
-        apply_impulse_pair(a, b, r_a0, r_b0, self.tangent, self.jt);
+        }
+        let (r_a0, r_b0) = self.anchors(bodies);
+        apply_impulse_pair(bodies, a, b, r_a0, r_b0, self.normal, self.jn);
+        apply_impulse_pair(bodies, a, b, r_a0, r_b0, self.tangent, self.jt);
+    }
+
+    /// World-space anchors at the start of the step, for the impulse Jacobians.
+    ///
+    /// Deliberately not at the predicted angle: positions are integrated from the start
+    /// pose with the solved velocities, so `v + omega x r` at this `r` is how the contact
+    /// point moves. Only the separation follows the rotation (see `solve_normal`).
+    #[inline]
+    fn anchors(&self, bodies: &BodyStore) -> (Vec2, Vec2) {
+        (
+            Mat2::rotation(bodies.angle[self.index_a]).mul_vec2(self.local_anchor_a),
+            Mat2::rotation(bodies.angle[self.index_b]).mul_vec2(self.local_anchor_b),
+        )
     }
 }
 
-#[inline]
-fn velocity_at(r: Vec2, e: &dyn PhysicalEntity) -> Vec2 {
-    *e.vel() + Vec2::new(-e.omega() * r.y, e.omega() * r.x)
+/// A single contact point as a `Constraint`, for solving it on its own.
+///
+/// `ConstraintSolver` itself solves the contacts in batches rather than one by one: every
+/// normal first (per manifold with `block_normal`), then the friction (per manifold with
+/// `block_friction`) and rolling resistance, so friction is clamped against this
+/// iteration's normal impulses.
+/// `prepare` does nothing since `build_constraints` already rescaled the warm-start impulses.
+impl Constraint for ContactConstraint {
+    fn bodies(&self) -> (usize, usize) {
+        (self.index_a, self.index_b)
+    }
+
+    fn warm_start(&self, bodies: &mut BodyStore) {
+        self.apply_warm_start(bodies);
+    }
+
+    fn solve_velocity(
+        &mut self,
+        bodies: &mut BodyStore,
+        dt: Scalar,
+        params: &SolverParams,
+        use_bias: bool,
+    ) {
+        let soft = params.softness.map(|s| s.coefficients(dt));
+        self.solve_normal(bodies, dt, params, soft, use_bias);
+        self.solve_tangent(bodies, dt);
+    }
+
+    fn reset_impulses(&mut self) {
+        self.jn = 0.0;
+        self.jt = 0.0;
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.index_a, index)
+            && renumber_after_removal(&mut self.index_b, index)
+    }
+
+    fn clone_box(&self) -> Box<dyn Constraint> {
+        Box::new(self.clone())
+    }
 }
 
-#[inline]
-fn apply_impulse_pair(
-    a: &mut dyn PhysicalEntity,
-    b: &mut dyn PhysicalEntity,
-    r_a: Vec2,
-    r_b: Vec2,
-    dir: Vec2,
-    magnitude: f32,
-) {
-    let impulse = dir * magnitude;
-    *a.vel_mut() = *a.vel() - a.inv_mass() * impulse;
-    *a.omega_mut() = a.omega() - a.inv_inertia() * r_a.cross(impulse);
-    *b.vel_mut() = *b.vel() + b.inv_mass() * impulse;
-    *b.omega_mut() = b.omega() + b.inv_inertia() * r_b.cross(impulse);
+/// Impulses the solver applied at one contact point during the last step.
+///
+/// Handy for gameplay: threshold `normal_impulse` to detect hard hits (damage, impact
+/// sounds). Divide by the step's `dt` for an average force (by `dt / substeps` when
+/// substepping: the impulses are per substep).
+#[derive(Debug, Clone, Copy)]
+pub struct ContactImpulse {
+    pub a: usize,
+    pub b: usize,
+    /// Accumulated normal impulse (>= 0), pushing `b` away from `a` along the contact normal.
+    pub normal_impulse: Scalar,
+    /// Accumulated friction impulse along the contact tangent.
+    pub tangent_impulse: Scalar,
+    pub point: Vec2,
 }
 
-#[inline]
-fn sync_pair_deltas(
-    a: &dyn PhysicalEntity,
-    b: &dyn PhysicalEntity,
-    index_a: usize,
-    index_b: usize,
-    delta_pos: &mut [Vec2],
-    delta_angle: &mut [f32],
-    dt: f32,
+/// Block friction for all points of one manifold.
+///
+/// Instead of giving every point its own friction cone, the points share one:
+/// `|Σ jt| <= friction * Σ jn`. Each point is still solved in turn (Gauss-Seidel), but its
+/// clamp range is whatever the shared budget leaves after the other points' impulses.
+/// A box resting on two points can then use the friction of both to resist sliding.
+fn solve_tangent_block(contacts: &mut [ContactConstraint], bodies: &mut BodyStore, dt: Scalar) {
+    let max_total: Scalar = contacts.iter().map(|c| c.friction * c.jn).sum();
+    for i in 0..contacts.len() {
+        let others: Scalar = contacts
+            .iter()
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .map(|(_, c)| c.jt)
+            .sum();
+        contacts[i].solve_tangent_within(bodies, dt, -max_total - others, max_total - others);
+    }
+}
+
+/// Largest condition number of a two-point manifold's normal mass matrix that
+/// `solve_normal_block` still inverts (Box2D's `k_maxConditionNumber`). Above it the points
+/// are nearly on top of each other and get solved one at a time.
+const MAX_BLOCK_CONDITION: Scalar = 1000.0;
+
+/// Both normal impulses of a two-point manifold at once (Box2D's block solver).
+///
+/// Solved one point at a time, the impulse at the first point also turns the body, the
+/// second point corrects that turn, and the next iteration starts over. The manifold never
+/// gets an even push: a resting box rocks, tilting its contact normals, and a column of
+/// boxes walks sideways. Here the accumulated impulses `x` solve the 2x2 LCP
+/// `w = vn + bias = K x + b`, `x >= 0`, `w >= 0`, `x · w = 0` exactly, by trying which
+/// points push: both, only the first, only the second, or neither.
+///
+/// Soft contacts, and an ill-conditioned `K`, are solved per point (`solve_normal`).
+fn solve_normal_block(
+    contacts: &mut [ContactConstraint],
+    bodies: &mut BodyStore,
+    dt: Scalar,
+    params: &SolverParams,
+    soft: Option<SoftCoefficients>,
+    use_bias: bool,
 ) {
-    if dt <= 0.0 {
+    let [c1, c2] = contacts else {
+        for c in contacts {
+            c.solve_normal(bodies, dt, params, soft, use_bias);
+        }
+        return;
+    };
+    let (a, b) = (c1.index_a, c1.index_b);
+    if !bodies.is_pair(a, b) {
         return;
     }
-    delta_pos[index_a] = *a.vel() * dt;
-    delta_angle[index_a] = a.omega() * dt;
-    delta_pos[index_b] = *b.vel() * dt;
-    delta_angle[index_b] = b.omega() * dt;
+    let (r1, r2) = (c1.anchors(bodies), c2.anchors(bodies));
+    let normal = c1.normal;
+
+    let (m_a, m_b) = (bodies.inv_mass[a], bodies.inv_mass[b]);
+    let (i_a, i_b) = (bodies.inv_inertia[a], bodies.inv_inertia[b]);
+    let (rn1a, rn1b) = (r1.0.cross(normal), r1.1.cross(normal));
+    let (rn2a, rn2b) = (r2.0.cross(normal), r2.1.cross(normal));
+    let k11 = m_a + m_b + i_a * rn1a * rn1a + i_b * rn1b * rn1b;
+    let k22 = m_a + m_b + i_a * rn2a * rn2a + i_b * rn2b * rn2b;
+    let k12 = m_a + m_b + i_a * rn1a * rn2a + i_b * rn1b * rn2b;
+    let det = k11 * k22 - k12 * k12;
+    if soft.is_some() || k11 * k11 >= MAX_BLOCK_CONDITION * det {
+        c1.solve_normal(bodies, dt, params, soft, use_bias);
+        c2.solve_normal(bodies, dt, params, soft, use_bias);
+        return;
+    }
+
+    // The velocity each point aims for. The bias moves with `vn` (`NormalBias::slope`), so
+    // it's taken where `vn + bias` would be zero rather than at the current `vn`: that's
+    // where solving the point on its own settles too.
+    let target = |c: &mut ContactConstraint, r: (Vec2, Vec2), bodies: &BodyStore| {
+        let vn = (bodies.velocity_at(b, r.1) - bodies.velocity_at(a, r.0)).dot(normal);
+        let bias = c.normal_bias(bodies, r, dt, params, None, use_bias);
+        (vn, (bias.velocity - bias.slope * vn) / (1.0 + bias.slope))
+    };
+    let (vn1, bias1) = target(c1, r1, bodies);
+    let (vn2, bias2) = target(c2, r2, bodies);
+    let (old1, old2) = (c1.jn, c2.jn);
+    // `vn + bias = K x + b` for the accumulated impulses `x`, from the current `x = old`.
+    let b1 = vn1 + bias1 - (k11 * old1 + k12 * old2);
+    let b2 = vn2 + bias2 - (k12 * old1 + k22 * old2);
+
+    let both = (-(k22 * b1 - k12 * b2) / det, -(k11 * b2 - k12 * b1) / det);
+    let first = -b1 / k11;
+    let second = -b2 / k22;
+    let (x1, x2) = if both.0 >= 0.0 && both.1 >= 0.0 {
+        both
+    } else if first >= 0.0 && k12 * first + b2 >= 0.0 {
+        (first, 0.0)
+    } else if second >= 0.0 && k12 * second + b1 >= 0.0 {
+        (0.0, second)
+    } else if b1 >= 0.0 && b2 >= 0.0 {
+        (0.0, 0.0)
+    } else {
+        // No case fits (only through round-off): leave the impulses as they are.
+        return;
+    };
+
+    c1.jn = x1;
+    c2.jn = x2;
+    c1.apply_normal(bodies, r1, x1 - old1);
+    c2.apply_normal(bodies, r2, x2 - old2);
+    bodies.sync_delta(a, dt);
+    bodies.sync_delta(b, dt);
 }
 
-fn get_pair_mut(
-    entities: &mut [Box<dyn PhysicalEntity>],
-    i: usize,
-    j: usize,
-) -> Option<(&mut dyn PhysicalEntity, &mut dyn PhysicalEntity)> {
-    if i == j || i >= entities.len() || j >= entities.len() {
-        return None;
-    }
-    let (lo, hi) = if i < j { (i, j) } else { (j, i) };
-    let (left, right) = entities.split_at_mut(hi);
-    let a = &mut *left[lo];
-    let b = &mut *right[0];
-    Some(if i < j { (a, b) } else { (b, a) })
+/// Radius a body rolls on: circles roll, other shapes don't.
+fn rolling_radius(e: &dyn PhysicalEntity) -> Scalar {
+    match e.collider() {
+        Some(Collider2D::Circle { radius }) => *radius,
+        _ => 0.0,
+    }
 }
 
-const CELL_SIZE: f32 = 0.05;
+#[inline]
+fn apply_impulse_pair(
+    bodies: &mut BodyStore,
+    a: usize,
+    b: usize,
+    r_a: Vec2,
+    r_b: Vec2,
+    dir: Vec2,
+    magnitude: Scalar,
+) {
+    let impulse = dir * magnitude;
+    bodies.apply_impulse(a, r_a, -impulse, 0.0);
+    bodies.apply_impulse(b, r_b, impulse, 0.0);
+}
 
-#[derive(Hash, Eq, PartialEq, Clone, Copy)]
+/// Warm-start cache key: a contact is "the same contact" as last step when it connects the
+/// same two bodies through the same pair of features, wherever the point has drifted to.
+///
+/// Ordered (`BTreeMap`) rather than hashed so nothing about the cache depends on a
+/// per-process hash seed: stepping the same scene twice gives bit-identical results.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Copy)]
 struct CacheKey {
     pair: (usize, usize),
-    cell_a: (i32, i32),
-    cell_b: (i32, i32),
+    feature_id: u32,
 }
 
 impl CacheKey {
-    fn cell(v: Vec2) -> (i32, i32) {
-        (
-            (v.x / CELL_SIZE).round() as i32,
-            (v.y / CELL_SIZE).round() as i32,
-        )
+    fn new(index_a: usize, index_b: usize, feature_id: u32) -> Self {
+        Self {
+            pair: (index_a, index_b),
+            feature_id,
+        }
+    }
+}
+
+/// Stiffness of soft contacts, as a damped spring.
+///
+/// Instead of a Baumgarte factor, penetration is pushed out by a spring of frequency `hertz`
+/// with the given damping ratio (1 = critically damped). This is the Box2D v3 "soft step"
+/// formulation; it stays stable in tall stacks where a hard bias overshoots.
+#[derive(Debug, Clone, Copy)]
+pub struct ContactSoftness {
+    pub hertz: Scalar,
+    pub damping_ratio: Scalar,
+}
+
+impl Default for ContactSoftness {
+    /// Box2D v3's contact defaults.
+    fn default() -> Self {
+        Self::new(30.0, 10.0)
     }
+}
 
-    fn new(index_a: usize, index_b: usize, local_anchor_a: Vec2, local_anchor_b: Vec2) -> Self {
+impl ContactSoftness {
+    pub fn new(hertz: Scalar, damping_ratio: Scalar) -> Self {
         Self {
-            pair: (index_a, index_b),
-            cell_a: Self::cell(local_anchor_a),
-            cell_b: Self::cell(local_anchor_b),
+            hertz,
+            damping_ratio,
+        }
+    }
+
+    /// Solver coefficients for a time step `dt`.
+    pub fn coefficients(&self, dt: Scalar) -> SoftCoefficients {
+        if self.hertz <= 0.0 || dt <= 0.0 {
+            // A zero-stiffness spring: no push-out, rigid velocity constraint.
+            return SoftCoefficients {
+                bias_rate: 0.0,
+                mass_scale: 1.0,
+                impulse_scale: 0.0,
+            };
+        }
+        let omega = 2.0 * crate::math::consts::PI * self.hertz;
+        let a1 = 2.0 * self.damping_ratio + dt * omega;
+        let a2 = dt * omega * a1;
+        let a3 = 1.0 / (1.0 + a2);
+        SoftCoefficients {
+            bias_rate: omega / a1,
+            mass_scale: a2 * a3,
+            impulse_scale: a3,
         }
     }
 }
 
+/// What `ContactConstraint::normal_bias` asks of the relative normal velocity `vn`.
+#[derive(Debug, Clone, Copy)]
+struct NormalBias {
+    /// The contact aims for `vn = -velocity`.
+    velocity: Scalar,
+    /// How much `velocity` grows per unit of `vn` itself: without substeps the predicted
+    /// separation moves with the velocity being solved (`BodyStore::sync_delta`).
+    slope: Scalar,
+    mass_scale: Scalar,
+    impulse_scale: Scalar,
+}
+
+/// `ContactSoftness` turned into per-step solver factors.
+#[derive(Debug, Clone, Copy)]
+pub struct SoftCoefficients {
+    /// Bias velocity per meter of penetration (already divided by time).
+    bias_rate: Scalar,
+    /// Scale on the effective mass.
+    mass_scale: Scalar,
+    /// Fraction of the accumulated impulse removed each iteration.
+    impulse_scale: Scalar,
+}
+
 /// TGS-style solver parameters.
 #[derive(Clone)]
 pub struct SolverParams {
     /// Baumgarte factor (0.1-0.3 typical). bias = bias_rate * penetration / dt
-    pub bias_rate: f32,
-    /// Penetration slop (meters). Small penetrations below this do not get corrected by bias.
-    pub slop: f32,
-    /// Maximum bias velocity (m/s) to prevent explosive corrections
-    pub max_bias_velocity: f32,
-    /// Restitution threshold (minimum relative velocity for bounce)
-    pub restitution_threshold: f32,
-    /// Default restitution coefficient
-    pub restitution: f32,
-    /// Default friction coefficient
-    pub friction: f32,
+    ///
+    /// Ignored when `softness` is set.
+    pub bias_rate: Scalar,
+    /// Penetration slop, in meters (world units, whatever the body size). Resting contacts
+    /// settle this deep: penetration below it is not corrected by the bias, which keeps
+    /// them touching from one step to the next instead of bouncing in and out of contact.
+    pub slop: Scalar,
+    /// Push contacts that have been touching for this many steps (`ContactConstraint::age`)
+    /// out to zero separation instead of `-slop`, so settled stacks sit flush. The last
+    /// `slop` is worked off at the usual bias rate. Without the slop's dead zone the bias is
+    /// always working on a resting contact, so tall stacks settle less quietly; meant for
+    /// small piles that should look right. `None` = always keep the slop.
+    pub flush_after: Option<u32>,
+    /// Maximum bias velocity (m/s) to prevent explosive corrections (hard and soft mode)
+    pub max_bias_velocity: Scalar,
+    /// Most penetration (meters) the bias may correct in one step, so a deep overlap is
+    /// worked off over several steps instead of popping apart. Unlike `max_bias_velocity`
+    /// it doesn't depend on dt. `Scalar::INFINITY` = no limit.
+    pub max_correction_per_step: Scalar,
+    /// Solve contacts as springs (`Some`) instead of with the Baumgarte `bias_rate` (`None`).
+    pub softness: Option<ContactSoftness>,
+    /// Baumgarte factor for joints. Joints have no slop and usually want firmer correction
+    /// than contacts, so they get their own factor.
+    pub joint_bias_rate: Scalar,
+    /// Restitution threshold (minimum relative velocity for bounce) of a contact with zero
+    /// restitution. It shrinks as the contact gets bouncier: a contact with restitution `e`
+    /// bounces above `restitution_threshold * (1 - e)`, so a super ball still bounces off a
+    /// soft landing.
+    pub restitution_threshold: Scalar,
+    /// Impacts slower than this never bounce, whatever the restitution, so a bouncy body
+    /// comes to rest instead of hopping forever.
+    pub restitution_velocity_floor: Scalar,
+    /// Default restitution coefficient, used for bodies without a `Material`
+    pub restitution: Scalar,
+    /// Default friction coefficient, used for bodies without a `Material`
+    pub friction: Scalar,
+    /// Default rolling resistance, used for bodies without a `Material`
+    pub rolling_resistance: Scalar,
+    /// Solve friction per manifold (one shared friction cone for all its points) instead of
+    /// clamping every point against its own normal impulse.
+    pub block_friction: bool,
+    /// Solve the two normal impulses of a two-point manifold together (see
+    /// `solve_normal_block`) instead of one point after the other. Hard contacts only;
+    /// with `softness` the points are always solved one at a time.
+    pub block_normal: bool,
+    /// Iterations with position bias (push-out of penetration).
+    pub velocity_iterations: usize,
+    /// Adaptive iteration count: with `Some(tolerance)` the biased iterations run until one
+    /// changes no contact or joint impulse by more than `tolerance` (an impulse, N·s; see
+    /// `ConstraintSolver::last_residual`), up to `max_iterations`, instead of exactly
+    /// `velocity_iterations`. A warm-started resting scene then stops after one or two.
+    pub convergence_tolerance: Option<Scalar>,
+    /// Cap on the biased iterations in adaptive mode.
+    pub max_iterations: usize,
+    /// Extra iterations without bias, run after the biased ones, to remove the velocity the
+    /// bias added. Without them that velocity shows up as jitter/bounce in stacks.
+    pub relax_iterations: usize,
+    /// Split each step into this many substeps, each integrating velocity, running the
+    /// biased and relax iterations and integrating position with `dt / substeps`.
+    /// Collision detection still runs once per step. The bias then sees up-to-date
+    /// separations, which is what keeps tall stacks still; lower `velocity_iterations` to
+    /// pay for it. 1 = the plain single solve.
+    pub substeps: usize,
+}
+
+impl SolverParams {
+    /// Material for bodies that don't have one: `restitution`, `friction` and
+    /// `rolling_resistance` from these params.
+    pub fn default_material(&self) -> Material {
+        Material::new(self.restitution, self.friction)
+            .with_rolling_resistance(self.rolling_resistance)
+    }
 }
 
 impl Default for SolverParams {
@@ -314,38 +738,85 @@ impl Default for SolverParams {
             // 0.2 is a common value (Box2D uses similar)
             bias_rate: 0.05,
             slop: 0.01,
+            flush_after: None,
             // Limit correction speed to prevent explosive behavior
             max_bias_velocity: 4.0,
+            max_correction_per_step: Scalar::INFINITY,
+            softness: None,
+            joint_bias_rate: 0.2,
             restitution_threshold: 1.0,
+            restitution_velocity_floor: 0.1,
             restitution: 0.3,
             friction: 0.5,
+            rolling_resistance: 0.0,
+            block_friction: true,
+            block_normal: true,
+            velocity_iterations: 10,
+            convergence_tolerance: None,
+            max_iterations: 30,
+            relax_iterations: 1,
+            substeps: 1,
         }
     }
 }
 
+/// Solver state carried from one step to the next: the last contacts with their
+/// accumulated impulses, and the dt they were solved with.
+#[derive(Debug, Clone)]
+pub(crate) struct SolverState {
+    constraints: Vec<ContactConstraint>,
+    last_dt: Scalar,
+}
+
 pub struct ConstraintSolver {
     pub constraints: Vec<ContactConstraint>,
-    pub iterations: usize,
+    /// `constraints[start..end]` ranges, one per manifold (used for block friction).
+    manifold_ranges: Vec<(usize, usize)>,
+    /// Rolling resistance of the manifolds that have any.
+    rolling: Vec<RollingResistance>,
     pub params: SolverParams,
-    cache: HashMap<CacheKey, (f32, f32)>,
-    dt: f32,
-    last_dt: f32,
-    // Solver-internal predicted per-body deltas for the current step.
-    delta_pos: Vec<Vec2>,
-    delta_angle: Vec<f32>,
+    /// Start each step's contacts from last step's impulses (on by default). Off, they start
+    /// from zero every step, which takes more iterations to hold a stack; for comparing.
+    pub warm_start_enabled: bool,
+    /// Last step's `(jn, jt, age)` of every contact that carried an impulse.
+    cache: BTreeMap<CacheKey, (Scalar, Scalar, u32)>,
+    dt: Scalar,
+    last_dt: Scalar,
+    /// `dt / last_dt` for this step: how much to scale last step's impulses by before
+    /// warm starting with them.
+    dt_ratio: Scalar,
+    // SoA copy of the bodies for the iteration loop, including the predicted per-body
+    // deltas. The solver is the single owner of the deltas; bodies don't carry copies.
+    // Read them through `predicted_delta`.
+    bodies: BodyStore,
+    /// See `last_residual`.
+    last_residual: Scalar,
+    /// See `last_iterations`.
+    iterations: usize,
+    /// Contact impulses before the measured iteration (scratch for `last_residual`).
+    impulses_before: Vec<(Scalar, Scalar)>,
 }
 
 impl ConstraintSolver {
-    pub fn new(iterations: usize) -> Self {
+    /// A solver with `velocity_iterations` biased iterations and default params otherwise.
+    pub fn new(velocity_iterations: usize) -> Self {
         Self {
             constraints: Vec::new(),
-            iterations,
-            params: SolverParams::default(),
-            cache: HashMap::new(),
+            manifold_ranges: Vec::new(),
+            rolling: Vec::new(),
+            params: SolverParams {
+                velocity_iterations,
+                ..SolverParams::default()
+            },
+            warm_start_enabled: true,
+            cache: BTreeMap::new(),
             dt: 0.0,
             last_dt: 0.0,
-            delta_pos: Vec::new(),
-            delta_angle: Vec::new(),
+            dt_ratio: 1.0,
+            bodies: BodyStore::default(),
+            last_residual: 0.0,
+            iterations: 0,
+            impulses_before: Vec::new(),
         }
     }
 
@@ -353,11 +824,17 @@ impl ConstraintSolver {
         &mut self,
         manifolds: &[Manifold],
         entities: &[Box<dyn PhysicalEntity>],
-        dt: f32,
+        dt: Scalar,
     ) {
+        // `World::step` integrated the forces over the whole step already, unless the solver
+        // does it per substep.
+        let force_dt = if self.params.substeps > 1 { 0.0 } else { dt };
+        // With substeps, the impulses are solved (and warm-started) per substep.
+        let dt = dt / self.params.substeps.max(1) as Scalar;
         self.dt = dt;
-        self.ensure_delta_capacity(entities.len());
-        let dt_ratio = if self.last_dt > 0.0 {
+        // An impulse is force * dt: the same resting force over a different step is a
+        // proportionally different impulse.
+        self.dt_ratio = if self.last_dt > 0.0 {
             dt / self.last_dt
         } else {
             1.0
@@ -367,105 +844,351 @@ impl ConstraintSolver {
         self.cache.clear();
         for c in &self.constraints {
             if c.jn != 0.0 || c.jt != 0.0 {
-                let key = CacheKey::new(c.index_a, c.index_b, c.local_anchor_a, c.local_anchor_b);
-                self.cache.insert(key, (c.jn, c.jt));
+                let key = CacheKey::new(c.index_a, c.index_b, c.feature_id);
+                self.cache.insert(key, (c.jn, c.jt, c.age));
             }
         }
 
         self.constraints.clear();
+        self.manifold_ranges.clear();
+        self.rolling.clear();
 
         for manifold in manifolds {
             let (Some(a), Some(b)) = (entities.get(manifold.a), entities.get(manifold.b)) else {
                 continue;
             };
+            let material = manifold.material();
+            let start = self.constraints.len();
             for cp in &manifold.points {
-                let mut c =
-                    ContactConstraint::new(manifold.a, manifold.b, manifold.normal, cp, &**a, &**b);
+                let mut c = ContactConstraint::new(manifold, cp, &**a, &**b, force_dt);
                 // Warm start: restore cached impulses
-                let key = CacheKey::new(c.index_a, c.index_b, c.local_anchor_a, c.local_anchor_b);
-                if let Some(&(jn, jt)) = self.cache.get(&key) {
-                    // Impulses scale roughly with dt; keep warm-start stable under variable time steps.
-                    c.jn = jn * dt_ratio;
-                    c.jt = jt * dt_ratio;
+                let key = CacheKey::new(c.index_a, c.index_b, c.feature_id);
+                if let Some(&(jn, jt, age)) = self.cache.get(&key) {
+                    if self.warm_start_enabled {
+                        c.jn = jn * self.dt_ratio;
+                        c.jt = jt * self.dt_ratio;
+                    }
+                    // Still the same contact, for restitution and `flush_after`.
+                    c.age = age.saturating_add(1);
                 }
                 self.constraints.push(c);
             }
+            let range = (start, self.constraints.len());
+
+            // Settled per manifold, so both points of a resting box are pushed out alike
+            // even if one of them was only just (re)created.
+            let contacts = &mut self.constraints[start..];
+            let age = contacts.iter().map(|c| c.age).max().unwrap_or(0);
+            let settled = self.params.flush_after.is_some_and(|n| age >= n);
+            let target_separation = if settled { 0.0 } else { -self.params.slop };
+            for c in contacts {
+                c.target_separation = target_separation;
+            }
+            self.manifold_ranges.push(range);
+
+            let radius = rolling_radius(&**a).max(rolling_radius(&**b));
+            self.rolling.extend(RollingResistance::new(
+                manifold.a,
+                manifold.b,
+                range,
+                material.rolling_resistance * radius,
+                a.inv_inertia() + b.inv_inertia(),
+            ));
         }
 
         self.last_dt = dt;
     }
 
-    /// TGS-style solve: multiple iterations with bias, then restitution pass.
-    pub fn solve(&mut self, entities: &mut [Box<dyn PhysicalEntity>]) {
+    /// TGS-style solve: biased iterations, relax iterations, then the restitution pass.
+    ///
+    /// Joints are solved in the same iterations as the contacts (joints first).
+    /// The bodies are copied into the SoA store first and only the velocities are written
+    /// back at the end.
+    pub fn solve(
+        &mut self,
+        entities: &mut [Box<dyn PhysicalEntity>],
+        joints: &mut [Box<dyn Constraint>],
+    ) {
         let dt = self.dt;
+        self.bodies.load(entities);
+        self.iterations = 0;
+        self.bodies.substepping = self.params.substeps > 1;
+        if self.params.substeps > 1 {
+            self.solve_substeps(entities, joints);
+            return;
+        }
 
         // Warm start
         for c in &self.constraints {
-            c.apply_warm_start(entities);
+            c.warm_start(&mut self.bodies);
+        }
+        for j in joints.iter_mut() {
+            j.prepare(&self.bodies, dt, self.dt_ratio);
+            j.warm_start(&mut self.bodies);
         }
 
         // After warm start velocities changed; initialize predicted deltas.
-        self.init_predicted_deltas(entities, dt);
+        self.bodies.init_deltas(dt);
 
-        // Main iterations with bias (corrects penetration).
-        // Deltas are kept in sync per-body inside solve_* after each impulse.
-        for _ in 0..self.iterations {
-            for c in &mut self.constraints {
-                c.solve_normal(
-                    entities,
-                    &mut self.delta_pos,
-                    &mut self.delta_angle,
-                    dt,
-                    &self.params,
-                    true,
-                );
+        // Biased, then relax iterations. Deltas are kept in sync per-body inside solve_*
+        // after each impulse. The push-out the relax pass takes back is still applied to the
+        // positions below, otherwise the overlap would never be resolved.
+        self.run_iterations(joints, dt, true);
+        let relax = self.params.relax_iterations > 0;
+
+        for c in &mut self.constraints {
+            c.apply_restitution(&mut self.bodies, dt, &self.params);
+        }
+
+        self.bodies.store_velocities(entities);
+        if relax {
+            self.bodies.apply_push_out(entities, dt);
+        }
+    }
+
+    /// `solve` with `substeps > 1`: also integrates the bodies, velocity from their force
+    /// accumulators at the start of every substep and position at the end, and writes the
+    /// final poses back.
+    ///
+    /// Contacts keep the geometry of the step's collision pass; their separation follows
+    /// the bodies through the substeps via the deltas. Restitution runs once, at the end.
+    fn solve_substeps(
+        &mut self,
+        entities: &mut [Box<dyn PhysicalEntity>],
+        joints: &mut [Box<dyn Constraint>],
+    ) {
+        let h = self.dt;
+        for j in joints.iter_mut() {
+            j.prepare(&self.bodies, h, self.dt_ratio);
+        }
+        let relax = self.params.relax_iterations > 0;
+        for substep in 0..self.params.substeps {
+            self.bodies.integrate_velocities(h);
+            // The accumulated impulses are per substep, so every substep starts from them.
+            for c in &self.constraints {
+                c.warm_start(&mut self.bodies);
             }
-            for c in &mut self.constraints {
-                c.solve_tangent(
-                    entities,
-                    &mut self.delta_pos,
-                    &mut self.delta_angle,
-                    dt,
-                    self.params.friction,
-                );
+            for j in joints.iter() {
+                j.warm_start(&mut self.bodies);
             }
+            self.bodies.init_deltas(h);
+
+            let last = substep + 1 == self.params.substeps;
+            self.run_iterations(joints, h, last);
+            self.bodies.integrate_positions(h, relax);
         }
 
+        // Once, after the last substep: every substep bouncing off the approach speed
+        // sampled at the start of the step would bounce it several times over.
         for c in &mut self.constraints {
-            c.apply_restitution(
-                entities,
-                &mut self.delta_pos,
-                &mut self.delta_angle,
-                dt,
-                self.params.restitution,
-                self.params.restitution_threshold,
-            );
+            c.apply_restitution(&mut self.bodies, h, &self.params);
         }
+        self.bodies.store_velocities(entities);
+        self.bodies.store_positions(entities);
     }
 
-    #[inline]
-    fn ensure_delta_capacity(&mut self, count: usize) {
-        if self.delta_pos.len() != count {
-            self.delta_pos.resize(count, Vec2::zero());
-            self.delta_angle.resize(count, 0.0);
+    /// The biased iterations (correct penetration), then the relax iterations: the same
+    /// constraints without bias, so the push-out velocity doesn't survive into the next
+    /// step. With `measure` the last iteration sets `last_residual`.
+    fn run_iterations(&mut self, joints: &mut [Box<dyn Constraint>], dt: Scalar, measure: bool) {
+        let tolerance = self.params.convergence_tolerance;
+        let max_biased = match tolerance {
+            Some(_) => self.params.max_iterations,
+            None => self.params.velocity_iterations,
+        };
+        let relax = self.params.relax_iterations;
+        if measure {
+            self.last_residual = 0.0;
+        }
+
+        for c in &mut self.constraints {
+            c.pushing = false;
+        }
+        for i in 0..max_biased {
+            self.iterations += 1;
+            // Measuring costs a copy of the impulses: only when adaptive, or for the very
+            // last iteration.
+            let last = measure && relax == 0 && i + 1 == max_biased;
+            if tolerance.is_none() && !last {
+                self.iterate(joints, dt, true);
+                continue;
+            }
+            let residual = self.measured_iteration(joints, dt, true);
+            if measure {
+                self.last_residual = residual;
+            }
+            if tolerance.is_some_and(|tol| residual <= tol) {
+                break;
+            }
+        }
+
+        if relax > 0 {
+            // A manifold's points share the push: the bias often works on only one of them,
+            // depending on the order they were solved in.
+            for &(start, end) in &self.manifold_ranges {
+                let pushing = self.constraints[start..end].iter().any(|c| c.pushing);
+                for c in &mut self.constraints[start..end] {
+                    c.pushing = pushing;
+                }
+            }
+            self.bodies.clear_push_out();
+        }
+        self.bodies.relaxing = true;
+        for i in 0..relax {
+            if measure && i + 1 == relax {
+                self.last_residual = self.measured_iteration(joints, dt, false);
+            } else {
+                self.iterate(joints, dt, false);
+            }
         }
+        self.bodies.relaxing = false;
     }
 
-    #[inline]
-    fn init_predicted_deltas(&mut self, entities: &[Box<dyn PhysicalEntity>], dt: f32) {
-        self.ensure_delta_capacity(entities.len());
-        if dt <= 0.0 {
-            for d in &mut self.delta_pos {
-                *d = Vec2::zero();
+    /// `iterate`, returning the largest change of any contact or joint impulse it made.
+    fn measured_iteration(
+        &mut self,
+        joints: &mut [Box<dyn Constraint>],
+        dt: Scalar,
+        use_bias: bool,
+    ) -> Scalar {
+        self.impulses_before.clear();
+        self.impulses_before
+            .extend(self.constraints.iter().map(|c| (c.jn, c.jt)));
+        self.iterate(joints, dt, use_bias);
+        let contacts = self
+            .constraints
+            .iter()
+            .zip(&self.impulses_before)
+            .map(|(c, &(jn, jt))| (c.jn - jn).abs().max((c.jt - jt).abs()));
+        let joint_changes = joints.iter().map(|j| j.impulse_change());
+        contacts.chain(joint_changes).fold(0.0, Scalar::max)
+    }
+
+    /// One Gauss-Seidel pass over joints, contact normals and friction.
+    fn iterate(&mut self, joints: &mut [Box<dyn Constraint>], dt: Scalar, use_bias: bool) {
+        let bodies = &mut self.bodies;
+        for j in joints.iter_mut() {
+            if use_bias {
+                j.solve_velocity(bodies, dt, &self.params, true);
+                continue;
             }
-            for a in &mut self.delta_angle {
-                *a = 0.0;
+            // What the relax pass takes back from a joint is push-out too.
+            let (a, b) = j.bodies();
+            let before = (bodies.vel.get(a).copied(), bodies.vel.get(b).copied());
+            j.solve_velocity(bodies, dt, &self.params, false);
+            if let Some(v) = before.0 {
+                bodies.record_joint_push_out(a, v);
+            }
+            if let (Some(v), true) = (before.1, b != a) {
+                bodies.record_joint_push_out(b, v);
             }
-            return;
         }
-        for (i, e) in entities.iter().enumerate() {
-            self.delta_pos[i] = *e.vel() * dt;
-            self.delta_angle[i] = e.omega() * dt;
+        let soft = self.params.softness.map(|s| s.coefficients(dt));
+        if self.params.block_normal {
+            for &(start, end) in &self.manifold_ranges {
+                let contacts = &mut self.constraints[start..end];
+                solve_normal_block(contacts, bodies, dt, &self.params, soft, use_bias);
+            }
+        } else {
+            for c in &mut self.constraints {
+                c.solve_normal(bodies, dt, &self.params, soft, use_bias);
+            }
         }
+        if self.params.block_friction {
+            for &(start, end) in &self.manifold_ranges {
+                solve_tangent_block(&mut self.constraints[start..end], bodies, dt);
+            }
+        } else {
+            for c in &mut self.constraints {
+                c.solve_tangent(bodies, dt);
+            }
+        }
+        for r in &mut self.rolling {
+            r.solve(&self.constraints, bodies, dt);
+        }
+    }
+
+    /// Largest change of any contact's accumulated normal or friction impulse, or of a
+    /// joint's impulse (`Constraint::impulse_change`), during the last iteration of the last
+    /// `solve`: 0 when everything had converged. A joint that doesn't report its change makes
+    /// it infinite.
+    ///
+    /// It's an impulse, so it scales with dt and mass; compare it with the impulses
+    /// themselves (e.g. `ContactView::normal_impulse`) to judge whether more iterations
+    /// would help.
+    pub fn last_residual(&self) -> Scalar {
+        self.last_residual
+    }
+
+    /// Biased iterations the last `solve` ran, over all its substeps. Fixed unless
+    /// `SolverParams::convergence_tolerance` is set.
+    pub fn last_iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Per-point impulses from the last `solve`, in constraint order.
+    pub fn contact_impulses(&self) -> Vec<ContactImpulse> {
+        self.constraints
+            .iter()
+            .map(|c| ContactImpulse {
+                a: c.index_a,
+                b: c.index_b,
+                normal_impulse: c.jn,
+                tangent_impulse: c.jt,
+                point: c.point,
+            })
+            .collect()
+    }
+
+    /// Drop the accumulated impulses of every contact touching entity `index`, so the next
+    /// step doesn't warm-start from them (e.g. after its mass changed).
+    pub fn forget_body(&mut self, index: usize) {
+        for c in &mut self.constraints {
+            if c.index_a == index || c.index_b == index {
+                c.jn = 0.0;
+                c.jt = 0.0;
+            }
+        }
+    }
+
+    /// Entity `index` was removed by `World::remove`: drop its contacts and renumber the
+    /// others, so they still warm-start next step.
+    pub fn body_removed(&mut self, index: usize) {
+        self.constraints.retain_mut(|c| c.body_removed(index));
+        // Both are rebuilt from the manifolds next step.
+        self.manifold_ranges.clear();
+        self.rolling.clear();
+    }
+
+    /// Drop the accumulated impulses of every contact, so the next step starts them all from
+    /// zero (and counts them as new). `forget_body` does it for the contacts of one body.
+    pub fn clear_cache(&mut self) {
+        for c in &mut self.constraints {
+            c.jn = 0.0;
+            c.jt = 0.0;
+        }
+    }
+
+    /// What the next `build_constraints` warm-starts from (see `World::checkpoint`).
+    pub(crate) fn warm_start_state(&self) -> SolverState {
+        SolverState {
+            constraints: self.constraints.clone(),
+            last_dt: self.last_dt,
+        }
+    }
+
+    pub(crate) fn restore_warm_start(&mut self, state: &SolverState) {
+        self.constraints.clone_from(&state.constraints);
+        self.last_dt = state.last_dt;
+    }
+
+    /// Predicted position/angle change of entity `index` over the last solved step.
+    ///
+    /// Useful for debugging the TGS solve. Returns `None` for indices the solver hasn't seen.
+    pub fn predicted_delta(&self, index: usize) -> Option<(Vec2, Scalar)> {
+        Some((
+            *self.bodies.delta_pos.get(index)?,
+            *self.bodies.delta_angle.get(index)?,
+        ))
     }
 }
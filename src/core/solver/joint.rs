@@ -0,0 +1,436 @@
+use super::body_store::BodyStore;
+use super::constraint::SolverParams;
+use super::pipeline::Constraint;
+use crate::core::body::PhysicalEntity;
+use crate::core::world::renumber_after_removal;
+use crate::math::Scalar;
+use crate::math::mat::Mat2;
+use crate::math::vec::Vec2;
+
+/// Rigidly glues two bodies together at an anchor point: no relative translation and
+/// no relative rotation.
+///
+/// The weld can be made breakable: if holding the bodies together takes more than
+/// `break_force` (or `break_torque`) during a step, the joint marks itself `broken`, stops
+/// acting for the rest of the solve, and `World::step` removes it and reports a `JointBreak`.
+#[derive(Debug, Clone)]
+pub struct WeldJoint {
+    pub body_a: usize,
+    pub body_b: usize,
+    /// Anchor relative to A's center of mass, in A's local frame.
+    pub local_anchor_a: Vec2,
+    /// Anchor relative to B's center of mass, in B's local frame.
+    pub local_anchor_b: Vec2,
+    /// Rest value of `angle_b - angle_a`.
+    pub reference_angle: Scalar,
+    /// Largest force (impulse / dt) the weld can transmit; `Scalar::INFINITY` = unbreakable.
+    pub break_force: Scalar,
+    /// Largest torque the weld can transmit; `Scalar::INFINITY` = unbreakable.
+    pub break_torque: Scalar,
+    /// Accumulated linear impulse (warm-started across steps).
+    pub linear_impulse: Vec2,
+    /// Accumulated angular impulse (warm-started across steps).
+    pub angular_impulse: Scalar,
+    /// What the last `solve_velocity` added to the impulses; see
+    /// `Constraint::impulse_change`.
+    pub last_change: Scalar,
+    /// Set once the weld has exceeded its break force/torque.
+    pub broken: bool,
+}
+
+/// Reported by `World::step` when a breakable joint snaps.
+#[derive(Debug, Clone, Copy)]
+pub struct JointBreak {
+    /// Index the joint had in `World::joints` before it was removed.
+    pub joint: usize,
+    pub body_a: usize,
+    pub body_b: usize,
+}
+
+impl WeldJoint {
+    /// Weld `body_a` and `body_b` together at the world point `anchor`, keeping their
+    /// current relative angle.
+    pub fn new(
+        a: &dyn PhysicalEntity,
+        b: &dyn PhysicalEntity,
+        body_a: usize,
+        body_b: usize,
+        anchor: Vec2,
+    ) -> Self {
+        let rot_a_t = Mat2::rotation(a.angle()).transpose();
+        let rot_b_t = Mat2::rotation(b.angle()).transpose();
+        Self {
+            body_a,
+            body_b,
+            local_anchor_a: rot_a_t.mul_vec2(anchor - a.world_center_of_mass()),
+            local_anchor_b: rot_b_t.mul_vec2(anchor - b.world_center_of_mass()),
+            reference_angle: b.angle() - a.angle(),
+            break_force: Scalar::INFINITY,
+            break_torque: Scalar::INFINITY,
+            linear_impulse: Vec2::zero(),
+            angular_impulse: 0.0,
+            last_change: 0.0,
+            broken: false,
+        }
+    }
+
+    /// Make the weld breakable.
+    pub fn with_break_limits(mut self, break_force: Scalar, break_torque: Scalar) -> Self {
+        self.break_force = break_force;
+        self.break_torque = break_torque;
+        self
+    }
+}
+
+impl Constraint for WeldJoint {
+    fn bodies(&self) -> (usize, usize) {
+        (self.body_a, self.body_b)
+    }
+
+    /// Rescale the accumulated impulses to the new step length.
+    fn prepare(&mut self, _bodies: &BodyStore, _dt: Scalar, dt_ratio: Scalar) {
+        self.linear_impulse = self.linear_impulse * dt_ratio;
+        self.angular_impulse *= dt_ratio;
+    }
+
+    fn warm_start(&self, bodies: &mut BodyStore) {
+        let (a, b) = (self.body_a, self.body_b);
+        if self.broken || !bodies.is_pair(a, b) {
+            return;
+        }
+        let r_a = Mat2::rotation(bodies.angle[a]).mul_vec2(self.local_anchor_a);
+        let r_b = Mat2::rotation(bodies.angle[b]).mul_vec2(self.local_anchor_b);
+        apply_weld_impulse(
+            bodies,
+            a,
+            b,
+            r_a,
+            r_b,
+            self.linear_impulse,
+            self.angular_impulse,
+        );
+    }
+
+    /// One velocity iteration (TGS-style: the position error uses the predicted deltas).
+    fn solve_velocity(
+        &mut self,
+        bodies: &mut BodyStore,
+        dt: Scalar,
+        params: &SolverParams,
+        use_bias: bool,
+    ) {
+        self.last_change = 0.0;
+        let (a, b) = (self.body_a, self.body_b);
+        if self.broken || !bodies.is_pair(a, b) {
+            return;
+        }
+        let bias_rate = if use_bias {
+            params.joint_bias_rate
+        } else {
+            0.0
+        };
+
+        // Anchors and position error at the predicted end-of-step poses.
+        let angle_a = bodies.angle[a] + bodies.delta_angle[a];
+        let angle_b = bodies.angle[b] + bodies.delta_angle[b];
+        let r_a = Mat2::rotation(angle_a).mul_vec2(self.local_anchor_a);
+        let r_b = Mat2::rotation(angle_b).mul_vec2(self.local_anchor_b);
+        let c_lin = (bodies.pos[b] + bodies.delta_pos[b] + r_b)
+            - (bodies.pos[a] + bodies.delta_pos[a] + r_a);
+        let c_ang = angle_b - angle_a - self.reference_angle;
+        let (bias_lin, bias_ang) = if dt > 0.0 {
+            (c_lin * (bias_rate / dt), c_ang * bias_rate / dt)
+        } else {
+            (Vec2::zero(), 0.0)
+        };
+
+        // Relative velocity at the anchor and relative angular velocity.
+        let cdot_lin = bodies.velocity_at(b, r_b) - bodies.velocity_at(a, r_a);
+        let cdot_ang = bodies.omega[b] - bodies.omega[a];
+
+        // Effective mass K (3x3, symmetric) of the point + angle constraint.
+        let (ma, mb) = (bodies.inv_mass[a], bodies.inv_mass[b]);
+        let (ia_inv, ib_inv) = (bodies.inv_inertia[a], bodies.inv_inertia[b]);
+        let k11 = ma + mb + r_a.y * r_a.y * ia_inv + r_b.y * r_b.y * ib_inv;
+        let k12 = -r_a.y * r_a.x * ia_inv - r_b.y * r_b.x * ib_inv;
+        let k13 = -r_a.y * ia_inv - r_b.y * ib_inv;
+        let k22 = ma + mb + r_a.x * r_a.x * ia_inv + r_b.x * r_b.x * ib_inv;
+        let k23 = r_a.x * ia_inv + r_b.x * ib_inv;
+        let k33 = ia_inv + ib_inv;
+        let k = [[k11, k12, k13], [k12, k22, k23], [k13, k23, k33]];
+
+        let rhs = [
+            -(cdot_lin.x + bias_lin.x),
+            -(cdot_lin.y + bias_lin.y),
+            -(cdot_ang + bias_ang),
+        ];
+        let (lin, ang) = if k33 > 0.0 {
+            match solve33(k, rhs) {
+                Some(x) => (Vec2::new(x[0], x[1]), x[2]),
+                None => return,
+            }
+        } else {
+            // Neither body can rotate: only the point part of the weld is meaningful.
+            match solve22([[k11, k12], [k12, k22]], [rhs[0], rhs[1]]) {
+                Some(x) => (Vec2::new(x[0], x[1]), 0.0),
+                None => return,
+            }
+        };
+
+        self.linear_impulse = self.linear_impulse + lin;
+        self.angular_impulse += ang;
+        self.last_change = lin.x.abs().max(lin.y.abs()).max(ang.abs());
+        apply_weld_impulse(bodies, a, b, r_a, r_b, lin, ang);
+        bodies.sync_delta(a, dt);
+        bodies.sync_delta(b, dt);
+
+        // Impulse over the step -> average force/torque the weld had to transmit.
+        if dt > 0.0
+            && (self.linear_impulse.length() / dt > self.break_force
+                || self.angular_impulse.abs() / dt > self.break_torque)
+        {
+            self.broken = true;
+        }
+    }
+
+    fn reset_impulses(&mut self) {
+        self.linear_impulse = Vec2::zero();
+        self.angular_impulse = 0.0;
+    }
+
+    fn impulse_change(&self) -> Scalar {
+        self.last_change
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.body_a, index)
+            && renumber_after_removal(&mut self.body_b, index)
+    }
+
+    fn is_broken(&self) -> bool {
+        self.broken
+    }
+
+    fn clone_box(&self) -> Box<dyn Constraint> {
+        Box::new(self.clone())
+    }
+}
+
+/// Two bodies hung from fixed pulleys on one rope: `len_a + ratio * len_b` stays at
+/// `length`, where `len_a` / `len_b` run from each ground anchor to the body's anchor.
+///
+/// With `ratio = 1` what one side gains the other loses (two masses over a pulley); a larger
+/// ratio gives B a block and tackle: it moves `ratio` times slower than A and is pulled with
+/// `ratio` times the tension. The rope pulls as
+/// well as pushes (it never goes slack), and an anchor right at its ground anchor has no
+/// rope direction and drops out of the constraint.
+#[derive(Debug, Clone)]
+pub struct PulleyJoint {
+    pub body_a: usize,
+    pub body_b: usize,
+    /// Fixed world point A's rope runs over.
+    pub ground_anchor_a: Vec2,
+    /// Fixed world point B's rope runs over.
+    pub ground_anchor_b: Vec2,
+    /// Anchor relative to A's center of mass, in A's local frame.
+    pub local_anchor_a: Vec2,
+    /// Anchor relative to B's center of mass, in B's local frame.
+    pub local_anchor_b: Vec2,
+    pub ratio: Scalar,
+    /// Rope length `len_a + ratio * len_b`.
+    pub length: Scalar,
+    /// Accumulated rope impulse (warm-started across steps).
+    pub impulse: Scalar,
+    /// What the last `solve_velocity` added to `impulse`; see `Constraint::impulse_change`.
+    pub last_change: Scalar,
+}
+
+impl PulleyJoint {
+    /// Hang `body_a` and `body_b` at the world points `anchors` from the world points
+    /// `ground_anchors`, with the rope length they have now.
+    pub fn new(
+        a: &dyn PhysicalEntity,
+        b: &dyn PhysicalEntity,
+        body_a: usize,
+        body_b: usize,
+        ground_anchors: [Vec2; 2],
+        anchors: [Vec2; 2],
+        ratio: Scalar,
+    ) -> Self {
+        let rot_a_t = Mat2::rotation(a.angle()).transpose();
+        let rot_b_t = Mat2::rotation(b.angle()).transpose();
+        let [ground_anchor_a, ground_anchor_b] = ground_anchors;
+        let [anchor_a, anchor_b] = anchors;
+        Self {
+            body_a,
+            body_b,
+            ground_anchor_a,
+            ground_anchor_b,
+            local_anchor_a: rot_a_t.mul_vec2(anchor_a - a.world_center_of_mass()),
+            local_anchor_b: rot_b_t.mul_vec2(anchor_b - b.world_center_of_mass()),
+            ratio,
+            length: (anchor_a - ground_anchor_a).length()
+                + ratio * (anchor_b - ground_anchor_b).length(),
+            impulse: 0.0,
+            last_change: 0.0,
+        }
+    }
+
+    /// Anchor offsets, unit rope directions (ground anchor to body) and rope lengths, with
+    /// the bodies at `pos` and `angle` (A, then B).
+    fn ropes(&self, pos: [Vec2; 2], angle: [Scalar; 2]) -> ([Vec2; 2], [Vec2; 2], [Scalar; 2]) {
+        let r = [
+            Mat2::rotation(angle[0]).mul_vec2(self.local_anchor_a),
+            Mat2::rotation(angle[1]).mul_vec2(self.local_anchor_b),
+        ];
+        let rope = [
+            pos[0] + r[0] - self.ground_anchor_a,
+            pos[1] + r[1] - self.ground_anchor_b,
+        ];
+        let len = rope.map(|d| d.length());
+        let dir = rope.map(|d| d.try_normalize().unwrap_or(Vec2::zero()));
+        (r, dir, len)
+    }
+
+    fn apply(&self, bodies: &mut BodyStore, r: [Vec2; 2], dir: [Vec2; 2], impulse: Scalar) {
+        bodies.apply_impulse(self.body_a, r[0], dir[0] * impulse, 0.0);
+        bodies.apply_impulse(self.body_b, r[1], dir[1] * (self.ratio * impulse), 0.0);
+    }
+}
+
+impl Constraint for PulleyJoint {
+    fn bodies(&self) -> (usize, usize) {
+        (self.body_a, self.body_b)
+    }
+
+    fn prepare(&mut self, _bodies: &BodyStore, _dt: Scalar, dt_ratio: Scalar) {
+        self.impulse *= dt_ratio;
+    }
+
+    fn warm_start(&self, bodies: &mut BodyStore) {
+        let (a, b) = (self.body_a, self.body_b);
+        if !bodies.is_pair(a, b) {
+            return;
+        }
+        let (r, dir, _) = self.ropes(
+            [bodies.pos[a], bodies.pos[b]],
+            [bodies.angle[a], bodies.angle[b]],
+        );
+        self.apply(bodies, r, dir, self.impulse);
+    }
+
+    fn solve_velocity(
+        &mut self,
+        bodies: &mut BodyStore,
+        dt: Scalar,
+        params: &SolverParams,
+        use_bias: bool,
+    ) {
+        self.last_change = 0.0;
+        let (a, b) = (self.body_a, self.body_b);
+        if !bodies.is_pair(a, b) {
+            return;
+        }
+        // Rope directions and length error at the predicted end-of-step poses.
+        let (r, dir, len) = self.ropes(
+            [
+                bodies.pos[a] + bodies.delta_pos[a],
+                bodies.pos[b] + bodies.delta_pos[b],
+            ],
+            [
+                bodies.angle[a] + bodies.delta_angle[a],
+                bodies.angle[b] + bodies.delta_angle[b],
+            ],
+        );
+        let c = len[0] + self.ratio * len[1] - self.length;
+        let bias = if use_bias && dt > 0.0 {
+            c * params.joint_bias_rate / dt
+        } else {
+            0.0
+        };
+
+        // Rate of change of the rope length, and the effective mass along it.
+        let cdot = bodies.velocity_at(a, r[0]).dot(dir[0])
+            + self.ratio * bodies.velocity_at(b, r[1]).dot(dir[1]);
+        let mass_along = |i: usize, r: Vec2, dir: Vec2| {
+            let rn = r.cross(dir);
+            bodies.inv_mass[i] + bodies.inv_inertia[i] * rn * rn
+        };
+        let k = mass_along(a, r[0], dir[0]) + self.ratio * self.ratio * mass_along(b, r[1], dir[1]);
+        if k <= 0.0 {
+            return;
+        }
+
+        let impulse = -(cdot + bias) / k;
+        self.impulse += impulse;
+        self.last_change = impulse.abs();
+        self.apply(bodies, r, dir, impulse);
+        bodies.sync_delta(a, dt);
+        bodies.sync_delta(b, dt);
+    }
+
+    fn reset_impulses(&mut self) {
+        self.impulse = 0.0;
+    }
+
+    fn impulse_change(&self) -> Scalar {
+        self.last_change
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.body_a, index)
+            && renumber_after_removal(&mut self.body_b, index)
+    }
+
+    fn clone_box(&self) -> Box<dyn Constraint> {
+        Box::new(self.clone())
+    }
+}
+
+#[inline]
+fn apply_weld_impulse(
+    bodies: &mut BodyStore,
+    a: usize,
+    b: usize,
+    r_a: Vec2,
+    r_b: Vec2,
+    linear: Vec2,
+    angular: Scalar,
+) {
+    bodies.apply_impulse(a, r_a, -linear, -angular);
+    bodies.apply_impulse(b, r_b, linear, angular);
+}
+
+/// Solve `k * x = b` for a 3x3 matrix by Cramer's rule.
+fn solve33(k: [[Scalar; 3]; 3], b: [Scalar; 3]) -> Option<[Scalar; 3]> {
+    let det3 = |m: [[Scalar; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let det = det3(k);
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    let mut x = [0.0; 3];
+    for (col, xi) in x.iter_mut().enumerate() {
+        let mut m = k;
+        for row in 0..3 {
+            m[row][col] = b[row];
+        }
+        *xi = det3(m) / det;
+    }
+    Some(x)
+}
+
+/// Solve `k * x = b` for a 2x2 matrix.
+fn solve22(k: [[Scalar; 2]; 2], b: [Scalar; 2]) -> Option<[Scalar; 2]> {
+    let det = k[0][0] * k[1][1] - k[0][1] * k[1][0];
+    if det.abs() < 1e-12 {
+        return None;
+    }
+    Some([
+        (k[1][1] * b[0] - k[0][1] * b[1]) / det,
+        (k[0][0] * b[1] - k[1][0] * b[0]) / det,
+    ])
+}
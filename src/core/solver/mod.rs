@@ -1,3 +1,15 @@
+mod body_store;
 mod constraint;
+mod joint;
+mod pipeline;
+mod rolling;
 
-pub use constraint::{ConstraintSolver, ContactConstraint};
+pub use body_store::BodyStore;
+pub use constraint::{
+    ConstraintSolver, ContactConstraint, ContactImpulse, ContactSoftness, SoftCoefficients,
+    SolverParams,
+};
+pub use joint::{JointBreak, PulleyJoint, WeldJoint};
+pub use pipeline::Constraint;
+
+pub(crate) use constraint::SolverState;
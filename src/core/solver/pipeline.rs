@@ -0,0 +1,81 @@
+use std::any::Any;
+use std::fmt::Debug;
+
+use super::body_store::BodyStore;
+use super::constraint::SolverParams;
+use crate::core::body::PhysicalEntity;
+use crate::math::Scalar;
+
+/// Anything the solver iterates: joints in `World::joints`, and the contact constraints.
+///
+/// Each step `ConstraintSolver::solve` drives every constraint through the same phases:
+/// 1) `prepare`, once, after the bodies are loaded into the `BodyStore`
+/// 2) `warm_start` with last step's accumulated impulses (every substep)
+/// 3) `solve_velocity`, biased iterations then relax iterations (every substep)
+///
+/// and after the positions are integrated `World::step` calls 4) `solve_position`.
+///
+/// Impulses are applied to the `BodyStore`, not the entities; after every impulse call
+/// `BodyStore::sync_delta` on the bodies it moved so the next constraint sees their
+/// predicted pose. Joints come first in each iteration, then the contacts.
+pub trait Constraint: Any + Debug {
+    /// The two entities it connects (for islands, and to know which joints a body has).
+    fn bodies(&self) -> (usize, usize);
+
+    /// Once per step, before warm starting. `dt` is the (sub)step the impulses are solved
+    /// for and `dt_ratio` is `dt / last dt`: accumulated impulses should be scaled by it,
+    /// since the same force over a different step is a different impulse.
+    fn prepare(&mut self, _bodies: &BodyStore, _dt: Scalar, _dt_ratio: Scalar) {}
+
+    /// Apply the accumulated impulses.
+    fn warm_start(&self, bodies: &mut BodyStore);
+
+    /// One velocity iteration. Without `use_bias` (the relax iterations) only the velocity
+    /// error is removed, not the position error.
+    fn solve_velocity(
+        &mut self,
+        bodies: &mut BodyStore,
+        dt: Scalar,
+        params: &SolverParams,
+        use_bias: bool,
+    );
+
+    /// Position correction on the integrated poses, after the velocity solve. Most
+    /// constraints correct position through their velocity bias and do nothing here.
+    fn solve_position(&mut self, _entities: &mut [Box<dyn PhysicalEntity>]) {}
+
+    /// Drop the accumulated impulses (a body changed mass or shape).
+    fn reset_impulses(&mut self);
+
+    /// How much the last `solve_velocity` changed the accumulated impulse (its largest
+    /// component, linear or angular). The solver's convergence measure includes it (see
+    /// `ConstraintSolver::last_residual`). The default, infinity, never counts as converged:
+    /// with `SolverParams::convergence_tolerance` set, the solver then runs
+    /// `max_iterations` while the constraint exists.
+    fn impulse_change(&self) -> Scalar {
+        Scalar::INFINITY
+    }
+
+    /// A broken constraint is removed by `World::step` and reported as a `JointBreak`.
+    fn is_broken(&self) -> bool {
+        false
+    }
+
+    /// Entity `index` was removed by `World::remove` and the later ones shifted down by one:
+    /// renumber the bodies (`renumber_after_removal`), or return false to be dropped. The
+    /// default keeps a constraint whose bodies both come before `index`, which need no
+    /// renumbering, and drops any other.
+    fn body_removed(&mut self, index: usize) -> bool {
+        let (a, b) = self.bodies();
+        a < index && b < index
+    }
+
+    /// For `Clone` on `Box<dyn Constraint>` (checkpoints copy the joints).
+    fn clone_box(&self) -> Box<dyn Constraint>;
+}
+
+impl Clone for Box<dyn Constraint> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
@@ -0,0 +1,71 @@
+use super::body_store::BodyStore;
+use super::constraint::ContactConstraint;
+use crate::math::Scalar;
+use crate::math::vec::Vec2;
+
+/// Slip speed above which a contact counts as sliding, where rolling resistance stays off.
+const ROLLING_SLIP_SPEED: Scalar = 0.05;
+
+/// Rolling resistance for one manifold: an angular impulse against the two bodies'
+/// relative spin, limited to `resistance * radius * Σ jn` (like a friction cone, but for
+/// rotation).
+///
+/// It only acts while the contact rolls; a sliding contact is left to regular friction.
+pub(super) struct RollingResistance {
+    a: usize,
+    b: usize,
+    /// `constraints[start..end]` of the manifold.
+    range: (usize, usize),
+    /// Combined resistance coefficient times the rolling radius.
+    max_per_normal: Scalar,
+    mass: Scalar,
+    impulse: Scalar,
+}
+
+impl RollingResistance {
+    /// `None` if there is nothing to resist (no resistance, or neither body can spin).
+    pub fn new(
+        a: usize,
+        b: usize,
+        range: (usize, usize),
+        max_per_normal: Scalar,
+        inv_inertia: Scalar,
+    ) -> Option<Self> {
+        if max_per_normal <= 0.0 || inv_inertia <= 0.0 {
+            return None;
+        }
+        Some(Self {
+            a,
+            b,
+            range,
+            max_per_normal,
+            mass: 1.0 / inv_inertia,
+            impulse: 0.0,
+        })
+    }
+
+    pub fn solve(&mut self, constraints: &[ContactConstraint], bodies: &mut BodyStore, dt: Scalar) {
+        let (a, b) = (self.a, self.b);
+        let contacts = &constraints[self.range.0..self.range.1];
+        if !bodies.is_pair(a, b)
+            || contacts
+                .iter()
+                .any(|c| c.slip_speed(bodies) > ROLLING_SLIP_SPEED)
+        {
+            return;
+        }
+
+        let total_jn: Scalar = contacts.iter().map(|c| c.jn).sum();
+        let max = self.max_per_normal * total_jn;
+        let lambda = -self.mass * (bodies.omega[b] - bodies.omega[a]);
+
+        let old = self.impulse;
+        self.impulse = (old + lambda).clamp(-max, max);
+        let delta = self.impulse - old;
+
+        bodies.apply_impulse(a, Vec2::zero(), Vec2::zero(), -delta);
+        bodies.apply_impulse(b, Vec2::zero(), Vec2::zero(), delta);
+        bodies.sync_delta(a, dt);
+        bodies.sync_delta(b, dt);
+    }
+}
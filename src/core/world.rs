@@ -1,19 +1,174 @@
-use super::body::PhysicalEntity;
-use super::collision::{Manifold, broad_phase, narrow_phase};
-use super::integrator::{Integrator, integrate_velocity};
+use std::any::Any;
+use std::collections::BTreeSet;
+
+use super::body::{PhysicalEntity, RigidBodyBuilder};
+use super::collision::{
+    Aabb, Bvh, Collider2D, IncrementalSap, Manifold, RayHit, ShapeHit, broad_phase, cast,
+    narrow_phase,
+};
+use super::integrator::{Integrator, integrate_velocity, move_body};
 use super::params::SimParams;
-use super::solver::ConstraintSolver;
-use crate::forces::ForceGen;
+use super::solver::{Constraint, ConstraintSolver, ContactImpulse, JointBreak, SolverState};
+use crate::forces::{ForceEntry, ForceGen};
+use crate::math::Scalar;
 use crate::math::vec::Vec2;
 
+/// How an explosion's strength decays with distance `d` (see `World::apply_radial_impulse`).
+#[derive(Debug, Clone, Copy)]
+pub enum Falloff {
+    /// `1 - d / radius`: full strength at the center, zero at the edge.
+    Linear,
+    /// `2 / (1 + (d / radius)^2) - 1`: full strength at the center, then inverse-square in
+    /// units of the radius, shifted down to reach zero at the edge like `Linear`.
+    InverseSquare,
+}
+
+impl Falloff {
+    fn scale(self, distance: Scalar, radius: Scalar) -> Scalar {
+        match self {
+            Falloff::Linear if radius > 0.0 => (1.0 - distance / radius).max(0.0),
+            Falloff::InverseSquare if radius > 0.0 => {
+                let x = distance / radius;
+                (2.0 / (1.0 + x * x) - 1.0).max(0.0)
+            }
+            Falloff::Linear | Falloff::InverseSquare => 0.0,
+        }
+    }
+}
+
+/// Width of the floor added by `World::add_ground` (meters).
+pub const GROUND_WIDTH: Scalar = 1000.0;
+/// Thickness of the floor added by `World::add_ground`; deep enough that fast bodies don't
+/// tunnel through it.
+pub const GROUND_DEPTH: Scalar = 10.0;
+
+/// One contact point from the last `step`: narrow-phase geometry plus the solver's impulse.
+#[derive(Debug, Clone, Copy)]
+pub struct ContactView {
+    pub body_a: usize,
+    pub body_b: usize,
+    pub point: Vec2,
+    /// Contact normal, pointing from `body_a` to `body_b`.
+    pub normal: Vec2,
+    /// Positive when overlapping, negative for a speculative (separated) contact.
+    pub penetration: Scalar,
+    /// Accumulated normal impulse applied at this point (0 if it wasn't solved).
+    pub normal_impulse: Scalar,
+    /// Consecutive steps this contact already pushed in before this one (0 = new contact,
+    /// e.g. an impact). See `ContactConstraint::age`.
+    pub age: u32,
+}
+
+/// Sensor overlaps that started or ended during the last `step`; see `World::trigger_events`.
+///
+/// Pairs are `(min, max)` body indices, sorted.
+#[derive(Debug, Clone, Default)]
+pub struct TriggerEvents {
+    pub entered: Vec<(usize, usize)>,
+    pub exited: Vec<(usize, usize)>,
+}
+
+/// Counts from one `World::step`, for HUDs and profiling. All of them were computed by the
+/// step anyway.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StepStats {
+    /// Broad-phase candidate pairs (`World::pairs`).
+    pub broad_pairs: usize,
+    /// Pairs the narrow phase found touching (or about to), sensors excluded.
+    pub manifolds: usize,
+    /// Contact points over all manifolds.
+    pub contacts: usize,
+    /// Biased iterations the solver ran (`ConstraintSolver::last_iterations`).
+    pub solver_iterations: usize,
+    /// Bodies that can move (non-zero inverse mass or inertia).
+    pub active_bodies: usize,
+    /// Always 0: bodies don't sleep yet.
+    pub sleeping_bodies: usize,
+}
+
+/// Kinematic state of one body, as saved by `World::checkpoint`.
+#[derive(Debug, Clone, Copy)]
+struct BodyState {
+    pos: Vec2,
+    vel: Vec2,
+    force: Vec2,
+    angle: Scalar,
+    omega: Scalar,
+    torque: Scalar,
+}
+
+/// Snapshot of everything `World::step` carries from one step to the next (see
+/// `World::checkpoint`).
+#[derive(Debug, Clone)]
+pub struct WorldState {
+    bodies: Vec<BodyState>,
+    forces: Vec<ForceEntry>,
+    removed_forces: Vec<usize>,
+    solver: SolverState,
+    pairs: Vec<(usize, usize)>,
+    manifolds: Vec<Manifold>,
+    joints: Vec<Box<dyn Constraint>>,
+    joint_breaks: Vec<JointBreak>,
+    invalid_bodies: Vec<usize>,
+    safe_poses: Vec<(Vec2, Scalar)>,
+    one_way_passing: BTreeSet<(usize, usize)>,
+    sensor_overlaps: BTreeSet<(usize, usize)>,
+    removed_overlaps: Vec<(usize, usize)>,
+    trigger_events: TriggerEvents,
+    time: Scalar,
+}
+
 pub struct World {
+    /// Acceleration applied to every body with mass; zero disables gravity. Can be changed
+    /// between steps (see `set_gravity`).
     pub gravity: Vec2,
     pub integrator: Integrator,
     pub params: SimParams,
     pub entities: Vec<Box<dyn PhysicalEntity>>,
-    pub forces: Vec<Box<dyn ForceGen>>,
+    /// Force generators, each with its on/off flag; see `add_force` and `set_force_enabled`.
+    pub forces: Vec<ForceEntry>,
+    /// Indices (before removal) of the force generators that asked to be removed during the
+    /// last `step`; see `ForceGen::should_remove`.
+    pub removed_forces: Vec<usize>,
     pub solver: ConstraintSolver,
+    /// Broad-phase candidate pairs of the last `step`, sorted; the narrow phase decides
+    /// which of them actually touch. Pairs of two immovable bodies are left out (see
+    /// `broad_phase::retain_responsive`).
+    pub pairs: Vec<(usize, usize)>,
+    /// Raw narrow-phase output of the last `step`; prefer `contacts` for reading contacts.
     pub manifolds: Vec<Manifold>,
+    /// Joints (and any other custom `Constraint`), solved together with the contacts.
+    pub joints: Vec<Box<dyn Constraint>>,
+    /// Joints that broke during the last `step` (already removed from `joints`).
+    pub joint_breaks: Vec<JointBreak>,
+    /// Bodies whose state went NaN/inf during the last `step`. Their velocity was reset to
+    /// zero and a NaN/inf position or angle put back to the last finite one, so the bad
+    /// values don't spread through contacts.
+    pub invalid_bodies: Vec<usize>,
+    /// Last finite pose of every body, for the NaN/inf guard.
+    safe_poses: Vec<(Vec2, Scalar)>,
+    /// Body pairs currently passing through a one-way platform.
+    one_way_passing: BTreeSet<(usize, usize)>,
+    /// Overlapping pairs involving a sensor, as of the last `step`.
+    sensor_overlaps: BTreeSet<(usize, usize)>,
+    /// Sensor overlaps ended by `remove` since the last `step`, which reports them as exits.
+    removed_overlaps: Vec<(usize, usize)>,
+    /// See `trigger_events`.
+    trigger_events: TriggerEvents,
+    /// Prebuilt tree of static scenery; see `rebuild_static_bvh`.
+    static_bvh: Option<Bvh>,
+    /// Persistent sweep and prune; see `set_incremental_broad_phase`.
+    incremental_sap: Option<IncrementalSap>,
+    /// Buffers of the per-step sweep and prune, reused between steps.
+    sap_scratch: broad_phase::SapScratch,
+    /// Island label of every body after the last `step`; see `island_of`.
+    islands: Vec<Option<usize>>,
+    /// Multiplies the `dt` passed to `step`; see `set_time_scale`.
+    time_scale: Scalar,
+    /// Simulated time; see `time`.
+    time: Scalar,
+    /// Scaled dt of the current (or last) step; see `dt`.
+    dt: Scalar,
 }
 
 impl World {
@@ -24,17 +179,584 @@ impl World {
             params: SimParams::default(),
             entities: Vec::new(),
             forces: Vec::new(),
+            removed_forces: Vec::new(),
             solver: ConstraintSolver::new(10),
+            pairs: Vec::new(),
             manifolds: Vec::new(),
+            joints: Vec::new(),
+            joint_breaks: Vec::new(),
+            invalid_bodies: Vec::new(),
+            safe_poses: Vec::new(),
+            static_bvh: None,
+            incremental_sap: None,
+            sap_scratch: broad_phase::SapScratch::default(),
+            one_way_passing: BTreeSet::new(),
+            sensor_overlaps: BTreeSet::new(),
+            removed_overlaps: Vec::new(),
+            trigger_events: TriggerEvents::default(),
+            islands: Vec::new(),
+            time_scale: 1.0,
+            time: 0.0,
+            dt: 0.0,
         }
     }
 
+    pub fn gravity(&self) -> Vec2 {
+        self.gravity
+    }
+
+    /// Change gravity at runtime, e.g. flip it or turn it off with `Vec2::zero()`.
+    pub fn set_gravity(&mut self, gravity: Vec2) {
+        self.gravity = gravity;
+    }
+
+    pub fn time_scale(&self) -> Scalar {
+        self.time_scale
+    }
+
+    /// Slow motion (or fast forward): every `step(dt)` simulates `dt * scale` instead.
+    /// Negative values are clamped to 0, which pauses the world.
+    pub fn set_time_scale(&mut self, scale: Scalar) {
+        self.time_scale = scale.max(0.0);
+    }
+
+    /// Simulated time in seconds: the sum of every step's scaled `dt`.
+    ///
+    /// While the force generators run it is the time at the start of the step being taken,
+    /// so a generator can plan for `time() + dt()`.
+    pub fn time(&self) -> Scalar {
+        self.time
+    }
+
+    /// Scaled length of the step being taken (of the last step, between steps).
+    pub fn dt(&self) -> Scalar {
+        self.dt
+    }
+
     pub fn add(&mut self, entity: Box<dyn PhysicalEntity>) {
         self.entities.push(entity);
     }
 
-    pub fn add_force(&mut self, force: Box<dyn ForceGen>) {
-        self.forces.push(force);
+    /// Remove entity `index` and hand it back. Later entities shift down by one, like
+    /// `Vec::remove`, and so does every index the world keeps: sensor overlaps, one-way
+    /// passes, last step's contacts (with their warm-start impulses), joints and force
+    /// generators.
+    ///
+    /// Joints and generators attached to the removed body are dropped (see
+    /// `Constraint::body_removed` and `ForceGen::body_removed`), and so are its contacts.
+    /// Its sensor overlaps end: the next step reports them in `TriggerEvents::exited`, with
+    /// the indices they had before the removal. Prefer this to removing from `entities`
+    /// directly, which leaves those indices pointing at the wrong bodies.
+    pub fn remove(&mut self, index: usize) -> Option<Box<dyn PhysicalEntity>> {
+        if index >= self.entities.len() {
+            return None;
+        }
+        let entity = self.entities.remove(index);
+        self.removed_overlaps.extend(
+            self.sensor_overlaps
+                .iter()
+                .filter(|&&(a, b)| a == index || b == index),
+        );
+        let renumber_pair = |&(a, b): &(usize, usize)| {
+            let (mut a, mut b) = (a, b);
+            (renumber_after_removal(&mut a, index) && renumber_after_removal(&mut b, index))
+                .then_some((a, b))
+        };
+        self.sensor_overlaps = self
+            .sensor_overlaps
+            .iter()
+            .filter_map(renumber_pair)
+            .collect();
+        self.one_way_passing = self
+            .one_way_passing
+            .iter()
+            .filter_map(renumber_pair)
+            .collect();
+        self.pairs = self.pairs.iter().filter_map(renumber_pair).collect();
+        self.manifolds.retain_mut(|m| {
+            renumber_after_removal(&mut m.a, index) && renumber_after_removal(&mut m.b, index)
+        });
+        self.solver.body_removed(index);
+        self.joints.retain_mut(|j| j.body_removed(index));
+        self.forces.retain_mut(|f| f.generator.body_removed(index));
+        if index < self.safe_poses.len() {
+            self.safe_poses.remove(index);
+        }
+        self.invalid_bodies
+            .retain_mut(|i| renumber_after_removal(i, index));
+        if self.static_bvh.is_some() {
+            self.rebuild_static_bvh();
+        }
+        if self.incremental_sap.is_some() {
+            self.incremental_sap = Some(IncrementalSap::new());
+        }
+        self.islands = label_islands(&self.entities, &self.manifolds, &self.joints);
+        Some(entity)
+    }
+
+    /// Add a static floor whose top surface is at height `y`, and return its index.
+    ///
+    /// It's an ordinary static box, `GROUND_WIDTH` wide and `GROUND_DEPTH` deep, centered
+    /// on x = 0, so bodies landing on it get the same friction and rotation response as on
+    /// any other body.
+    pub fn add_ground(&mut self, y: Scalar, friction: Scalar, restitution: Scalar) -> usize {
+        let ground = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, y - 0.5 * GROUND_DEPTH))
+            .mass(0.0)
+            .box_shape(GROUND_WIDTH, GROUND_DEPTH)
+            .friction(friction)
+            .restitution(restitution)
+            .build();
+        self.add(Box::new(ground));
+        self.entities.len() - 1
+    }
+
+    /// Add a force generator and return its index in `forces`.
+    pub fn add_force(&mut self, force: Box<dyn ForceGen>) -> usize {
+        self.forces.push(ForceEntry::new(force));
+        self.forces.len() - 1
+    }
+
+    /// Remove force generator `index` and hand it back. Later generators shift down by one,
+    /// like `Vec::remove`.
+    pub fn remove_force(&mut self, index: usize) -> Option<Box<dyn ForceGen>> {
+        if index >= self.forces.len() {
+            return None;
+        }
+        Some(self.forces.remove(index).generator)
+    }
+
+    /// Turn force generator `index` off or back on without removing it. A disabled
+    /// generator is skipped by `step` (no force, no `should_remove` check) and stores no
+    /// energy.
+    pub fn set_force_enabled(&mut self, index: usize, enabled: bool) {
+        if let Some(entry) = self.forces.get_mut(index) {
+            entry.enabled = enabled;
+        }
+    }
+
+    pub fn force_enabled(&self, index: usize) -> bool {
+        self.forces.get(index).is_some_and(|f| f.enabled)
+    }
+
+    /// Put every static body (zero inverse mass and inertia, with a collider) into a BVH.
+    ///
+    /// Call this after adding the scenery. From then on the broad phase only sweeps the
+    /// remaining bodies and queries them against the tree, which pays off with hundreds of
+    /// static tiles and a handful of moving bodies. Call it again after moving or removing
+    /// static bodies or changing their speculative margins. Static bodies moved by their
+    /// velocity (e.g. a `PathMover` platform) don't need it: `step` refits the tree after
+    /// they move.
+    pub fn rebuild_static_bvh(&mut self) {
+        let items: Vec<(usize, Aabb)> = self
+            .entities
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| e.inv_mass() == 0.0 && e.inv_inertia() == 0.0)
+            .filter(|(_, e)| e.collider().is_some())
+            .map(|(i, e)| (i, broad_phase::fat_aabb(&**e, self.params, self.dt)))
+            .collect();
+        self.static_bvh = Some(Bvh::build(&items));
+    }
+
+    /// Drop the static BVH and go back to sweeping every body.
+    pub fn clear_static_bvh(&mut self) {
+        self.static_bvh = None;
+    }
+
+    /// Switch between re-sorting every body each step (the default) and an `IncrementalSap`
+    /// that keeps its sorted order between steps.
+    ///
+    /// The incremental sweep pays off with many slow-moving bodies. It finds the same pairs
+    /// but sweeps every body, so it ignores the static BVH while enabled.
+    pub fn set_incremental_broad_phase(&mut self, enabled: bool) {
+        self.incremental_sap = enabled.then(IncrementalSap::new);
+    }
+
+    pub fn incremental_broad_phase(&self) -> bool {
+        self.incremental_sap.is_some()
+    }
+
+    /// Freeze entity `index` into static scenery: it stops moving, gravity and forces no
+    /// longer affect it, but it still collides.
+    ///
+    /// Static just means zero inverse mass and inertia, which `step` already leaves alone.
+    /// Warm-start impulses of the body's contacts and joints are dropped, since they were
+    /// solved for the old mass.
+    pub fn set_static(&mut self, index: usize) {
+        let Some(e) = self.entities.get_mut(index) else {
+            return;
+        };
+        *e.vel_mut() = Vec2::zero();
+        *e.omega_mut() = 0.0;
+        e.set_mass(0.0);
+        self.invalidate_body(index);
+    }
+
+    /// Make entity `index` dynamic again with the given mass; the inertia is recomputed from
+    /// its collider (or restored from `RigidBody::inertia_override`). If it was in the static
+    /// BVH, the tree is rebuilt without it.
+    pub fn set_dynamic(&mut self, index: usize, mass: Scalar) {
+        let Some(e) = self.entities.get_mut(index) else {
+            return;
+        };
+        e.set_mass(mass);
+        self.invalidate_body(index);
+    }
+
+    /// Move entity `index` to a new pose, keeping its velocity: the supported way to
+    /// reposition a body (respawns, editor drags) rather than writing `pos_mut`.
+    ///
+    /// Its contacts' warm-start impulses are dropped (`invalidate_body`): they were solved
+    /// for where it was, and would kick it wherever it lands on the next step. The other
+    /// bodies' contacts keep theirs. It's no longer passing through any one-way platform.
+    /// Sensor overlaps are kept, so the next step reports it leaving a sensor it jumped out
+    /// of.
+    pub fn set_transform(&mut self, index: usize, pos: Vec2, angle: Scalar) {
+        let Some(e) = self.entities.get_mut(index) else {
+            return;
+        };
+        *e.pos_mut() = pos;
+        *e.angle_mut() = angle;
+        self.one_way_passing
+            .retain(|&(a, b)| a != index && b != index);
+        self.invalidate_body(index);
+    }
+
+    /// Call after changing entity `index`'s shape or mass in place (e.g.
+    /// `RigidBody::rescale`).
+    ///
+    /// Drops the warm-start impulses of its contacts and joints, which were solved for the
+    /// old body, and rebuilds the static BVH if the body is in it.
+    pub fn invalidate_body(&mut self, index: usize) {
+        self.solver.forget_body(index);
+        for j in &mut self.joints {
+            let (a, b) = j.bodies();
+            if a == index || b == index {
+                j.reset_impulses();
+            }
+        }
+        if self
+            .static_bvh
+            .as_ref()
+            .is_some_and(|bvh| bvh.contains(index))
+        {
+            self.rebuild_static_bvh();
+        }
+    }
+
+    /// Add a joint and return its index in `joints`.
+    pub fn add_joint<J: Constraint>(&mut self, joint: J) -> usize {
+        self.joints.push(Box::new(joint));
+        self.joints.len() - 1
+    }
+
+    /// Joint `index` as its concrete type, e.g. `world.joint::<WeldJoint>(0)`.
+    pub fn joint<J: Constraint>(&self, index: usize) -> Option<&J> {
+        let any: &dyn Any = self.joints.get(index)?.as_ref();
+        any.downcast_ref()
+    }
+
+    /// Indices of all entities whose collider AABB overlaps the box `[min, max]`.
+    ///
+    /// With `fattened = true` the entity AABBs are grown by their speculative margins,
+    /// exactly like the broad phase sees them; otherwise the tight bounds are used.
+    pub fn query_aabb(&self, min: Vec2, max: Vec2, fattened: bool) -> Vec<usize> {
+        let params = fattened.then_some((self.params, self.dt));
+        broad_phase::query_aabb(&self.entities, &Aabb::new(min, max), params)
+    }
+
+    /// Indices of all entities whose (tight) AABB comes within `radius` of `center`.
+    ///
+    /// This is an AABB-level test: the box query is refined by the distance from
+    /// `center` to the closest point of each candidate's AABB, not to its exact shape.
+    pub fn query_circle(&self, center: Vec2, radius: Scalar) -> Vec<usize> {
+        let ext = Vec2::new(radius, radius);
+        self.query_aabb(center - ext, center + ext, false)
+            .into_iter()
+            .filter(|&i| {
+                let aabb = broad_phase::entity_aabb(&*self.entities[i], 0.0);
+                (aabb.closest_point(center) - center).length_squared() <= radius * radius
+            })
+            .collect()
+    }
+
+    /// Sweep `collider` (kept at angle 0) in a straight line from `from` to `to` and return
+    /// the first body it hits, if any.
+    ///
+    /// `toi` is the fraction of the way along the sweep where the shape touches the body. A
+    /// shape that already overlaps something at `from` reports `toi` 0 and a normal that
+    /// pushes it out.
+    pub fn shape_cast(&self, collider: &Collider2D, from: Vec2, to: Vec2) -> Option<ShapeHit> {
+        cast::shape_cast(&self.entities, collider, from, to, |_| true)
+    }
+
+    /// `shape_cast` against only the bodies for which `filter(index)` is true, e.g.
+    /// `|i| i != player` to sweep a body's own collider without hitting the body itself.
+    pub fn shape_cast_filtered(
+        &self,
+        collider: &Collider2D,
+        from: Vec2,
+        to: Vec2,
+        filter: impl FnMut(usize) -> bool,
+    ) -> Option<ShapeHit> {
+        cast::shape_cast(&self.entities, collider, from, to, filter)
+    }
+
+    /// Cast a ray `max_dist` along `dir` from `origin` and return the first body it hits,
+    /// with the bodies moving at their current velocities over `dt`: a hitscan shot that a
+    /// fast target can't slip past between two steps.
+    ///
+    /// `toi` is the fraction of `dt` at which the body reaches the ray; of bodies hit at the
+    /// same time the nearest wins. `dt = 0` is a plain raycast. Spin isn't swept.
+    pub fn raycast_ccd(
+        &self,
+        origin: Vec2,
+        dir: Vec2,
+        max_dist: Scalar,
+        dt: Scalar,
+    ) -> Option<RayHit> {
+        cast::raycast_ccd(&self.entities, origin, dir, max_dist, dt)
+    }
+
+    /// Impulses applied at every contact point during the last `step`.
+    pub fn contact_impulses(&self) -> Vec<ContactImpulse> {
+        self.solver.contact_impulses()
+    }
+
+    /// Total normal impulse between bodies `a` and `b` (in either order) during the last
+    /// `step`, summed over all their contact points; `None` if they weren't in contact.
+    pub fn contact_impulse(&self, a: usize, b: usize) -> Option<Scalar> {
+        let pair = (a.min(b), a.max(b));
+        self.solver
+            .constraints
+            .iter()
+            .filter(|c| (c.index_a.min(c.index_b), c.index_a.max(c.index_b)) == pair)
+            .map(|c| c.jn)
+            .reduce(|sum, jn| sum + jn)
+    }
+
+    /// Save the simulation state, e.g. for rollback netcode.
+    ///
+    /// Covers the bodies' kinematic state (including pending force/torque), the force
+    /// generators (with their on/off flags, so a spring that snapped since comes back), the
+    /// solver's warm-start impulses, the joints and the last step's contacts. Bodies' shapes,
+    /// masses and materials and the params are not saved: `restore` expects the same set of
+    /// bodies. The simulated `time` is saved too.
+    pub fn checkpoint(&self) -> WorldState {
+        WorldState {
+            bodies: self
+                .entities
+                .iter()
+                .map(|e| BodyState {
+                    pos: *e.pos(),
+                    vel: *e.vel(),
+                    force: *e.force(),
+                    angle: e.angle(),
+                    omega: e.omega(),
+                    torque: e.torque(),
+                })
+                .collect(),
+            forces: self.forces.clone(),
+            removed_forces: self.removed_forces.clone(),
+            solver: self.solver.warm_start_state(),
+            pairs: self.pairs.clone(),
+            manifolds: self.manifolds.clone(),
+            joints: self.joints.clone(),
+            joint_breaks: self.joint_breaks.clone(),
+            invalid_bodies: self.invalid_bodies.clone(),
+            safe_poses: self.safe_poses.clone(),
+            one_way_passing: self.one_way_passing.clone(),
+            sensor_overlaps: self.sensor_overlaps.clone(),
+            removed_overlaps: self.removed_overlaps.clone(),
+            trigger_events: self.trigger_events.clone(),
+            time: self.time,
+        }
+    }
+
+    /// Go back to a `checkpoint`. Stepping on from here reproduces the original run
+    /// bit for bit, as long as the bodies' shapes, masses and materials, the params and the
+    /// static BVH are the same as when it was taken.
+    ///
+    /// Bodies are matched by index; bodies added after the checkpoint keep their state.
+    /// Force generators are replaced by the saved ones.
+    pub fn restore(&mut self, state: &WorldState) {
+        for (e, b) in self.entities.iter_mut().zip(&state.bodies) {
+            *e.pos_mut() = b.pos;
+            *e.vel_mut() = b.vel;
+            *e.force_mut() = b.force;
+            *e.angle_mut() = b.angle;
+            *e.omega_mut() = b.omega;
+            *e.torque_mut() = b.torque;
+        }
+        self.forces.clone_from(&state.forces);
+        self.removed_forces.clone_from(&state.removed_forces);
+        self.solver.restore_warm_start(&state.solver);
+        self.pairs.clone_from(&state.pairs);
+        self.manifolds.clone_from(&state.manifolds);
+        self.joints.clone_from(&state.joints);
+        self.joint_breaks.clone_from(&state.joint_breaks);
+        self.invalid_bodies.clone_from(&state.invalid_bodies);
+        self.safe_poses.clone_from(&state.safe_poses);
+        self.one_way_passing.clone_from(&state.one_way_passing);
+        self.sensor_overlaps.clone_from(&state.sensor_overlaps);
+        self.removed_overlaps.clone_from(&state.removed_overlaps);
+        self.trigger_events.clone_from(&state.trigger_events);
+        self.time = state.time;
+        self.islands = label_islands(&self.entities, &self.manifolds, &self.joints);
+    }
+
+    /// Sensor overlaps that began (`entered`) or ended (`exited`) during the last `step`, one
+    /// event per body pair: a checkpoint fires once when a body enters it.
+    ///
+    /// A removed body exits every sensor it overlapped on the next step, whether it was
+    /// popped off the end of `entities` or taken out with `World::remove` (which reports the
+    /// indices from before the removal and renumbers the other overlaps).
+    pub fn trigger_events(&self) -> &TriggerEvents {
+        &self.trigger_events
+    }
+
+    /// Island of body `index` as of the last `step`: dynamic bodies connected through
+    /// contacts or joints share an island, labelled by its lowest body index. Static bodies
+    /// don't connect anything and belong to no island (`None`), like bodies added since.
+    pub fn island_of(&self, index: usize) -> Option<usize> {
+        self.islands.get(index).copied().flatten()
+    }
+
+    /// Every contact point from the last `step`, with the impulse the solver applied there.
+    ///
+    /// This is the stable way to inspect contacts (debug drawing, custom inspectors).
+    pub fn contacts(&self) -> impl Iterator<Item = ContactView> + '_ {
+        // The solver builds one constraint per manifold point, in the same order.
+        let mut constraints = self.solver.constraints.iter();
+        self.manifolds
+            .iter()
+            .flat_map(|m| m.points.iter().map(move |cp| (m, cp)))
+            .map(move |(m, cp)| {
+                let (normal_impulse, age) = constraints
+                    .next()
+                    .filter(|c| c.index_a == m.a && c.index_b == m.b && c.feature_id == cp.id)
+                    .map_or((0.0, 0), |c| (c.jn, c.age));
+                ContactView {
+                    body_a: m.a,
+                    body_b: m.b,
+                    point: cp.point,
+                    normal: m.normal,
+                    penetration: cp.penetration,
+                    normal_impulse,
+                    age,
+                }
+            })
+    }
+
+    /// The contact point from the last `step` nearest to `p`, if one is within `radius`:
+    /// for a debug inspector that follows a single contact of a pile.
+    pub fn pick_contact(&self, p: Vec2, radius: Scalar) -> Option<ContactView> {
+        self.contacts()
+            .map(|c| ((c.point - p).length_squared(), c))
+            .filter(|(d, _)| *d <= radius * radius)
+            .min_by(|(d, _), (e, _)| d.total_cmp(e))
+            .map(|(_, c)| c)
+    }
+
+    /// Explosion: push every body within `radius` of `center` directly away from it.
+    ///
+    /// The impulse points from `center` to the body's center of mass; its size is `strength`
+    /// scaled by `falloff` of the distance from `center` to the nearest point of the body's
+    /// AABB (so a large body next to the blast is hit even if its center is out of range). It
+    /// is applied at the center of mass, so it adds no spin. Nothing blocks the blast: bodies
+    /// behind walls are hit too.
+    pub fn apply_radial_impulse(
+        &mut self,
+        center: Vec2,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: Falloff,
+    ) {
+        self.radial_impulse(center, radius, strength, falloff, false);
+    }
+
+    /// Like `apply_radial_impulse`, but the impulse is applied at the point of each body's
+    /// AABB nearest to `center`, so bodies hit off-center also start to spin.
+    pub fn apply_radial_impulse_with_spin(
+        &mut self,
+        center: Vec2,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: Falloff,
+    ) {
+        self.radial_impulse(center, radius, strength, falloff, true);
+    }
+
+    fn radial_impulse(
+        &mut self,
+        center: Vec2,
+        radius: Scalar,
+        strength: Scalar,
+        falloff: Falloff,
+        spin: bool,
+    ) {
+        for i in self.query_circle(center, radius) {
+            let e = &mut self.entities[i];
+            let com = e.world_center_of_mass();
+            let offset = com - center;
+            let center_distance = offset.length();
+            if center_distance < 1e-6 {
+                // Blast exactly at the body center: no direction to push in.
+                continue;
+            }
+            let hit_point = broad_phase::entity_aabb(&**e, 0.0).closest_point(center);
+            let scale = falloff.scale((hit_point - center).length(), radius);
+            let impulse = offset * (strength * scale / center_distance);
+
+            *e.vel_mut() = *e.vel() + impulse * e.inv_mass();
+            if spin {
+                let r = hit_point - com;
+                *e.omega_mut() = e.omega() + r.cross(impulse) * e.inv_inertia();
+            }
+        }
+    }
+
+    /// Total kinetic energy: translational `1/2 m v^2` plus rotational `1/2 I w^2`.
+    ///
+    /// Static bodies (zero inverse mass / inertia) contribute nothing.
+    pub fn total_kinetic_energy(&self) -> Scalar {
+        let mut kinetic = 0.0;
+        for e in &self.entities {
+            kinetic += 0.5 * e.mass() * e.vel().length_squared();
+            kinetic += 0.5 * e.inertia() * e.omega() * e.omega();
+        }
+        kinetic
+    }
+
+    /// Total linear momentum `sum(m v)` of all dynamic bodies.
+    pub fn total_momentum(&self) -> Vec2 {
+        let mut momentum = Vec2::zero();
+        for e in &self.entities {
+            momentum = momentum + *e.vel() * e.mass();
+        }
+        momentum
+    }
+
+    /// Total angular momentum about the world point `p`:
+    /// orbital part `sum(r x m v)` plus spin part `sum(I w)`.
+    pub fn angular_momentum_about(&self, p: Vec2) -> Scalar {
+        let mut l = 0.0;
+        for e in &self.entities {
+            let r = e.world_center_of_mass() - p;
+            l += r.cross(*e.vel()) * e.mass();
+            l += e.inertia() * e.omega();
+        }
+        l
+    }
+
+    /// Elastic energy stored in all force generators (springs are the only ones that store any).
+    pub fn spring_potential_energy(&self) -> Scalar {
+        self.forces
+            .iter()
+            .filter(|f| f.enabled)
+            .map(|f| f.generator.potential_energy(self))
+            .sum()
     }
 
     /// TGS-style simulation step:
@@ -44,10 +766,26 @@ impl World {
     /// 4) collision detect (broad + narrow)
     /// 5) solve contacts (TGS: solver predicts per-body motion internally)
     /// 6) integrate position
-    pub fn step(&mut self, dt: f32) {
+    /// 7) joint position passes (`Constraint::solve_position`)
+    ///
+    /// With `SolverParams::substeps > 1`, steps 3 and 6 happen inside the solve instead,
+    /// once per substep (always semi-implicit Euler).
+    ///
+    /// Contacts are built after step 3, but restitution bounces off the approach velocity
+    /// from before it: an elastic ball landing under gravity rebounds to its drop height.
+    ///
+    /// `dt` is scaled by the time scale first. The accumulators are cleared at the start
+    /// rather than the end, so afterwards each body's `force` / `torque` still hold what
+    /// gravity and the generators applied during this step (contact impulses not included).
+    ///
+    /// Returns what the step did (see `StepStats`); a step with `dt <= 0` does nothing and
+    /// returns all zeros.
+    pub fn step(&mut self, dt: Scalar) -> StepStats {
+        let dt = dt * self.time_scale;
         if dt <= 0.0 {
-            return;
+            return StepStats::default();
         }
+        self.dt = dt;
 
         // (1) Clear accumulators.
         for e in &mut self.entities {
@@ -55,41 +793,274 @@ impl World {
             e.clear_torque();
         }
 
-        // (2) Apply gravity as force: F = m * g.
+        // (2) Apply gravity as force: F = m * g, at the center of mass (no torque; a body
+        // with an offset one rights itself through its contacts).
         for e in &mut self.entities {
-            if e.inv_mass() > 0.0 {
-                let mass = 1.0 / e.inv_mass();
-                *e.force_mut() = *e.force() + self.gravity * mass;
-            }
+            *e.force_mut() = *e.force() + self.gravity * e.mass();
         }
 
         // (2b) Apply user force generators (springs, drag, ...).
-        let forces = core::mem::take(&mut self.forces);
-        for f in &forces {
-            f.apply(self);
+        // Generators see an empty `forces` while they run; indices are unchanged afterwards.
+        let mut forces = core::mem::take(&mut self.forces);
+        for f in forces.iter().filter(|f| f.enabled) {
+            f.generator.apply(self);
+        }
+
+        // (2c) Drop generators that are done (snapped springs, ...).
+        let mut removed = core::mem::take(&mut self.removed_forces);
+        removed.clear();
+        for (i, f) in forces.iter().enumerate() {
+            if f.enabled && f.generator.should_remove(self) {
+                removed.push(i);
+            }
+        }
+        for &i in removed.iter().rev() {
+            forces.remove(i);
         }
+        self.removed_forces = removed;
         self.forces = forces;
+        self.time += dt;
 
         // (3) Integrate velocities from accumulated force/torque.
-        for e in &mut self.entities {
-            integrate_velocity(&mut **e, dt, self.integrator);
+        // Bodies that can't rotate (particles, static scenery) skip the angular terms, which
+        // would only multiply by a zero inverse inertia; large particle systems notice.
+        // With substeps the solver integrates velocity and position itself, per substep.
+        let substepping = self.solver.params.substeps > 1;
+        if !substepping {
+            for e in &mut self.entities {
+                if e.inv_inertia() == 0.0 {
+                    *e.vel_mut() = *e.vel() + *e.force() * e.inv_mass() * dt;
+                } else {
+                    integrate_velocity(&mut **e, dt, self.integrator);
+                }
+            }
         }
 
-        // (4) Detect collisions at current configuration.
-        let pairs = broad_phase::detect_sap(&self.entities, self.params);
-        self.manifolds = narrow_phase::detect(&self.entities, &pairs, self.params);
+        // (3b) Catch NaN/inf before it reaches contacts and spreads to neighbours.
+        self.invalid_bodies.clear();
+        self.safe_poses
+            .resize(self.entities.len(), (Vec2::zero(), 0.0));
+        for (i, e) in self.entities.iter_mut().enumerate() {
+            if !reset_if_non_finite(&mut **e, &mut self.safe_poses[i]) {
+                self.invalid_bodies.push(i);
+            }
+        }
+
+        // (4) Detect collisions at current configuration, looking ahead over this step.
+        // Into the buffers of last step, which are usually big enough already.
+        match (&mut self.incremental_sap, &self.static_bvh) {
+            (Some(sap), _) => sap.update_into(&self.entities, self.params, dt, &mut self.pairs),
+            (None, Some(bvh)) => broad_phase::detect_sap_with_bvh_into(
+                &self.entities,
+                self.params,
+                dt,
+                bvh,
+                &mut self.sap_scratch,
+                &mut self.pairs,
+            ),
+            (None, None) => broad_phase::detect_sap_into(
+                &self.entities,
+                self.params,
+                dt,
+                &mut self.sap_scratch,
+                &mut self.pairs,
+            ),
+        }
+        broad_phase::retain_responsive(&self.entities, &mut self.pairs);
+        narrow_phase::detect_into(
+            &self.entities,
+            &self.pairs,
+            self.params,
+            dt,
+            self.solver.params.default_material(),
+            &mut self.manifolds,
+        );
+        narrow_phase::filter_one_way(
+            &self.entities,
+            &mut self.manifolds,
+            &mut self.one_way_passing,
+        );
+
+        // (4b) Sensors: no response, only overlap events.
+        let overlaps = narrow_phase::take_sensor_overlaps(&self.entities, &mut self.manifolds);
+        self.trigger_events.entered.clear();
+        self.trigger_events
+            .entered
+            .extend(overlaps.difference(&self.sensor_overlaps));
+        self.trigger_events.exited.clear();
+        self.trigger_events
+            .exited
+            .extend(self.sensor_overlaps.difference(&overlaps));
+        self.trigger_events
+            .exited
+            .append(&mut self.removed_overlaps);
+        self.trigger_events.exited.sort_unstable();
+        self.sensor_overlaps = overlaps;
 
         // (5) Build constraints and solve (TGS-style: uses delta tracking).
         self.solver
             .build_constraints(&self.manifolds, &self.entities, dt);
-        self.solver.solve(&mut self.entities);
+        self.solver.solve(&mut self.entities, &mut self.joints);
+
+        // (5b) Remove joints that snapped during the solve and report them.
+        self.joint_breaks.clear();
+        let mut index = 0;
+        self.joints.retain(|j| {
+            let broken = j.is_broken();
+            if broken {
+                let (body_a, body_b) = j.bodies();
+                self.joint_breaks.push(JointBreak {
+                    joint: index,
+                    body_a,
+                    body_b,
+                });
+            }
+            index += 1;
+            !broken
+        });
+        self.islands = label_islands(&self.entities, &self.manifolds, &self.joints);
+
+        // (5c) Same guard after the solve, then the speed limits.
+        for (i, e) in self.entities.iter_mut().enumerate() {
+            if !reset_if_non_finite(&mut **e, &mut self.safe_poses[i])
+                && !self.invalid_bodies.contains(&i)
+            {
+                self.invalid_bodies.push(i);
+            }
+            clamp_speed(&mut **e, self.params);
+        }
 
         // (6) Integrate positions.
-        for e in &mut self.entities {
-            let dp = *e.vel() * dt;
-            let da = e.omega() * dt;
-            *e.pos_mut() = *e.pos() + dp;
-            *e.angle_mut() = e.angle() + da;
+        if !substepping {
+            for e in &mut self.entities {
+                // A zero inverse inertia can still spin if the user set omega (kinematic
+                // bodies).
+                let (dp, da) = (*e.vel() * dt, e.omega() * dt);
+                move_body(&mut **e, dp, da);
+            }
+        }
+
+        // (7) Position pass of the joints that have one.
+        for j in &mut self.joints {
+            j.solve_position(&mut self.entities);
+        }
+
+        // (8) Static bodies only move when driven (kinematic platforms); keep their leaves
+        // in the static BVH where they are now.
+        if let Some(bvh) = &mut self.static_bvh {
+            let moved = |i: usize| {
+                let e = &self.entities[i];
+                e.vel().length_squared() > 0.0 || e.omega() != 0.0
+            };
+            if (0..self.entities.len()).any(|i| bvh.contains(i) && moved(i)) {
+                bvh.refit(|i| {
+                    self.entities
+                        .get(i)
+                        .map_or(Aabb::new(Vec2::zero(), Vec2::zero()), |e| {
+                            broad_phase::fat_aabb(&**e, self.params, dt)
+                        })
+                });
+            }
         }
+
+        StepStats {
+            broad_pairs: self.pairs.len(),
+            manifolds: self.manifolds.len(),
+            contacts: self.manifolds.iter().map(|m| m.points.len()).sum(),
+            solver_iterations: self.solver.last_iterations(),
+            active_bodies: self.islands.iter().filter(|i| i.is_some()).count(),
+            sleeping_bodies: 0,
+        }
+    }
+}
+
+/// Entity `removed` was taken out by `World::remove`: shift `index` down by one if it came
+/// after it. Returns `false` if `index` was the removed entity itself.
+pub fn renumber_after_removal(index: &mut usize, removed: usize) -> bool {
+    if *index == removed {
+        return false;
+    }
+    if *index > removed {
+        *index -= 1;
+    }
+    true
+}
+
+/// Union-find over the contact and joint graph; see `World::island_of`.
+fn label_islands(
+    entities: &[Box<dyn PhysicalEntity>],
+    manifolds: &[Manifold],
+    joints: &[Box<dyn Constraint>],
+) -> Vec<Option<usize>> {
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let is_dynamic = |i: usize| entities[i].inv_mass() > 0.0 || entities[i].inv_inertia() > 0.0;
+    let mut parent: Vec<usize> = (0..entities.len()).collect();
+    let links = manifolds
+        .iter()
+        .map(|m| (m.a, m.b))
+        .chain(joints.iter().map(|j| j.bodies()));
+    for (a, b) in links {
+        if a >= entities.len() || b >= entities.len() || !is_dynamic(a) || !is_dynamic(b) {
+            continue;
+        }
+        let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+        // The lower index becomes the root, so it labels the island.
+        parent[ra.max(rb)] = ra.min(rb);
+    }
+    (0..entities.len())
+        .map(|i| is_dynamic(i).then(|| root(&mut parent, i)))
+        .collect()
+}
+
+/// Zero the velocity of a body whose state contains NaN/inf, and put a NaN/inf position
+/// or angle back to `safe_pose`, its last finite one (the origin, unrotated, if it never
+/// had one). A finite position or angle is remembered in `safe_pose`.
+///
+/// Returns `false` if the state was not finite.
+fn reset_if_non_finite(e: &mut dyn PhysicalEntity, safe_pose: &mut (Vec2, Scalar)) -> bool {
+    let pos_finite = e.pos().x.is_finite() && e.pos().y.is_finite();
+    let angle_finite = e.angle().is_finite();
+    let vel_finite = e.vel().x.is_finite() && e.vel().y.is_finite() && e.omega().is_finite();
+    if pos_finite {
+        safe_pose.0 = *e.pos();
+    } else {
+        *e.pos_mut() = safe_pose.0;
+    }
+    if angle_finite {
+        safe_pose.1 = e.angle();
+    } else {
+        *e.angle_mut() = safe_pose.1;
+    }
+    if !(pos_finite && angle_finite && vel_finite) {
+        *e.vel_mut() = Vec2::zero();
+        *e.omega_mut() = 0.0;
+        return false;
+    }
+    true
+}
+
+/// Clamp a body's speeds to the limits in `params` and its own spin limit.
+///
+/// A negative limit counts by its size and a NaN one as no limit, so a bad setting can't
+/// panic (or reverse the body) in the middle of a step.
+fn clamp_speed(e: &mut dyn PhysicalEntity, params: SimParams) {
+    let max_speed = params.max_linear_speed.abs();
+    let speed = e.vel().length();
+    if speed > max_speed {
+        *e.vel_mut() = *e.vel() * (max_speed / speed);
     }
+    // `min` skips a NaN operand.
+    let max_omega = [e.max_angular_speed(), Some(params.max_angular_speed)]
+        .into_iter()
+        .flatten()
+        .fold(Scalar::INFINITY, |max, m| max.min(m.abs()));
+    let omega = e.omega();
+    *e.omega_mut() = omega.clamp(-max_omega, max_omega);
 }
@@ -0,0 +1,109 @@
+use super::spring::Spring;
+use crate::core::{Particle, World};
+use crate::math::Scalar;
+use crate::math::consts::SQRT_2;
+use crate::math::vec::Vec2;
+
+/// Layout of a particle cloth for `build_grid_cloth`.
+#[derive(Debug, Clone, Copy)]
+pub struct GridCloth {
+    /// Position of the top-left particle. Columns go along +x, rows along -y.
+    pub origin: Vec2,
+    pub cols: usize,
+    pub rows: usize,
+    /// Rest distance between neighbouring particles.
+    pub spacing: Scalar,
+    /// Stiffness and damping of every spring.
+    pub k: Scalar,
+    pub c: Scalar,
+    /// Mass of each particle.
+    pub mass: Scalar,
+    /// Diagonal springs across every cell, so the cloth resists shearing.
+    pub shear: bool,
+    /// Springs that skip one particle, so the cloth resists folding.
+    pub bend: bool,
+    /// Make the top row static (zero mass) so the cloth hangs from it.
+    pub pin_top_row: bool,
+}
+
+impl GridCloth {
+    /// Structural springs only, top row pinned.
+    pub fn new(
+        origin: Vec2,
+        cols: usize,
+        rows: usize,
+        spacing: Scalar,
+        k: Scalar,
+        c: Scalar,
+        mass: Scalar,
+    ) -> Self {
+        Self {
+            origin,
+            cols,
+            rows,
+            spacing,
+            k,
+            c,
+            mass,
+            shear: false,
+            bend: false,
+            pin_top_row: true,
+        }
+    }
+}
+
+/// Add the particles and springs of `cloth` to `world`.
+///
+/// Returns the entity index of every particle as `grid[row][col]`, row 0 being the top.
+pub fn build_grid_cloth(world: &mut World, cloth: &GridCloth) -> Vec<Vec<usize>> {
+    let inv_mass = if cloth.mass > 0.0 {
+        1.0 / cloth.mass
+    } else {
+        0.0
+    };
+    let grid: Vec<Vec<usize>> = (0..cloth.rows)
+        .map(|row| {
+            (0..cloth.cols)
+                .map(|col| {
+                    let pos =
+                        cloth.origin + Vec2::new(col as Scalar, -(row as Scalar)) * cloth.spacing;
+                    let inv_mass = if row == 0 && cloth.pin_top_row {
+                        0.0
+                    } else {
+                        inv_mass
+                    };
+                    world.add(Box::new(Particle::new(pos, Vec2::zero(), inv_mass)));
+                    world.entities.len() - 1
+                })
+                .collect()
+        })
+        .collect();
+
+    // (row, col) steps to the neighbour each spring connects to, with its rest length.
+    let mut links = vec![((0, 1), 1.0), ((1, 0), 1.0)];
+    if cloth.shear {
+        links.extend([((1, 1), SQRT_2), ((1, -1), SQRT_2)]);
+    }
+    if cloth.bend {
+        links.extend([((0, 2), 2.0), ((2, 0), 2.0)]);
+    }
+    for row in 0..cloth.rows {
+        for col in 0..cloth.cols {
+            for &((dr, dc), length) in &links {
+                let (r, c) = (row + dr, col as isize + dc);
+                if r >= cloth.rows || c < 0 || c as usize >= cloth.cols {
+                    continue;
+                }
+                let spring = Spring::between(
+                    grid[row][col],
+                    grid[r][c as usize],
+                    cloth.k,
+                    cloth.c,
+                    length * cloth.spacing,
+                );
+                world.add_force(Box::new(spring));
+            }
+        }
+    }
+    grid
+}
@@ -1,8 +1,12 @@
 use super::ForceGen;
 use crate::core::World;
+use crate::core::collision::{Aabb, broad_phase};
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
 
+#[derive(Debug, Clone)]
 pub struct LinearDrag {
-    pub k: f32,
+    pub k: Scalar,
 }
 
 impl ForceGen for LinearDrag {
@@ -11,4 +15,69 @@ impl ForceGen for LinearDrag {
             *entity.force_mut() = *entity.force() - *entity.vel() * self.k;
         }
     }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
+
+/// Rotational drag: `torque = -k * omega` on every body that can spin.
+///
+/// With `scale_by_radius` the torque is also multiplied by the body's characteristic radius
+/// (half the diagonal of its unrotated collider AABB), so bigger bodies are slowed more.
+/// Bodies without a collider use a radius of 1.
+#[derive(Debug, Clone)]
+pub struct AngularDrag {
+    pub k: Scalar,
+    pub scale_by_radius: bool,
+}
+
+impl ForceGen for AngularDrag {
+    fn apply(&self, world: &mut World) {
+        for entity in world.entities.iter_mut().filter(|e| e.inv_inertia() > 0.0) {
+            let radius = match entity.collider() {
+                Some(c) if self.scale_by_radius => {
+                    c.aabb(&Transform2D::identity()).half_extents().length()
+                }
+                _ => 1.0,
+            };
+            *entity.torque_mut() -= self.k * radius * entity.omega();
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
+
+/// Linear drag confined to a box-shaped zone (water, mud, ...).
+///
+/// A body straddling the boundary gets drag scaled by the fraction of its (tight) AABB area
+/// inside the zone, so entering and leaving the zone is smooth rather than a step.
+/// Bodies without a collider count as a tiny box around their center.
+#[derive(Debug, Clone)]
+pub struct RegionDrag {
+    pub aabb: Aabb,
+    pub k: Scalar,
+}
+
+impl ForceGen for RegionDrag {
+    fn apply(&self, world: &mut World) {
+        for entity in world.entities.iter_mut().filter(|e| e.inv_mass() > 0.0) {
+            let body = broad_phase::entity_aabb(&**entity, 0.0);
+            let Some(inside) = body.intersection(&self.aabb) else {
+                continue;
+            };
+            let fraction = if body.area() > 0.0 {
+                (inside.area() / body.area()).min(1.0)
+            } else {
+                1.0
+            };
+            *entity.force_mut() = *entity.force() - *entity.vel() * (self.k * fraction);
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
 }
@@ -0,0 +1,77 @@
+use super::ForceGen;
+use crate::core::World;
+use crate::core::world::renumber_after_removal;
+use crate::math::Scalar;
+use crate::math::vec::Vec2;
+
+/// What a `PathMover` does after its last waypoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopMode {
+    /// Stop at the last waypoint.
+    Once,
+    /// Start over from the first waypoint (repeat the first one at the end for a closed
+    /// patrol route).
+    Loop,
+    /// Run the path backwards, then forwards again.
+    PingPong,
+}
+
+/// Moves a kinematic body along a timed path: at each `(point, time)` waypoint the body is
+/// at `point` at simulated time `time` (see `World::time`), moving in straight lines between.
+///
+/// It sets the body's velocity rather than its position, so the body reaches the path
+/// point at the end of each step and contacts see how fast it moves: a crate on a moving
+/// platform is carried along by friction. The body should be static (mass 0), otherwise
+/// gravity and contacts push it off the path. Waypoint times must increase.
+#[derive(Debug, Clone)]
+pub struct PathMover {
+    pub entity: usize,
+    pub waypoints: Vec<(Vec2, Scalar)>,
+    pub loop_mode: LoopMode,
+}
+
+impl PathMover {
+    /// Where the path is at simulated time `time`. Before the first waypoint it waits there.
+    pub fn position_at(&self, time: Scalar) -> Option<Vec2> {
+        let (&(first, start), &(last, end)) = (self.waypoints.first()?, self.waypoints.last()?);
+        let duration = end - start;
+        if duration <= 0.0 || time <= start {
+            return Some(first);
+        }
+        let t = match self.loop_mode {
+            LoopMode::Once => time.min(end),
+            LoopMode::Loop => start + (time - start).rem_euclid(duration),
+            LoopMode::PingPong => {
+                let phase = (time - start).rem_euclid(2.0 * duration);
+                start + phase.min(2.0 * duration - phase)
+            }
+        };
+        let next = self.waypoints.partition_point(|&(_, wt)| wt <= t);
+        if next == self.waypoints.len() {
+            return Some(last);
+        }
+        let (p0, t0) = self.waypoints[next - 1];
+        let (p1, t1) = self.waypoints[next];
+        Some(p0 + (p1 - p0) * ((t - t0) / (t1 - t0)))
+    }
+}
+
+impl ForceGen for PathMover {
+    fn apply(&self, world: &mut World) {
+        let dt = world.dt();
+        let Some(target) = self.position_at(world.time() + dt) else {
+            return;
+        };
+        if let Some(e) = world.entities.get_mut(self.entity) {
+            *e.vel_mut() = (target - *e.pos()) / dt;
+        }
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.entity, index)
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
@@ -1,10 +1,63 @@
+pub mod cloth;
 pub mod drag;
+pub mod kinematic;
 pub mod spring;
+pub mod torque;
 
 use std::any::Any;
+use std::fmt::Debug;
 
 use crate::core::World;
+use crate::math::Scalar;
 
-pub trait ForceGen: Any {
+pub trait ForceGen: Any + Debug {
     fn apply(&self, world: &mut World);
+
+    /// Checked by `World::step` right after `apply`; returning true removes the generator
+    /// (e.g. a spring that snapped). Its index is reported in `World::removed_forces`.
+    fn should_remove(&self, _world: &World) -> bool {
+        false
+    }
+
+    /// Potential energy stored in this generator (e.g. a stretched spring).
+    ///
+    /// Dissipative or external forces (drag, gravity-like fields) store none.
+    fn potential_energy(&self, _world: &World) -> Scalar {
+        0.0
+    }
+
+    /// Entity `index` was removed by `World::remove` and the later ones shifted down by one:
+    /// renumber the entities this generator acts on (`renumber_after_removal`), or return
+    /// false to be dropped. The default keeps the generator as it is, which suits those that
+    /// act on every body, like drag.
+    fn body_removed(&mut self, _index: usize) -> bool {
+        true
+    }
+
+    /// For `Clone` on `Box<dyn ForceGen>` (checkpoints copy the generators).
+    fn clone_box(&self) -> Box<dyn ForceGen>;
+}
+
+impl Clone for Box<dyn ForceGen> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// A force generator in `World::forces`, with its on/off switch.
+#[derive(Debug, Clone)]
+pub struct ForceEntry {
+    pub generator: Box<dyn ForceGen>,
+    /// A disabled generator is skipped by `World::step`; see `World::set_force_enabled`.
+    pub enabled: bool,
+}
+
+impl ForceEntry {
+    /// An enabled entry for `generator`.
+    pub fn new(generator: Box<dyn ForceGen>) -> Self {
+        Self {
+            generator,
+            enabled: true,
+        }
+    }
 }
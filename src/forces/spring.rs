@@ -1,39 +1,101 @@
 use super::ForceGen;
 use crate::core::World;
+use crate::core::world::renumber_after_removal;
+use crate::math::Scalar;
 use crate::math::vec::Vec2;
 
+#[derive(Debug, Clone)]
 pub enum SpringEnd {
     Entity(usize),
     Anchor(Vec2),
 }
 
+impl SpringEnd {
+    /// World-space position of this end (`None` if the entity index is out of range).
+    pub fn position(&self, world: &World) -> Option<Vec2> {
+        match self {
+            SpringEnd::Entity(i) => world.entities.get(*i).map(|e| *e.pos()),
+            SpringEnd::Anchor(p) => Some(*p),
+        }
+    }
+
+    /// See `ForceGen::body_removed`; anchors are kept as they are.
+    fn body_removed(&mut self, index: usize) -> bool {
+        match self {
+            SpringEnd::Entity(i) => renumber_after_removal(i, index),
+            SpringEnd::Anchor(_) => true,
+        }
+    }
+}
+
+/// Damped linear spring between two ends.
+///
+/// `rest = 0.0` is a pure attractor: the force `-k * displacement` needs no direction, so it
+/// stays defined all the way down to coincident ends. For `rest > 0` the direction matters
+/// and the force is skipped while the ends are within `1e-6` of each other; the damper is
+/// skipped there in both cases (it acts along the direction).
+///
+/// `max_force` caps the magnitude of the total spring + damper force. It applies to whatever
+/// the distance guard above leaves, so it never brings back a skipped force.
+///
+/// With `max_strain` set the spring snaps once `|extension| / rest` exceeds it: `World::step`
+/// removes it after that step's force and reports it in `removed_forces`. Zero rest length
+/// springs never snap.
+#[derive(Debug, Clone)]
 pub struct Spring {
     pub a: SpringEnd,
     pub b: SpringEnd,
-    pub k: f32,
-    pub c: f32,
-    pub rest: f32,
+    pub k: Scalar,
+    pub c: Scalar,
+    pub rest: Scalar,
+    pub max_force: Option<Scalar>,
+    pub max_strain: Option<Scalar>,
 }
 
 impl Spring {
-    pub fn between(i: usize, j: usize, k: f32, c: f32, rest: f32) -> Self {
+    pub fn between(i: usize, j: usize, k: Scalar, c: Scalar, rest: Scalar) -> Self {
         Self {
             a: SpringEnd::Entity(i),
             b: SpringEnd::Entity(j),
             k,
             c,
             rest,
+            max_force: None,
+            max_strain: None,
         }
     }
 
-    pub fn to_anchor(i: usize, anchor: Vec2, k: f32, c: f32, rest: f32) -> Self {
+    pub fn to_anchor(i: usize, anchor: Vec2, k: Scalar, c: Scalar, rest: Scalar) -> Self {
         Self {
             a: SpringEnd::Entity(i),
             b: SpringEnd::Anchor(anchor),
             k,
             c,
             rest,
+            max_force: None,
+            max_strain: None,
+        }
+    }
+
+    /// Clamp the spring + damper force to `max_force` (stiff springs with a large dt).
+    pub fn with_max_force(mut self, max_force: Scalar) -> Self {
+        self.max_force = Some(max_force);
+        self
+    }
+
+    /// Snap when stretched or compressed by more than `max_strain` times the rest length.
+    pub fn with_max_strain(mut self, max_strain: Scalar) -> Self {
+        self.max_strain = Some(max_strain);
+        self
+    }
+
+    /// `|extension| / rest`, or `None` for a zero rest length or a missing entity.
+    pub fn strain(&self, world: &World) -> Option<Scalar> {
+        if self.rest <= 0.0 {
+            return None;
         }
+        let (pa, pb) = (self.a.position(world)?, self.b.position(world)?);
+        Some(((pa - pb).length() - self.rest).abs() / self.rest)
     }
 }
 
@@ -69,29 +131,178 @@ impl ForceGen for Spring {
 
         let displacement = pa - pb;
         let distance = displacement.length();
-        if distance < 1e-6 {
+        let mut f_a = if distance >= 1e-6 {
+            let direction = displacement / distance;
+            let extension = distance - self.rest;
+            let axial = (va - vb).dot(direction);
+            direction * (-self.k * extension - self.c * axial)
+        } else if self.rest == 0.0 {
+            // Zero rest length: -k * displacement is well defined without a direction.
+            displacement * -self.k
+        } else {
             return;
+        };
+
+        if let Some(max_force) = self.max_force {
+            let magnitude = f_a.length();
+            if magnitude > max_force {
+                f_a = f_a * (max_force / magnitude);
+            }
+        }
+
+        if let SpringEnd::Entity(i) = self.a
+            && inv_ma > 0.0
+        {
+            let e = &mut world.entities[i];
+            *e.force_mut() = *e.force() + f_a;
+        }
+        if let SpringEnd::Entity(j) = self.b
+            && inv_mb > 0.0
+        {
+            let e = &mut world.entities[j];
+            *e.force_mut() = *e.force() - f_a;
         }
+    }
 
-        let direction = displacement / distance;
-        let extension = distance - self.rest;
-        let f_spring = direction * (-self.k * extension);
-        let v_rel = va - vb;
-        let axial = v_rel.dot(direction);
-        let f_damp = direction * (-self.c * axial);
-        let f_a = f_spring + f_damp;
-
-        if let SpringEnd::Entity(i) = self.a {
-            if inv_ma > 0.0 {
-                let e = &mut world.entities[i];
-                *e.force_mut() = *e.force() + f_a;
+    fn should_remove(&self, world: &World) -> bool {
+        match (self.max_strain, self.strain(world)) {
+            (Some(max), Some(strain)) => strain > max,
+            _ => false,
+        }
+    }
+
+    /// Elastic energy `1/2 * k * x^2` of the current extension `x`.
+    fn potential_energy(&self, world: &World) -> Scalar {
+        match (self.a.position(world), self.b.position(world)) {
+            (Some(pa), Some(pb)) => {
+                let x = (pa - pb).length() - self.rest;
+                0.5 * self.k * x * x
             }
+            _ => 0.0,
         }
-        if let SpringEnd::Entity(j) = self.b {
-            if inv_mb > 0.0 {
-                let e = &mut world.entities[j];
-                *e.force_mut() = *e.force() - f_a;
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        self.a.body_removed(index) && self.b.body_removed(index)
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
+
+/// Torsional spring pulling one entity's orientation toward `target_angle`.
+///
+/// Torque: `-k * error - c * omega`, where `error` is the shortest signed angle from the
+/// target to the current angle (in `[-π, π)`), so a body at 170° with target -170° is pulled
+/// 20° forward rather than 340° back.
+#[derive(Debug, Clone)]
+pub struct AngularSpring {
+    pub entity: usize,
+    pub target_angle: Scalar,
+    pub k: Scalar,
+    pub c: Scalar,
+}
+
+impl AngularSpring {
+    fn error(&self, angle: Scalar) -> Scalar {
+        shortest_angle(angle - self.target_angle)
+    }
+}
+
+impl ForceGen for AngularSpring {
+    fn apply(&self, world: &mut World) {
+        let Some(e) = world.entities.get_mut(self.entity) else {
+            return;
+        };
+        if e.inv_inertia() <= 0.0 {
+            return;
+        }
+        let torque = -self.k * self.error(e.angle()) - self.c * e.omega();
+        *e.torque_mut() += torque;
+    }
+
+    /// Elastic energy `1/2 * k * error^2`.
+    fn potential_energy(&self, world: &World) -> Scalar {
+        match world.entities.get(self.entity) {
+            Some(e) => {
+                let err = self.error(e.angle());
+                0.5 * self.k * err * err
             }
+            None => 0.0,
         }
     }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.entity, index)
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
+
+/// Torsion spring between two entities: drives `angle_b - angle_a` toward `rest_angle`.
+///
+/// Equal and opposite torques `±(-k * error - c * relative omega)`; the error is wrapped to
+/// the shortest path like `AngularSpring`. A static end (zero inverse inertia) receives no
+/// torque, so the spring then acts on the other body alone.
+#[derive(Debug, Clone)]
+pub struct RelativeAngularSpring {
+    pub a: usize,
+    pub b: usize,
+    pub rest_angle: Scalar,
+    pub k: Scalar,
+    pub c: Scalar,
+}
+
+impl RelativeAngularSpring {
+    fn error(&self, world: &World) -> Option<Scalar> {
+        let a = world.entities.get(self.a)?;
+        let b = world.entities.get(self.b)?;
+        Some(shortest_angle(b.angle() - a.angle() - self.rest_angle))
+    }
+}
+
+impl ForceGen for RelativeAngularSpring {
+    fn apply(&self, world: &mut World) {
+        if self.a == self.b {
+            return;
+        }
+        let Some(error) = self.error(world) else {
+            return;
+        };
+        let relative_omega = world.entities[self.b].omega() - world.entities[self.a].omega();
+        // Torque on b; a gets the reaction.
+        let torque = -self.k * error - self.c * relative_omega;
+
+        let b = &mut world.entities[self.b];
+        if b.inv_inertia() > 0.0 {
+            *b.torque_mut() += torque;
+        }
+        let a = &mut world.entities[self.a];
+        if a.inv_inertia() > 0.0 {
+            *a.torque_mut() -= torque;
+        }
+    }
+
+    /// Elastic energy `1/2 * k * error^2`.
+    fn potential_energy(&self, world: &World) -> Scalar {
+        self.error(world)
+            .map_or(0.0, |err| 0.5 * self.k * err * err)
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.a, index) && renumber_after_removal(&mut self.b, index)
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
+
+/// Wrap an angle difference into `[-π, π)`.
+fn shortest_angle(delta: Scalar) -> Scalar {
+    use crate::math::consts::{PI, TAU};
+    (delta + PI).rem_euclid(TAU) - PI
 }
@@ -0,0 +1,58 @@
+use super::ForceGen;
+use crate::core::World;
+use crate::core::world::renumber_after_removal;
+use crate::math::Scalar;
+
+/// A fixed torque on one entity, every step.
+///
+/// Like the linear forces it adds to the entity's accumulator, which `World::step` clears at
+/// the start of every step, so it composes with other torque sources.
+#[derive(Debug, Clone)]
+pub struct ConstantTorque {
+    pub entity: usize,
+    pub torque: Scalar,
+}
+
+impl ForceGen for ConstantTorque {
+    fn apply(&self, world: &mut World) {
+        if let Some(e) = world.entities.get_mut(self.entity) {
+            *e.torque_mut() += self.torque;
+        }
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.entity, index)
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
+
+/// Spins an entity up to `target_omega` and holds it there, like a motor-driven flywheel.
+///
+/// The torque is `gain * (target_omega - omega)`, limited to `max_torque` in either direction.
+#[derive(Debug, Clone)]
+pub struct Flywheel {
+    pub entity: usize,
+    pub target_omega: Scalar,
+    pub gain: Scalar,
+    pub max_torque: Scalar,
+}
+
+impl ForceGen for Flywheel {
+    fn apply(&self, world: &mut World) {
+        if let Some(e) = world.entities.get_mut(self.entity) {
+            let torque = self.gain * (self.target_omega - e.omega());
+            *e.torque_mut() += torque.clamp(-self.max_torque, self.max_torque);
+        }
+    }
+
+    fn body_removed(&mut self, index: usize) -> bool {
+        renumber_after_removal(&mut self.entity, index)
+    }
+
+    fn clone_box(&self) -> Box<dyn ForceGen> {
+        Box::new(self.clone())
+    }
+}
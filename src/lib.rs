@@ -1,6 +1,7 @@
 pub mod core;
 pub mod forces;
 pub mod math;
+pub mod prelude;
 
 #[cfg(feature = "visualize")]
 pub mod utils;
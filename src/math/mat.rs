@@ -1,15 +1,17 @@
+use super::Scalar;
 use super::vec::Vec2;
 
+/// Not `PartialEq`, like `Vec2`: compare with `approx_eq`.
 #[derive(Debug, Clone, Copy)]
 pub struct Mat2 {
-    pub m00: f32,
-    pub m01: f32,
-    pub m10: f32,
-    pub m11: f32,
+    pub m00: Scalar,
+    pub m01: Scalar,
+    pub m10: Scalar,
+    pub m11: Scalar,
 }
 
 impl Mat2 {
-    pub fn new(m00: f32, m01: f32, m10: f32, m11: f32) -> Self {
+    pub fn new(m00: Scalar, m01: Scalar, m10: Scalar, m11: Scalar) -> Self {
         Self { m00, m01, m10, m11 }
     }
 
@@ -17,12 +19,17 @@ impl Mat2 {
         Self::new(1.0, 0.0, 0.0, 1.0)
     }
 
-    pub fn rotation(radians: f32) -> Self {
+    pub fn rotation(radians: Scalar) -> Self {
         let c = radians.cos();
         let s = radians.sin();
         Self::new(c, -s, s, c)
     }
 
+    /// Angle of a pure rotation matrix (inverse of `rotation`).
+    pub fn angle(self) -> Scalar {
+        self.m10.atan2(self.m00)
+    }
+
     pub fn transpose(self) -> Self {
         Self::new(self.m00, self.m10, self.m01, self.m11)
     }
@@ -34,6 +41,18 @@ impl Mat2 {
         )
     }
 
+    /// Every entry within `eps` of `other`'s.
+    pub fn approx_eq(self, other: Self, eps: Scalar) -> bool {
+        [
+            self.m00 - other.m00,
+            self.m01 - other.m01,
+            self.m10 - other.m10,
+            self.m11 - other.m11,
+        ]
+        .iter()
+        .all(|d| d.abs() <= eps)
+    }
+
     pub fn mul_mat2(self, rhs: &Mat2) -> Mat2 {
         Mat2::new(
             self.m00 * rhs.m00 + self.m01 * rhs.m10,
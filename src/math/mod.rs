@@ -1,3 +1,63 @@
 pub mod mat;
 pub mod transform;
 pub mod vec;
+
+/// Floating-point type of the engine: `f32`, or `f64` with the `f64` feature (less drift in
+/// long scientific runs, at twice the memory).
+#[cfg(not(feature = "f64"))]
+pub type Scalar = f32;
+#[cfg(feature = "f64")]
+pub type Scalar = f64;
+
+/// `std::f32::consts` or `std::f64::consts`, matching `Scalar`.
+pub mod consts {
+    #[cfg(not(feature = "f64"))]
+    pub use std::f32::consts::*;
+    #[cfg(feature = "f64")]
+    pub use std::f64::consts::*;
+}
+
+/// Float-tolerant equality, for `assert_approx_eq!`. `Vec2` and `Mat2` compare every
+/// component (see their inherent `approx_eq`).
+pub trait ApproxEq: Copy {
+    fn approx_eq(self, other: Self, eps: Scalar) -> bool;
+}
+
+impl ApproxEq for Scalar {
+    fn approx_eq(self, other: Self, eps: Scalar) -> bool {
+        (self - other).abs() <= eps
+    }
+}
+
+impl ApproxEq for vec::Vec2 {
+    fn approx_eq(self, other: Self, eps: Scalar) -> bool {
+        vec::Vec2::approx_eq(self, other, eps)
+    }
+}
+
+impl ApproxEq for mat::Mat2 {
+    fn approx_eq(self, other: Self, eps: Scalar) -> bool {
+        mat::Mat2::approx_eq(self, other, eps)
+    }
+}
+
+/// Assert that two `Scalar`s, `Vec2`s or `Mat2`s are equal within `eps`, printing both if
+/// not. Like `assert!`, an optional format string and arguments after `eps` add a message.
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($a:expr, $b:expr, $eps:expr $(,)?) => {{
+        let (a, b, eps) = ($a, $b, $eps);
+        assert!(
+            $crate::math::ApproxEq::approx_eq(a, b, eps),
+            "{a:?} != {b:?} (eps {eps:?})"
+        );
+    }};
+    ($a:expr, $b:expr, $eps:expr, $($arg:tt)+) => {{
+        let (a, b, eps) = ($a, $b, $eps);
+        assert!(
+            $crate::math::ApproxEq::approx_eq(a, b, eps),
+            "{a:?} != {b:?} (eps {eps:?}): {}",
+            format_args!($($arg)+)
+        );
+    }};
+}
@@ -1,6 +1,8 @@
+use super::Scalar;
 use super::mat::Mat2;
 use super::vec::Vec2;
 
+#[derive(Debug, Clone, Copy)]
 pub struct Transform2D {
     pub rotation: Mat2,
     pub translation: Vec2,
@@ -22,10 +24,15 @@ impl Transform2D {
         Self::new(Mat2::identity(), translation)
     }
 
-    pub fn from_rotation(radians: f32) -> Self {
+    pub fn from_rotation(radians: Scalar) -> Self {
         Self::new(Mat2::rotation(radians), Vec2::zero())
     }
 
+    /// Pose of a body at `position`, rotated by `angle` radians.
+    pub fn from_pose(position: Vec2, angle: Scalar) -> Self {
+        Self::new(Mat2::rotation(angle), position)
+    }
+
     pub fn apply_to_point(&self, p: Vec2) -> Vec2 {
         self.rotation.mul_vec2(p) + self.translation
     }
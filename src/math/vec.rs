@@ -1,13 +1,17 @@
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
+use super::Scalar;
+
+/// Deliberately not `PartialEq`: computed vectors are almost never exactly equal, so compare
+/// them with `approx_eq` (or `assert_approx_eq!` in tests).
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Vec2 {
-    pub x: f32,
-    pub y: f32,
+    pub x: Scalar,
+    pub y: Scalar,
 }
 
 impl Vec2 {
-    pub fn new(x: f32, y: f32) -> Self {
+    pub fn new(x: Scalar, y: Scalar) -> Self {
         Self { x, y }
     }
 
@@ -19,11 +23,11 @@ impl Vec2 {
         Self { x: 1.0, y: 1.0 }
     }
 
-    pub fn length(self) -> f32 {
+    pub fn length(self) -> Scalar {
         (self.x * self.x + self.y * self.y).sqrt()
     }
 
-    pub fn length_squared(self) -> f32 {
+    pub fn length_squared(self) -> Scalar {
         self.x * self.x + self.y * self.y
     }
 
@@ -48,11 +52,11 @@ impl Vec2 {
         }
     }
 
-    pub fn dot(self, other: Self) -> f32 {
+    pub fn dot(self, other: Self) -> Scalar {
         self.x * other.x + self.y * other.y
     }
 
-    pub fn cross(self, other: Self) -> f32 {
+    pub fn cross(self, other: Self) -> Scalar {
         self.x * other.y - self.y * other.x
     }
 
@@ -62,6 +66,11 @@ impl Vec2 {
             y: self.x,
         }
     }
+
+    /// Both components within `eps` of `other`'s.
+    pub fn approx_eq(self, other: Self, eps: Scalar) -> bool {
+        (self.x - other.x).abs() <= eps && (self.y - other.y).abs() <= eps
+    }
 }
 
 impl Add for Vec2 {
@@ -84,9 +93,9 @@ impl Sub for Vec2 {
     }
 }
 
-impl Mul<f32> for Vec2 {
+impl Mul<Scalar> for Vec2 {
     type Output = Self;
-    fn mul(self, rhs: f32) -> Self {
+    fn mul(self, rhs: Scalar) -> Self {
         Self {
             x: self.x * rhs,
             y: self.y * rhs,
@@ -94,7 +103,7 @@ impl Mul<f32> for Vec2 {
     }
 }
 
-impl Mul<Vec2> for f32 {
+impl Mul<Vec2> for Scalar {
     type Output = Vec2;
     fn mul(self, rhs: Vec2) -> Vec2 {
         Vec2 {
@@ -104,9 +113,9 @@ impl Mul<Vec2> for f32 {
     }
 }
 
-impl Div<f32> for Vec2 {
+impl Div<Scalar> for Vec2 {
     type Output = Self;
-    fn div(self, rhs: f32) -> Self {
+    fn div(self, rhs: Scalar) -> Self {
         Self {
             x: self.x / rhs,
             y: self.y / rhs,
@@ -0,0 +1,14 @@
+//! The common types in one import: `use tiny_physics_engine::prelude::*;`
+//!
+//! Everything here is also reachable through its module path; less common types (joints,
+//! solver internals, individual force generators) are only there.
+
+pub use crate::core::{
+    Collider2D, Integrator, Material, Particle, PhysicalEntity, RigidBody, RigidBodyBuilder,
+    SolverParams, World,
+};
+pub use crate::forces::ForceGen;
+pub use crate::forces::spring::Spring;
+pub use crate::math::Scalar;
+pub use crate::math::mat::Mat2;
+pub use crate::math::vec::Vec2;
@@ -1,35 +1,148 @@
 use macroquad::prelude as mq;
 use std::any::Any;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
+use crate::core::collision::broad_phase;
 use crate::core::{Collider2D, Particle, PhysicalEntity, RigidBody, World};
 use crate::forces::{
     drag::LinearDrag,
     spring::{Spring, SpringEnd},
 };
+use crate::math::Scalar;
+use crate::math::transform::Transform2D;
 use crate::math::vec::Vec2;
 
 static SHOW_CONTACTS: AtomicBool = AtomicBool::new(false);
+static SHOW_BROAD_PHASE: AtomicBool = AtomicBool::new(false);
+static SHOW_VELOCITIES: AtomicBool = AtomicBool::new(false);
+static SHOW_FORCES: AtomicBool = AtomicBool::new(false);
+/// Index into `ColorMode::ALL`.
+static COLOR_MODE: AtomicU32 = AtomicU32::new(0);
+/// Arrow length per m/s and per N, as `f32` bits; see `set_vector_scales`.
+static VELOCITY_SCALE: AtomicU32 = AtomicU32::new(0.25f32.to_bits());
+static FORCE_SCALE: AtomicU32 = AtomicU32::new(0.05f32.to_bits());
 
-pub fn handle_debug_input() {
+/// A world-space `Scalar` as macroquad's `f32` (a no-op without the `f64` feature).
+#[allow(clippy::unnecessary_cast)]
+fn to_f32(x: Scalar) -> f32 {
+    x as f32
+}
+
+/// World-space arrow length per unit of velocity (m/s) and of force (N) for the `E` / `F`
+/// overlays.
+pub fn set_vector_scales(velocity: f32, force: f32) {
+    VELOCITY_SCALE.store(velocity.to_bits(), Ordering::Relaxed);
+    FORCE_SCALE.store(force.to_bits(), Ordering::Relaxed);
+}
+
+/// What part of the world is on screen: `center` sits in the middle of the window and one
+/// meter is `zoom` pixels. World y points up, screen y down. Screen coordinates are always
+/// `f32`; world ones are `Scalar`, so they're cast here and at the other draw calls.
+#[derive(Debug, Clone, Copy)]
+pub struct Camera {
+    pub center: Vec2,
+    pub zoom: f32,
+}
+
+impl Camera {
+    /// Pan speed of the keys, in screen pixels per second.
+    const PAN_SPEED: f32 = 600.0;
+    /// Zoom factor per mouse wheel notch.
+    const ZOOM_STEP: f32 = 1.1;
+
+    /// Centered on the world origin.
+    pub fn new(zoom: f32) -> Self {
+        Self {
+            center: Vec2::zero(),
+            zoom,
+        }
+    }
+
+    pub fn to_screen(&self, p: Vec2) -> (f32, f32) {
+        let d = (p - self.center) * self.zoom as Scalar;
+        (
+            mq::screen_width() * 0.5 + to_f32(d.x),
+            mq::screen_height() * 0.5 - to_f32(d.y),
+        )
+    }
+
+    pub fn to_world(&self, (sx, sy): (f32, f32)) -> Vec2 {
+        let d = Vec2::new(
+            (sx - mq::screen_width() * 0.5) as Scalar,
+            (mq::screen_height() * 0.5 - sy) as Scalar,
+        );
+        self.center + d / self.zoom as Scalar
+    }
+
+    /// WASD or the arrow keys pan, dragging with the right or middle mouse button pans, and
+    /// the wheel zooms about the cursor.
+    pub fn handle_input(&mut self) {
+        use mq::KeyCode;
+        let held = |keys: [KeyCode; 2]| keys.into_iter().any(mq::is_key_down);
+        let mut pan = Vec2::zero();
+        if held([KeyCode::A, KeyCode::Left]) {
+            pan.x -= 1.0;
+        }
+        if held([KeyCode::D, KeyCode::Right]) {
+            pan.x += 1.0;
+        }
+        if held([KeyCode::S, KeyCode::Down]) {
+            pan.y -= 1.0;
+        }
+        if held([KeyCode::W, KeyCode::Up]) {
+            pan.y += 1.0;
+        }
+        let pan_step = Self::PAN_SPEED * mq::get_frame_time() / self.zoom;
+        self.center = self.center + pan * pan_step as Scalar;
+
+        if mq::is_mouse_button_down(mq::MouseButton::Right)
+            || mq::is_mouse_button_down(mq::MouseButton::Middle)
+        {
+            let delta = mq::mouse_delta_position();
+            // `mouse_delta_position` is in normalized device units (2 per window extent).
+            let drag = Vec2::new(
+                (delta.x * mq::screen_width() * 0.5) as Scalar,
+                (-delta.y * mq::screen_height() * 0.5) as Scalar,
+            );
+            self.center = self.center + drag / self.zoom as Scalar;
+        }
+
+        let (_, wheel) = mq::mouse_wheel();
+        if wheel != 0.0 {
+            // Keep the point under the cursor in place.
+            let cursor = mq::mouse_position();
+            let before = self.to_world(cursor);
+            self.zoom *= Self::ZOOM_STEP.powf(wheel.signum());
+            self.center = self.center + before - self.to_world(cursor);
+        }
+    }
+}
+
+pub fn handle_debug_input(camera: &mut Camera) {
+    camera.handle_input();
     if mq::is_key_pressed(mq::KeyCode::V) {
         SHOW_CONTACTS.fetch_xor(true, Ordering::Relaxed);
     }
+    if mq::is_key_pressed(mq::KeyCode::B) {
+        SHOW_BROAD_PHASE.fetch_xor(true, Ordering::Relaxed);
+    }
+    if mq::is_key_pressed(mq::KeyCode::E) {
+        SHOW_VELOCITIES.fetch_xor(true, Ordering::Relaxed);
+    }
+    if mq::is_key_pressed(mq::KeyCode::F) {
+        SHOW_FORCES.fetch_xor(true, Ordering::Relaxed);
+    }
+    if mq::is_key_pressed(mq::KeyCode::C) {
+        ColorMode::current().next().set();
+    }
 }
 
 pub trait Drawable {
-    fn draw(&self, _world: &World, _scale: f32) {}
+    fn draw(&self, _world: &World, _camera: &Camera) {}
 }
 
-fn to_screen(p: Vec2, scale: f32) -> (f32, f32) {
-    let cx = mq::screen_width() * 0.5;
-    let cy = mq::screen_height() * 0.5;
-    (cx + p.x * scale, cy - p.y * scale)
-}
-
-pub fn draw_axes_and_ground() {
-    let cx = mq::screen_width() * 0.5;
-    let cy = mq::screen_height() * 0.5;
+pub fn draw_axes_and_ground(camera: &Camera) {
+    let (cx, cy) = camera.to_screen(Vec2::zero());
     mq::draw_line(0.0, cy, mq::screen_width(), cy, 1.0, mq::GRAY);
     mq::draw_line(cx, 0.0, cx, mq::screen_height(), 1.0, mq::GRAY);
     mq::draw_line(
@@ -42,118 +155,262 @@ pub fn draw_axes_and_ground() {
     );
 }
 
-pub fn draw_particles(world: &World, scale: f32) {
+pub fn draw_particles(world: &World, camera: &Camera) {
     for e in &world.entities {
-        let (sx, sy) = to_screen(*e.pos(), scale);
+        let (sx, sy) = camera.to_screen(*e.pos());
         mq::draw_circle(sx, sy, 6.0, mq::YELLOW);
     }
 }
 
-fn draw_forces(world: &World, scale: f32) {
-    for g in &world.forces {
-        let any: &dyn Any = g.as_ref();
+fn draw_forces(world: &World, camera: &Camera) {
+    for f in &world.forces {
+        let any: &dyn Any = f.generator.as_ref();
         if let Some(s) = any.downcast_ref::<Spring>() {
-            s.draw(world, scale);
+            s.draw(world, camera);
         } else if let Some(s) = any.downcast_ref::<LinearDrag>() {
-            s.draw(world, scale);
+            s.draw(world, camera);
         }
     }
 }
 
-fn draw_collider_at(pos: Vec2, angle: f32, collider: &Collider2D, scale: f32) {
+fn draw_collider_at(xf: &Transform2D, collider: &Collider2D, color: mq::Color, camera: &Camera) {
+    let pos = xf.translation;
     match collider {
         Collider2D::Circle { radius } => {
-            let (sx, sy) = to_screen(pos, scale);
-            mq::draw_circle_lines(sx, sy, radius * scale, 2.0, mq::YELLOW);
-            let dir = Vec2::new(angle.cos(), angle.sin());
+            let (sx, sy) = camera.to_screen(pos);
+            mq::draw_circle_lines(sx, sy, to_f32(*radius) * camera.zoom, 2.0, color);
+            let dir = xf.rotation.mul_vec2(Vec2::new(1.0, 0.0));
             let tip = pos + dir * *radius;
-            let (tx, ty) = to_screen(tip, scale);
+            let (tx, ty) = camera.to_screen(tip);
             mq::draw_line(sx, sy, tx, ty, 2.0, mq::ORANGE);
         }
-        Collider2D::Box { half_extents } => {
-            let (cx, cy) = to_screen(pos, scale);
-            let hw = half_extents.x * scale;
-            let hh = half_extents.y * scale;
-            let corners = [(-hw, -hh), (hw, -hh), (hw, hh), (-hw, hh)];
-            let c = angle.cos();
-            let s = angle.sin();
-            let pts: Vec<_> = corners
-                .iter()
-                .map(|&(x, y)| (cx + x * c - y * s, cy - (x * s + y * c)))
+        Collider2D::Box { .. } | Collider2D::RoundedBox { .. } => {
+            let pts: Vec<_> = collider
+                .world_vertices(xf)
+                .into_iter()
+                .map(|p| camera.to_screen(p))
                 .collect();
-            for i in 0..4 {
+            for i in 0..pts.len() {
                 let (x0, y0) = pts[i];
-                let (x1, y1) = pts[(i + 1) % 4];
-                mq::draw_line(x0, y0, x1, y1, 2.0, mq::YELLOW);
+                let (x1, y1) = pts[(i + 1) % pts.len()];
+                mq::draw_line(x0, y0, x1, y1, 2.0, color);
+            }
+        }
+        Collider2D::Segment { a, b } => {
+            let (x0, y0) = camera.to_screen(xf.apply_to_point(*a));
+            let (x1, y1) = camera.to_screen(xf.apply_to_point(*b));
+            mq::draw_line(x0, y0, x1, y1, 2.0, color);
+        }
+        Collider2D::Chain { .. } => {
+            let pts: Vec<_> = collider
+                .world_vertices(xf)
+                .into_iter()
+                .map(|p| camera.to_screen(p))
+                .collect();
+            for pair in pts.windows(2) {
+                let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+                mq::draw_line(x0, y0, x1, y1, 2.0, color);
+            }
+        }
+        Collider2D::Compound { parts } => {
+            for (local, part) in parts {
+                draw_collider_at(&local.then(xf), part, color, camera);
             }
         }
     }
 }
 
-fn draw_entities(world: &World, scale: f32) {
-    for e in &world.entities {
+fn draw_entities(world: &World, camera: &Camera) {
+    let mode = ColorMode::current();
+    for (i, e) in world.entities.iter().enumerate() {
+        if let Some(color) = mode.tint(world, i) {
+            match e.collider() {
+                Some(col) => draw_collider_at(&e.transform(), col, color, camera),
+                None => {
+                    let (sx, sy) = camera.to_screen(*e.pos());
+                    mq::draw_circle(sx, sy, 6.0, color);
+                }
+            }
+            continue;
+        }
         let any: &dyn Any = e.as_ref();
         if let Some(rb) = any.downcast_ref::<RigidBody>() {
-            rb.draw(world, scale);
+            rb.draw(world, camera);
         } else if let Some(p) = any.downcast_ref::<Particle>() {
-            p.draw(world, scale);
+            p.draw(world, camera);
         } else {
-            let (sx, sy) = to_screen(*e.pos(), scale);
+            let (sx, sy) = camera.to_screen(*e.pos());
             mq::draw_circle(sx, sy, 5.0, mq::WHITE);
         }
     }
 }
 
-pub fn draw_world(world: &World, scale: f32) {
-    handle_debug_input();
+/// How `draw_world` colors bodies; `C` cycles through the modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Each entity type's own `Drawable` colors.
+    Plain,
+    /// One color per island (`World::island_of`); static bodies gray.
+    Island,
+    /// Blue at rest through red at `HEATMAP_MAX_SPEED` and above.
+    Speed,
+}
+
+/// Speed (m/s) that `ColorMode::Speed` draws fully red.
+const HEATMAP_MAX_SPEED: f32 = 10.0;
+
+impl ColorMode {
+    const ALL: [ColorMode; 3] = [ColorMode::Plain, ColorMode::Island, ColorMode::Speed];
+
+    pub fn current() -> Self {
+        Self::ALL[COLOR_MODE.load(Ordering::Relaxed) as usize % Self::ALL.len()]
+    }
+
+    pub fn set(self) {
+        COLOR_MODE.store(self as u32, Ordering::Relaxed);
+    }
+
+    fn next(self) -> Self {
+        Self::ALL[(self as usize + 1) % Self::ALL.len()]
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            ColorMode::Plain => "plain",
+            ColorMode::Island => "island",
+            ColorMode::Speed => "speed",
+        }
+    }
+
+    /// Color of entity `i`, or `None` to let its `Drawable` impl decide.
+    fn tint(self, world: &World, i: usize) -> Option<mq::Color> {
+        match self {
+            ColorMode::Plain => None,
+            ColorMode::Island => Some(match world.island_of(i) {
+                // Golden-ratio hue steps keep neighbouring labels apart.
+                Some(island) => hsv((island as f32 * 0.618_034).fract(), 0.7, 1.0),
+                None => mq::GRAY,
+            }),
+            ColorMode::Speed => {
+                let speed = to_f32(world.entities[i].vel().length());
+                let t = (speed / HEATMAP_MAX_SPEED).min(1.0);
+                Some(hsv(0.66 * (1.0 - t), 0.8, 1.0))
+            }
+        }
+    }
+}
+
+/// HSV to RGB with the hue in `[0, 1)`.
+fn hsv(h: f32, s: f32, v: f32) -> mq::Color {
+    let k = |n: f32| {
+        let k = (n + h * 6.0) % 6.0;
+        v - v * s * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+    mq::Color::new(k(5.0), k(3.0), k(1.0), 1.0)
+}
+
+pub fn draw_world(world: &World, camera: &mut Camera) {
+    handle_debug_input(camera);
+    let camera = &*camera;
     mq::clear_background(mq::Color::from_rgba(18, 18, 24, 255));
-    draw_axes_and_ground();
-    draw_forces(world, scale);
-    draw_entities(world, scale);
+    draw_axes_and_ground(camera);
+    draw_forces(world, camera);
+    draw_entities(world, camera);
+    if SHOW_BROAD_PHASE.load(Ordering::Relaxed) {
+        draw_broad_phase(world, camera);
+    }
+    if SHOW_VELOCITIES.load(Ordering::Relaxed) {
+        let factor = f32::from_bits(VELOCITY_SCALE.load(Ordering::Relaxed)) as Scalar;
+        draw_vectors(world, camera, |e| *e.vel() * factor, mq::LIME);
+    }
+    if SHOW_FORCES.load(Ordering::Relaxed) {
+        // `step` leaves the last step's accumulated force in place.
+        let factor = f32::from_bits(FORCE_SCALE.load(Ordering::Relaxed)) as Scalar;
+        draw_vectors(world, camera, |e| *e.force() * factor, mq::MAGENTA);
+    }
     if SHOW_CONTACTS.load(Ordering::Relaxed) {
-        draw_contacts(world, scale);
+        draw_contacts(world, camera);
     }
     draw_hud(world);
 }
 
-fn draw_contacts(world: &World, scale: f32) {
-    for manifold in &world.manifolds {
-        let normal = manifold.normal;
-        for cp in &manifold.points {
-            let (sx, sy) = to_screen(cp.point, scale);
-            mq::draw_circle(sx, sy, 5.0, mq::RED);
-            let tip = cp.point + normal * 0.3;
-            let (tx, ty) = to_screen(tip, scale);
-            mq::draw_line(sx, sy, tx, ty, 2.0, mq::GREEN);
-            mq::draw_text(
-                &format!("{:.3}", cp.penetration),
-                sx + 8.0,
-                sy - 8.0,
-                16.0,
-                mq::WHITE,
-            );
+/// Fat AABBs (as the broad phase sees them) and a faint line per candidate pair.
+fn draw_broad_phase(world: &World, camera: &Camera) {
+    let aabbs: Vec<_> = world
+        .entities
+        .iter()
+        .map(|e| broad_phase::fat_aabb(&**e, world.params, world.dt()))
+        .collect();
+    for (e, aabb) in world.entities.iter().zip(&aabbs) {
+        if e.collider().is_none() {
+            continue;
+        }
+        let (x0, y0) = camera.to_screen(Vec2::new(aabb.min.x, aabb.max.y));
+        let (x1, y1) = camera.to_screen(Vec2::new(aabb.max.x, aabb.min.y));
+        mq::draw_rectangle_lines(x0, y0, x1 - x0, y1 - y0, 1.0, mq::SKYBLUE);
+    }
+    let faint = mq::Color::new(0.4, 0.8, 1.0, 0.35);
+    for &(i, j) in &world.pairs {
+        let (x0, y0) = camera.to_screen(aabbs[i].center());
+        let (x1, y1) = camera.to_screen(aabbs[j].center());
+        mq::draw_line(x0, y0, x1, y1, 1.0, faint);
+    }
+}
+
+/// An arrow from every movable body's center, `vector` long in world units.
+fn draw_vectors(
+    world: &World,
+    camera: &Camera,
+    vector: impl Fn(&dyn PhysicalEntity) -> Vec2,
+    color: mq::Color,
+) {
+    for e in world.entities.iter().filter(|e| e.inv_mass() > 0.0) {
+        let v = vector(&**e);
+        if v.length_squared() < 1e-8 {
+            continue;
         }
+        let (x0, y0) = camera.to_screen(*e.pos());
+        let (x1, y1) = camera.to_screen(*e.pos() + v);
+        mq::draw_line(x0, y0, x1, y1, 2.0, color);
+        // Arrow head: two short strokes back from the tip.
+        let (dx, dy) = (x1 - x0, y1 - y0);
+        let len = (dx * dx + dy * dy).sqrt();
+        let head = 8.0f32.min(0.5 * len);
+        let (ux, uy) = (dx / len * head, dy / len * head);
+        mq::draw_line(x1, y1, x1 - ux - 0.5 * uy, y1 - uy + 0.5 * ux, 2.0, color);
+        mq::draw_line(x1, y1, x1 - ux + 0.5 * uy, y1 - uy - 0.5 * ux, 2.0, color);
+    }
+}
+
+fn draw_contacts(world: &World, camera: &Camera) {
+    for contact in world.contacts() {
+        let (sx, sy) = camera.to_screen(contact.point);
+        mq::draw_circle(sx, sy, 5.0, mq::RED);
+        let tip = contact.point + contact.normal * 0.3;
+        let (tx, ty) = camera.to_screen(tip);
+        mq::draw_line(sx, sy, tx, ty, 2.0, mq::GREEN);
+        mq::draw_text(
+            &format!("{:.3}", contact.penetration),
+            sx + 8.0,
+            sy - 8.0,
+            16.0,
+            mq::WHITE,
+        );
     }
 }
 
 impl Drawable for Spring {
-    fn draw(&self, world: &World, scale: f32) {
-        let p_of = |end: &SpringEnd| -> Option<Vec2> {
-            match end {
-                SpringEnd::Entity(i) => world.entities.get(*i).map(|e| *e.pos()),
-                SpringEnd::Anchor(p) => Some(*p),
-            }
-        };
-        if let (Some(pa), Some(pb)) = (p_of(&self.a), p_of(&self.b)) {
-            let (x0, y0) = to_screen(pa, scale);
-            let (x1, y1) = to_screen(pb, scale);
+    fn draw(&self, world: &World, camera: &Camera) {
+        if let (Some(pa), Some(pb)) = (self.a.position(world), self.b.position(world)) {
+            let (x0, y0) = camera.to_screen(pa);
+            let (x1, y1) = camera.to_screen(pb);
             mq::draw_line(x0, y0, x1, y1, 2.0, mq::ORANGE);
             if let SpringEnd::Anchor(p) = self.a {
-                let (ax, ay) = to_screen(p, scale);
+                let (ax, ay) = camera.to_screen(p);
                 mq::draw_circle(ax, ay, 4.0, mq::RED);
             }
             if let SpringEnd::Anchor(p) = self.b {
-                let (ax, ay) = to_screen(p, scale);
+                let (ax, ay) = camera.to_screen(p);
                 mq::draw_circle(ax, ay, 4.0, mq::RED);
             }
         }
@@ -163,33 +420,9 @@ impl Drawable for Spring {
 impl Drawable for LinearDrag {}
 
 fn draw_hud(world: &World) {
-    let (mut kinetic, mut px, mut py) = (0.0f32, 0.0f32, 0.0f32);
-    for e in &world.entities {
-        let inv_m = e.inv_mass();
-        if inv_m > 0.0 {
-            let m = 1.0 / inv_m;
-            let v = e.vel();
-            kinetic += 0.5 * m * v.length_squared();
-            px += m * v.x;
-            py += m * v.y;
-        }
-    }
-
-    let mut potential = 0.0f32;
-    for g in &world.forces {
-        if let Some(s) = (g.as_ref() as &dyn Any).downcast_ref::<Spring>() {
-            let p_of = |end: &SpringEnd| -> Option<Vec2> {
-                match end {
-                    SpringEnd::Entity(i) => world.entities.get(*i).map(|e| *e.pos()),
-                    SpringEnd::Anchor(p) => Some(*p),
-                }
-            };
-            if let (Some(pa), Some(pb)) = (p_of(&s.a), p_of(&s.b)) {
-                let x = (pa - pb).length() - s.rest;
-                potential += 0.5 * s.k * x * x;
-            }
-        }
-    }
+    let kinetic = world.total_kinetic_energy();
+    let momentum = world.total_momentum();
+    let potential = world.spring_potential_energy();
 
     let contact_count: usize = world.manifolds.iter().map(|m| m.points.len()).sum();
 
@@ -206,16 +439,17 @@ fn draw_hud(world: &World) {
 
         let normal = manifold.normal;
         for cp in &manifold.points {
-            if cp.penetration > max_penetration {
-                max_penetration = cp.penetration;
+            let penetration = to_f32(cp.penetration);
+            if penetration > max_penetration {
+                max_penetration = penetration;
             }
 
-            let r_a = cp.point - *a.pos();
-            let r_b = cp.point - *b.pos();
+            let r_a = cp.point - a.world_center_of_mass();
+            let r_b = cp.point - b.world_center_of_mass();
             let va = *a.vel() + Vec2::new(-a.omega() * r_a.y, a.omega() * r_a.x);
             let vb = *b.vel() + Vec2::new(-b.omega() * r_b.y, b.omega() * r_b.x);
             let vn = (vb - va).dot(normal);
-            let abs_vn = vn.abs();
+            let abs_vn = to_f32(vn.abs());
             if abs_vn > max_abs_vn {
                 max_abs_vn = abs_vn;
             }
@@ -223,46 +457,54 @@ fn draw_hud(world: &World) {
     }
 
     let constraints = world.solver.constraints.len();
-    let iterations = world.solver.iterations;
-    let debug = if SHOW_CONTACTS.load(Ordering::Relaxed) {
-        "ON"
-    } else {
-        "OFF"
+    let iterations = world.solver.params.velocity_iterations;
+    let residual = world.solver.last_residual();
+    let on_off = |flag: &AtomicBool| {
+        if flag.load(Ordering::Relaxed) {
+            "ON"
+        } else {
+            "OFF"
+        }
     };
 
     // Fixed width + fixed decimals to avoid text "jumping" as numbers change.
     let text = format!(
-        "K={:>9.3}  U={:>9.3}  E={:>9.3}  P=({:>8.3},{:>8.3})  pen={:>6.3}  |vn|={:>7.3}  N={:>3}  cp={:>3}  con={:>3}  it={:>2}  [V]Debug:{}",
+        "K={:>9.3}  U={:>9.3}  E={:>9.3}  P=({:>8.3},{:>8.3})  pen={:>6.3}  |vn|={:>7.3}  N={:>3}  cp={:>3}  con={:>3}  it={:>2}  res={:>9.2e}  [V]Debug:{}  [B]Broad:{}  [E]Vel:{}  [F]Force:{}  [C]Color:{}",
         kinetic,
         potential,
         kinetic + potential,
-        px,
-        py,
+        momentum.x,
+        momentum.y,
         max_penetration,
         max_abs_vn,
         world.entities.len(),
         contact_count,
         constraints,
         iterations,
-        debug
+        residual,
+        on_off(&SHOW_CONTACTS),
+        on_off(&SHOW_BROAD_PHASE),
+        on_off(&SHOW_VELOCITIES),
+        on_off(&SHOW_FORCES),
+        ColorMode::current().name()
     );
     mq::draw_text(&text, 16.0, 24.0, 22.0, mq::WHITE);
 }
 
 impl Drawable for RigidBody {
-    fn draw(&self, _world: &World, scale: f32) {
+    fn draw(&self, _world: &World, camera: &Camera) {
         if let Some(col) = &self.collider {
-            draw_collider_at(self.pos, self.angle, col, scale);
+            draw_collider_at(&self.transform(), col, mq::YELLOW, camera);
         } else {
-            let (sx, sy) = to_screen(self.pos, scale);
+            let (sx, sy) = camera.to_screen(self.pos);
             mq::draw_circle(sx, sy, 6.0, mq::YELLOW);
         }
     }
 }
 
 impl Drawable for Particle {
-    fn draw(&self, _world: &World, scale: f32) {
-        let (sx, sy) = to_screen(*self.pos(), scale);
+    fn draw(&self, _world: &World, camera: &Camera) {
+        let (sx, sy) = camera.to_screen(*self.pos());
         mq::draw_circle(sx, sy, 6.0, mq::YELLOW);
     }
 }
@@ -0,0 +1,82 @@
+//! `Aabb::merge` and `Aabb::contains_point`.
+
+use tiny_physics_engine::core::Aabb;
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+fn aabb(min: (Scalar, Scalar), max: (Scalar, Scalar)) -> Aabb {
+    Aabb::new(Vec2::new(min.0, min.1), Vec2::new(max.0, max.1))
+}
+
+fn assert_bounds(actual: Aabb, expected: Aabb) {
+    assert!(
+        actual.min.x == expected.min.x
+            && actual.min.y == expected.min.y
+            && actual.max.x == expected.max.x
+            && actual.max.y == expected.max.y,
+        "{actual:?} != {expected:?}"
+    );
+}
+
+#[test]
+fn merge_of_disjoint_boxes_spans_both() {
+    let a = aabb((-3.0, -1.0), (-2.0, 0.0));
+    let b = aabb((1.0, 2.0), (4.0, 5.0));
+    let union = aabb((-3.0, -1.0), (4.0, 5.0));
+    assert_bounds(a.merge(&b), union);
+    assert_bounds(b.merge(&a), union);
+}
+
+#[test]
+fn merge_with_a_nested_box_is_the_outer_box() {
+    let outer = aabb((-2.0, -2.0), (2.0, 2.0));
+    let inner = aabb((-1.0, 0.0), (0.5, 1.5));
+    assert_bounds(outer.merge(&inner), outer);
+    assert_bounds(inner.merge(&outer), outer);
+    assert_bounds(outer.merge(&outer), outer);
+}
+
+#[test]
+fn merge_of_touching_boxes_is_tight() {
+    // Sharing the edge x = 1.
+    let left = aabb((0.0, 0.0), (1.0, 1.0));
+    let right = aabb((1.0, 0.5), (2.0, 3.0));
+    assert_bounds(left.merge(&right), aabb((0.0, 0.0), (2.0, 3.0)));
+    // Touching at a single corner.
+    let corner = aabb((2.0, 3.0), (4.0, 4.0));
+    assert_bounds(right.merge(&corner), aabb((1.0, 0.5), (4.0, 4.0)));
+}
+
+#[test]
+fn contains_point_includes_the_boundary() {
+    let b = aabb((-1.0, -2.0), (3.0, 4.0));
+    // Inside, on each edge and on each corner.
+    for (x, y) in [
+        (0.0, 0.0),
+        (-1.0, 1.0),
+        (3.0, 1.0),
+        (1.0, -2.0),
+        (1.0, 4.0),
+        (-1.0, -2.0),
+        (3.0, -2.0),
+        (3.0, 4.0),
+        (-1.0, 4.0),
+    ] {
+        assert!(b.contains_point(Vec2::new(x, y)), "({x}, {y}) not inside");
+    }
+}
+
+#[test]
+fn contains_point_rejects_points_just_outside() {
+    let b = aabb((-1.0, -2.0), (3.0, 4.0));
+    let eps = 1e-4;
+    for (x, y) in [
+        (-1.0 - eps, 0.0),
+        (3.0 + eps, 0.0),
+        (0.0, -2.0 - eps),
+        (0.0, 4.0 + eps),
+        (3.0 + eps, 4.0 + eps),
+    ] {
+        assert!(!b.contains_point(Vec2::new(x, y)), "({x}, {y}) inside");
+    }
+}
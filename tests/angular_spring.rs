@@ -0,0 +1,170 @@
+//! Torsion springs: `AngularSpring` toward a fixed angle and `RelativeAngularSpring`
+//! between two bodies.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::forces::spring::{AngularSpring, RelativeAngularSpring};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A 1 kg disc of radius 0.5 (inertia 0.125) at `angle`, no gravity.
+fn disc_world(angle: Scalar) -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    world.add(Box::new(
+        RigidBodyBuilder::new().angle(angle).circle(0.5).build(),
+    ));
+    world
+}
+
+#[test]
+fn pulls_the_short_way_across_the_wrap() {
+    let mut world = disc_world(Scalar::to_radians(170.0));
+    world.add_force(Box::new(AngularSpring {
+        entity: 0,
+        target_angle: Scalar::to_radians(-170.0),
+        k: 2.0,
+        c: 0.0,
+    }));
+    // 20 degrees short of the target going forward, not 340 degrees past it.
+    let twenty = Scalar::to_radians(20.0);
+    let energy = world.spring_potential_energy();
+    assert_approx_eq!(energy, twenty * twenty, 1e-5, "energy {energy}");
+    world.step(DT);
+    let torque = world.entities[0].torque();
+    assert_approx_eq!(torque, 2.0 * twenty, 1e-5, "torque {torque}");
+
+    // It turns forward through 180 degrees and comes to rest at the target.
+    world.forces.clear();
+    world.add_force(Box::new(AngularSpring {
+        entity: 0,
+        target_angle: Scalar::to_radians(-170.0),
+        k: 2.0,
+        c: 0.5,
+    }));
+    for _ in 0..600 {
+        world.step(DT);
+    }
+    let angle = world.entities[0].angle();
+    assert_approx_eq!(
+        angle,
+        Scalar::to_radians(190.0),
+        1e-3,
+        "ended at {} degrees",
+        angle.to_degrees()
+    );
+}
+
+#[test]
+fn damping_opposes_the_spin() {
+    let mut world = disc_world(0.0);
+    *world.entities[0].omega_mut() = 3.0;
+    world.add_force(Box::new(AngularSpring {
+        entity: 0,
+        target_angle: 0.0,
+        k: 0.0,
+        c: 0.1,
+    }));
+    world.step(DT);
+    // No error yet: the torque is all damping, -c * omega.
+    let torque = world.entities[0].torque();
+    assert_approx_eq!(torque, -0.3, 1e-5, "torque {torque}");
+
+    // A damped spring settles; an undamped one keeps swinging.
+    let peak_after = |c: Scalar| {
+        let mut world = disc_world(1.0);
+        world.add_force(Box::new(AngularSpring {
+            entity: 0,
+            target_angle: 0.0,
+            k: 1.0,
+            c,
+        }));
+        for _ in 0..600 {
+            world.step(DT);
+        }
+        let mut peak: Scalar = 0.0;
+        for _ in 0..120 {
+            world.step(DT);
+            peak = peak.max(world.entities[0].angle().abs());
+        }
+        peak
+    };
+    let (undamped, damped) = (peak_after(0.0), peak_after(0.2));
+    assert!(undamped > 0.9, "undamped swing {undamped}");
+    assert!(damped < 0.01, "damped swing {damped}");
+}
+
+/// Two discs at `angle_a` and `angle_b` (the first one static if `static_a`), joined by a
+/// torsion spring with rest angle 0, `k = 2`, `c = 0.5`.
+fn torsion_pair(angle_a: Scalar, angle_b: Scalar, static_a: bool) -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    let mass = if static_a { 0.0 } else { 1.0 };
+    let a = RigidBodyBuilder::new()
+        .angle(angle_a)
+        .mass(mass)
+        .circle(0.5);
+    world.add(Box::new(a.build()));
+    let b = RigidBodyBuilder::new()
+        .position(Vec2::new(2.0, 0.0))
+        .angle(angle_b)
+        .circle(0.5);
+    world.add(Box::new(b.build()));
+    world.add_force(Box::new(RelativeAngularSpring {
+        a: 0,
+        b: 1,
+        rest_angle: 0.0,
+        k: 2.0,
+        c: 0.5,
+    }));
+    world
+}
+
+#[test]
+fn torsion_torques_are_equal_and_opposite() {
+    let mut world = torsion_pair(0.0, 0.5, false);
+    *world.entities[1].omega_mut() = 1.0;
+    world.step(DT);
+    let (ta, tb) = (world.entities[0].torque(), world.entities[1].torque());
+    // -k * 0.5 - c * 1.0 on b, the reaction on a.
+    assert_approx_eq!(tb, -1.5, 1e-5, "torque on b {tb}");
+    assert_eq!(ta, -tb);
+
+    // Angular momentum stays at what b started with while the two settle together.
+    for _ in 0..600 {
+        world.step(DT);
+    }
+    let (a, b) = (&world.entities[0], &world.entities[1]);
+    let momentum = 0.125 * (a.omega() + b.omega());
+    assert_approx_eq!(momentum, 0.125, 1e-4, "angular momentum {momentum}");
+    assert_approx_eq!(b.angle(), a.angle(), 1e-3);
+}
+
+#[test]
+fn torsion_error_takes_the_short_way() {
+    // b is 340 degrees behind a, i.e. 20 degrees ahead of it: pushed back by 20 degrees.
+    let mut world = torsion_pair(Scalar::to_radians(170.0), Scalar::to_radians(-170.0), false);
+    let twenty = Scalar::to_radians(20.0);
+    let energy = world.spring_potential_energy();
+    assert_approx_eq!(energy, twenty * twenty, 1e-5, "energy {energy}");
+    world.step(DT);
+    let tb = world.entities[1].torque();
+    assert_approx_eq!(tb, -2.0 * twenty, 1e-5, "torque on b {tb}");
+}
+
+#[test]
+fn static_end_gets_no_torque() {
+    let mut world = torsion_pair(0.0, 0.5, true);
+    world.step(DT);
+    assert_eq!(world.entities[0].torque(), 0.0);
+    assert_eq!(world.entities[0].omega(), 0.0);
+    assert!(world.entities[1].torque() < 0.0);
+
+    // b alone is pulled back to the static disc's angle.
+    for _ in 0..600 {
+        world.step(DT);
+    }
+    assert_eq!(world.entities[0].angle(), 0.0);
+    let angle = world.entities[1].angle();
+    assert_approx_eq!(angle, 0.0, 1e-3, "b at {angle}");
+}
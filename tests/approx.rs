@@ -0,0 +1,52 @@
+//! Float-tolerant comparison of scalars, `Vec2` and `Mat2`.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::mat::Mat2;
+use tiny_physics_engine::math::vec::Vec2;
+
+#[test]
+fn vec2_approx_eq_is_per_component() {
+    let v = Vec2::new(1.0, -2.0);
+    assert!(v.approx_eq(Vec2::new(1.0005, -2.0005), 1e-3));
+    assert!(!v.approx_eq(Vec2::new(1.0, -2.01), 1e-3));
+    assert!(!v.approx_eq(Vec2::new(1.01, -2.0), 1e-3));
+    // NaN is never close to anything.
+    assert!(!v.approx_eq(Vec2::new(Scalar::NAN, -2.0), 1.0));
+}
+
+#[test]
+fn composed_rotations_match_the_summed_angle() {
+    let (a, b) = (0.7, -2.1);
+    assert_approx_eq!(
+        Mat2::rotation(a).mul_mat2(&Mat2::rotation(b)),
+        Mat2::rotation(a + b),
+        1e-6
+    );
+    assert!(!Mat2::rotation(a).approx_eq(Mat2::rotation(b), 1e-3));
+    assert_approx_eq!(
+        Mat2::rotation(a).mul_vec2(Vec2::new(1.0, 0.0)),
+        Vec2::new(a.cos(), a.sin()),
+        1e-6
+    );
+}
+
+#[test]
+fn assert_approx_eq_takes_scalars() {
+    assert_approx_eq!(0.1 + 0.2, 0.3, 1e-6);
+    let two: Scalar = 2.0;
+    assert_approx_eq!(two.sqrt() * two.sqrt(), 2.0, 1e-6);
+}
+
+#[test]
+#[should_panic(expected = "!=")]
+fn assert_approx_eq_reports_a_mismatch() {
+    assert_approx_eq!(Vec2::new(0.0, 0.0), Vec2::new(0.0, 1.0), 1e-3);
+}
+
+#[test]
+#[should_panic(expected = "0.0 != 1.0 (eps 0.001): step 3 of 5")]
+fn assert_approx_eq_adds_a_message() {
+    let (step, steps) = (3, 5);
+    assert_approx_eq!(0.0, 1.0, 1e-3, "step {step} of {}", steps);
+}
@@ -0,0 +1,90 @@
+//! Springs with a strain limit snap and are removed by `World::step`.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, Particle, World};
+use tiny_physics_engine::forces::spring::Spring;
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 120.0;
+const LINKS: usize = 10;
+const SPACING: Scalar = 0.5;
+
+/// Link of the bridge that gives first.
+const FRAYED: usize = 3;
+
+/// A rope bridge of `LINKS` springs between two static end particles. Entity `i` is the
+/// `i`-th particle from the left, spring `i` links particles `i` and `i + 1`. Link `FRAYED`
+/// snaps at 0.8 times `max_strain`.
+fn rope_bridge(max_strain: Scalar) -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    for i in 0..=LINKS {
+        let inv_mass = if i == 0 || i == LINKS { 0.0 } else { 10.0 };
+        let pos = Vec2::new(i as Scalar * SPACING, 0.0);
+        world.add(Box::new(Particle::new(pos, Vec2::zero(), inv_mass)));
+    }
+    for i in 0..LINKS {
+        let limit = if i == FRAYED {
+            0.8 * max_strain
+        } else {
+            max_strain
+        };
+        let spring = Spring::between(i, i + 1, 500.0, 2.0, SPACING).with_max_strain(limit);
+        world.add_force(Box::new(spring));
+    }
+    world
+}
+
+#[test]
+fn rope_bridge_holds_its_own_weight() {
+    let mut world = rope_bridge(0.5);
+    for _ in 0..600 {
+        world.step(DT);
+        assert!(world.removed_forces.is_empty(), "a link snapped");
+    }
+    assert_eq!(world.forces.len(), LINKS);
+}
+
+#[test]
+fn overloaded_rope_bridge_tears() {
+    let mut world = rope_bridge(0.5);
+    for _ in 0..240 {
+        world.step(DT);
+    }
+
+    // Hang a heavy load from the middle of the bridge.
+    let middle = LINKS / 2;
+    world.entities[middle].set_mass(50.0);
+    let mut snapped = Vec::new();
+    for _ in 0..240 {
+        world.step(DT);
+        snapped.extend(world.removed_forces.iter().copied());
+        if !snapped.is_empty() {
+            break;
+        }
+    }
+
+    assert!(!snapped.is_empty(), "bridge never tore");
+    // A taut rope carries about the same tension everywhere, so the weak link goes.
+    assert_eq!(snapped, [FRAYED]);
+    assert_eq!(world.forces.len(), LINKS - 1);
+
+    // The bridge now hangs in two pieces and the load swings down.
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    let y = world.entities[middle].pos().y;
+    assert!(y < -2.0, "load still held at {y}");
+}
+
+#[test]
+fn strain_is_relative_to_rest_length() {
+    let mut world = rope_bridge(0.5);
+    *world.entities[1].pos_mut() = Vec2::new(SPACING * 2.5, 0.0);
+    let spring = Spring::between(0, 1, 1.0, 0.0, SPACING);
+    let strain = spring.strain(&world).unwrap();
+    assert_approx_eq!(strain, 1.5, 1e-5);
+
+    let attractor = Spring::between(0, 1, 1.0, 0.0, 0.0).with_max_strain(0.1);
+    assert!(attractor.strain(&world).is_none());
+}
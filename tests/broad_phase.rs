@@ -0,0 +1,209 @@
+//! Broad-phase candidate pairs: the incremental sweep and prune must report exactly the pairs
+//! of a full re-sort, and `World` keeps the last step's pairs.
+
+use tiny_physics_engine::core::collision::broad_phase::SapScratch;
+use tiny_physics_engine::core::collision::{IncrementalSap, broad_phase};
+use tiny_physics_engine::core::{
+    Integrator, PhysicalEntity, RigidBody, RigidBodyBuilder, SimParams, World,
+};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// Small deterministic generator so the test doesn't need a rand dependency.
+struct Lcg(u32);
+
+impl Lcg {
+    fn next(&mut self) -> Scalar {
+        self.0 = self.0.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+        (self.0 >> 8) as Scalar / (1u32 << 24) as Scalar
+    }
+
+    fn range(&mut self, lo: Scalar, hi: Scalar) -> Scalar {
+        lo + (hi - lo) * self.next()
+    }
+}
+
+fn random_box(rng: &mut Lcg) -> Box<dyn PhysicalEntity> {
+    let pos = Vec2::new(rng.range(-10.0, 10.0), rng.range(-4.0, 4.0));
+    let (w, h) = (rng.range(0.2, 1.5), rng.range(0.2, 1.5));
+    Box::new(RigidBody::box_xy(pos, rng.range(-1.0, 1.0), 1.0, w, h))
+}
+
+#[test]
+fn incremental_sap_matches_detect_sap() {
+    let params = SimParams::default();
+    let mut rng = Lcg(7);
+    let mut entities: Vec<Box<dyn PhysicalEntity>> =
+        (0..60).map(|_| random_box(&mut rng)).collect();
+    let mut sap = IncrementalSap::new();
+
+    for frame in 0..300 {
+        for e in &mut entities {
+            // Mostly small, coherent moves, with the odd teleport.
+            let step = if rng.next() < 0.02 { 8.0 } else { 0.1 };
+            let delta = Vec2::new(rng.range(-step, step), rng.range(-step, step));
+            *e.pos_mut() = *e.pos() + delta;
+        }
+        // Changing the entity count forces a rebuild.
+        if frame % 100 == 50 {
+            entities.push(random_box(&mut rng));
+        }
+
+        let expected = broad_phase::detect_sap(&entities, params, DT);
+        assert_eq!(sap.update(&entities, params, DT), expected, "frame {frame}");
+    }
+}
+
+#[test]
+fn reused_buffers_match_fresh_ones() {
+    let params = SimParams::default();
+    let mut rng = Lcg(11);
+    let mut entities: Vec<Box<dyn PhysicalEntity>> =
+        (0..40).map(|_| random_box(&mut rng)).collect();
+    let mut scratch = SapScratch::default();
+    let mut pairs = vec![(99, 100)];
+
+    for frame in 0..50 {
+        for e in &mut entities {
+            let delta = Vec2::new(rng.range(-0.5, 0.5), rng.range(-0.5, 0.5));
+            *e.pos_mut() = *e.pos() + delta;
+        }
+        // Fewer entities than last frame: nothing stale may survive in the buffers.
+        if frame == 25 {
+            entities.truncate(20);
+        }
+        broad_phase::detect_sap_into(&entities, params, DT, &mut scratch, &mut pairs);
+        assert_eq!(
+            pairs,
+            broad_phase::detect_sap(&entities, params, DT),
+            "frame {frame}"
+        );
+    }
+}
+
+#[test]
+fn incremental_sap_reports_touching_boxes() {
+    let params = SimParams::default();
+    let mut entities: Vec<Box<dyn PhysicalEntity>> = vec![
+        Box::new(RigidBody::box_xy(Vec2::new(-3.0, 0.0), 0.0, 1.0, 1.0, 1.0)),
+        Box::new(RigidBody::box_xy(Vec2::new(3.0, 0.0), 0.0, 1.0, 1.0, 1.0)),
+    ];
+    let mut sap = IncrementalSap::new();
+    assert!(sap.update(&entities, params, DT).is_empty());
+
+    // Slide the boxes together, through each other and apart again.
+    let mut seen = false;
+    for _ in 0..60 {
+        *entities[0].pos_mut() = *entities[0].pos() + Vec2::new(0.1, 0.0);
+        *entities[1].pos_mut() = *entities[1].pos() - Vec2::new(0.1, 0.0);
+        let pairs = sap.update(&entities, params, DT);
+        assert_eq!(pairs, broad_phase::detect_sap(&entities, params, DT));
+        seen |= pairs == [(0, 1)];
+    }
+    assert!(seen, "boxes never reported as overlapping");
+    assert!(sap.update(&entities, params, DT).is_empty());
+}
+
+#[test]
+fn world_keeps_last_steps_candidate_pairs() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        10.0,
+        1.0,
+    )));
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, 0.5),
+        0.0,
+        1.0,
+        1.0,
+        1.0,
+    )));
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(3.0, 5.0),
+        0.0,
+        1.0,
+        1.0,
+        1.0,
+    )));
+    assert!(world.pairs.is_empty());
+
+    world.step(1.0 / 60.0);
+    assert_eq!(world.pairs, [(0, 1)]);
+    // Every manifold comes from a candidate pair.
+    for m in &world.manifolds {
+        assert!(world.pairs.contains(&(m.a.min(m.b), m.a.max(m.b))));
+    }
+}
+
+#[test]
+fn overlapping_static_boxes_make_no_pairs() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    // A ground box and a slope sunk into it, as in the `slope_friction` example.
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        10.0,
+        1.0,
+    )));
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(2.0, 0.0),
+        0.4,
+        0.0,
+        4.0,
+        0.5,
+    )));
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(-2.0, 0.5),
+        0.0,
+        1.0,
+        1.0,
+        1.0,
+    )));
+    world.step(1.0 / 60.0);
+    assert_eq!(world.pairs, [(0, 2)]);
+    assert!(
+        world
+            .solver
+            .constraints
+            .iter()
+            .all(|c| (c.index_a, c.index_b) == (0, 2)),
+        "constraint between the static boxes"
+    );
+
+    // A static sensor still reports what it overlaps, static or not.
+    let sensor = RigidBodyBuilder::new()
+        .position(Vec2::new(-4.0, -0.5))
+        .mass(0.0)
+        .box_shape(1.0, 1.0)
+        .sensor(true)
+        .build();
+    world.add(Box::new(sensor));
+    world.step(1.0 / 60.0);
+    assert_eq!(world.trigger_events().entered, [(0, 3)]);
+}
+
+#[test]
+fn static_bvh_pairs_come_out_sorted() {
+    // Two boxes resting side by side on a ground box held in the static BVH: their own pair
+    // comes from the sweep, their pairs with the ground from the tree.
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    for x in [-0.5, 0.5] {
+        world.add(Box::new(RigidBody::box_xy(
+            Vec2::new(x, 0.5),
+            0.0,
+            1.0,
+            1.0,
+            1.0,
+        )));
+    }
+    world.rebuild_static_bvh();
+    world.step(DT);
+    assert_eq!(world.pairs, [(0, 1), (0, 2), (1, 2)]);
+}
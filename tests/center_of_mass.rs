@@ -0,0 +1,104 @@
+//! Bodies with a center of mass off their origin turn about it: a weighted base rights a
+//! tipped roly-poly, and nothing spins a body in free fall.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, PhysicalEntity, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A 0.5 m circle with its center of mass 0.3 below its center. Rolling resistance stops
+/// the rocking; without it the toy rocks forever.
+fn roly_poly(pos: Vec2, angle: Scalar) -> Box<dyn PhysicalEntity> {
+    let toy = RigidBodyBuilder::new()
+        .position(pos)
+        .angle(angle)
+        .circle(0.5)
+        .rolling_resistance(0.01)
+        .center_of_mass(Vec2::new(0.0, -0.3))
+        .build();
+    Box::new(toy)
+}
+
+#[test]
+fn free_fall_does_not_spin_an_offset_body() {
+    for substeps in [1, 4] {
+        let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+        world.solver.params.substeps = substeps;
+        world.add(roly_poly(Vec2::new(0.0, 10.0), 1.0));
+        let start = world.entities[0].world_center_of_mass();
+        for _ in 0..60 {
+            world.step(DT);
+            let e = &world.entities[0];
+            assert_eq!(e.omega(), 0.0, "{substeps} substeps");
+            assert_eq!(e.angle(), 1.0, "{substeps} substeps");
+        }
+        // The center of mass falls straight down, the origin with it.
+        let com = world.entities[0].world_center_of_mass();
+        assert_approx_eq!(com.x, start.x, 1e-5, "{com:?} from {start:?}");
+        assert!(com.y < start.y - 4.0, "{com:?} from {start:?}");
+    }
+}
+
+#[test]
+fn spinning_body_turns_about_its_center_of_mass() {
+    for substeps in [1, 4] {
+        let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+        world.solver.params.substeps = substeps;
+        world.add(roly_poly(Vec2::new(2.0, 1.0), 0.0));
+        *world.entities[0].omega_mut() = 3.0;
+        let com = world.entities[0].world_center_of_mass();
+        for step in 0..120 {
+            world.step(DT);
+            let e = &world.entities[0];
+            assert_approx_eq!(
+                e.world_center_of_mass(),
+                com,
+                1e-4,
+                "{substeps} substeps, step {step}"
+            );
+            // The origin circles the center of mass.
+            let arm = (*e.pos() - com).length();
+            assert_approx_eq!(arm, 0.3, 1e-4, "{substeps} substeps, step {step}");
+        }
+        assert_approx_eq!(world.entities[0].angle(), 6.0, 1e-3);
+    }
+}
+
+#[test]
+fn tipped_roly_poly_rights_itself() {
+    for tilt in [0.6, -0.9, 1.2, -1.5] {
+        let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+        world.add_ground(0.0, 0.8, 0.0);
+        world.add(roly_poly(Vec2::new(0.0, 0.5), tilt));
+        for _ in 0..2400 {
+            world.step(DT);
+        }
+        let e = &world.entities[1];
+        // It comes to rest where the righting torque m g d sin(angle) no longer beats the
+        // rolling resistance c r m g: within asin(0.01 * 0.5 / 0.3) ≈ 0.017 rad of upright.
+        assert_approx_eq!(e.angle(), 0.0, 0.017, "tilt {tilt}: angle");
+        assert_approx_eq!(e.omega(), 0.0, 1e-3, "tilt {tilt}: omega");
+        // Rolled upright without slipping: the contact point moved by `radius * tilt`.
+        let pos = *e.pos();
+        assert_approx_eq!(pos.x, 0.5 * tilt, 0.02, "tilt {tilt}: {pos:?}");
+        assert_approx_eq!(pos.y, 0.5, 0.02, "tilt {tilt}: {pos:?}");
+    }
+}
+
+#[test]
+fn inertia_is_taken_about_the_center_of_mass() {
+    // 1/2 m r^2 about the circle's center, plus m d^2 out to the weight 0.3 below it.
+    let toy = roly_poly(Vec2::zero(), 0.0);
+    let expected = 0.5 * 0.25 + 0.09;
+    assert_approx_eq!(toy.inertia(), expected, 1e-5);
+
+    // A hand-set inertia is already about the center of mass.
+    let flywheel = RigidBodyBuilder::new()
+        .circle(0.5)
+        .center_of_mass(Vec2::new(0.0, -0.3))
+        .inertia(2.0)
+        .build();
+    assert_eq!(flywheel.inertia, 2.0);
+}
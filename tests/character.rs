@@ -0,0 +1,123 @@
+//! `CharacterController::move_and_slide`: walking, sliding along walls, stepping up ledges
+//! and the ground probe.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{CharacterController, Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+/// Ground at y = 0 and a 0.5 x 1 character (static, index 1) standing on it at x = 0.
+fn level(controller: &CharacterController) -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    let character = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.5 + controller.skin))
+        .mass(0.0)
+        .box_shape(0.5, 1.0)
+        .build();
+    world.add(Box::new(character));
+    world
+}
+
+/// A static `w` x `h` block standing on the ground with its left face at x = `left`.
+fn add_block(world: &mut World, left: Scalar, w: Scalar, h: Scalar) {
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(left + 0.5 * w, 0.5 * h))
+        .mass(0.0)
+        .box_shape(w, h)
+        .build();
+    world.add(Box::new(block));
+}
+
+#[test]
+fn walks_along_the_ground() {
+    let controller = CharacterController::default();
+    let mut world = level(&controller);
+    // Walking with gravity pulling it down: the ground takes the downward part.
+    let result = controller.move_and_slide(&mut world, 1, Vec2::new(0.5, -0.1));
+    assert!(result.grounded);
+    assert_approx_eq!(result.moved, Vec2::new(0.5, 0.0), 1e-3);
+    assert_approx_eq!(*world.entities[1].pos(), Vec2::new(0.5, 0.51), 1e-3);
+}
+
+#[test]
+fn slides_along_a_wall() {
+    let controller = CharacterController::default();
+    let mut world = level(&controller);
+    add_block(&mut world, 1.0, 1.0, 5.0);
+    // Up and into the wall: it stops a skin short of the wall and keeps rising.
+    let result = controller.move_and_slide(&mut world, 1, Vec2::new(2.0, 1.0));
+    let pos = *world.entities[1].pos();
+    assert_approx_eq!(pos.x, 0.75 - controller.skin, 1e-3);
+    assert_approx_eq!(pos.y, 1.51, 1e-3);
+    assert!(!result.grounded);
+}
+
+#[test]
+fn steps_up_a_low_ledge_only() {
+    let controller = CharacterController::default();
+    for (height, climbs) in [(0.2, true), (0.5, false)] {
+        let mut world = level(&controller);
+        add_block(&mut world, 1.0, 3.0, height);
+        let result = controller.move_and_slide(&mut world, 1, Vec2::new(1.0, 0.0));
+        let pos = *world.entities[1].pos();
+        if climbs {
+            // Over the edge and standing on top of the ledge.
+            assert_approx_eq!(pos.x, 1.0, 1e-3);
+            assert_approx_eq!(pos.y, height + 0.5 + controller.skin, 1e-3);
+            assert!(result.grounded, "height {height}");
+        } else {
+            // Stopped against it, still on the ground.
+            assert_approx_eq!(pos.x, 0.75 - controller.skin, 1e-3);
+            assert_approx_eq!(pos.y, 0.51, 1e-3);
+            assert!(result.grounded, "height {height}");
+        }
+    }
+}
+
+#[test]
+fn ground_probe_reaches_only_a_little_way_down() {
+    let controller = CharacterController::default();
+    let mut world = level(&controller);
+    let result = controller.move_and_slide(&mut world, 1, Vec2::new(0.0, 0.03));
+    assert!(result.grounded, "within the probe");
+    let result = controller.move_and_slide(&mut world, 1, Vec2::new(0.0, 0.5));
+    assert!(!result.grounded, "jumped clear");
+    // Falling back lands it on the ground again.
+    let result = controller.move_and_slide(&mut world, 1, Vec2::new(0.0, -2.0));
+    assert!(result.grounded);
+    assert_approx_eq!(world.entities[1].pos().y, 0.51, 1e-3);
+}
+
+/// Where the character ends up after walking right into a 60° ramp whose foot is at
+/// x ≈ 2.88.
+fn walk_into_ramp(controller: &CharacterController) -> Vec2 {
+    let mut world = level(controller);
+    let ramp = RigidBodyBuilder::new()
+        .position(Vec2::new(3.0, 0.0))
+        .angle(Scalar::to_radians(60.0))
+        .mass(0.0)
+        .box_shape(4.0, 0.2)
+        .build();
+    world.add(Box::new(ramp));
+    for _ in 0..20 {
+        controller.move_and_slide(&mut world, 1, Vec2::new(0.2, 0.0));
+    }
+    *world.entities[1].pos()
+}
+
+#[test]
+fn climbs_only_slopes_it_can_stand_on() {
+    // Too steep (the default limit is 0.8 rad, about 46°): it stops at the foot.
+    let pos = walk_into_ramp(&CharacterController::default());
+    assert_approx_eq!(pos.y, 0.51, 1e-3);
+    assert!(pos.x < 2.7, "walked into the ramp: {pos:?}");
+
+    // With a 70° limit it walks up.
+    let steep = CharacterController {
+        max_slope: Scalar::to_radians(70.0),
+        ..CharacterController::default()
+    };
+    let pos = walk_into_ramp(&steep);
+    assert!(pos.y > 1.0, "stayed at the foot: {pos:?}");
+}
@@ -0,0 +1,116 @@
+//! `collision::closest_points` against hand-computed distances.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::Collider2D;
+use tiny_physics_engine::core::collision::closest_points;
+use tiny_physics_engine::math::transform::Transform2D;
+use tiny_physics_engine::math::vec::Vec2;
+use tiny_physics_engine::math::{Scalar, consts};
+
+fn at(pos: Vec2, angle: Scalar) -> Transform2D {
+    Transform2D::from_pose(pos, angle)
+}
+
+fn square(side: Scalar) -> Collider2D {
+    Collider2D::Box {
+        half_extents: Vec2::new(0.5 * side, 0.5 * side),
+    }
+}
+
+#[test]
+fn separated_circles() {
+    let a = Collider2D::Circle { radius: 1.0 };
+    let b = Collider2D::Circle { radius: 0.5 };
+    // Centers 5 apart along (3, 4).
+    let (pa, pb, d) = closest_points(
+        &a,
+        &at(Vec2::zero(), 0.0),
+        &b,
+        &at(Vec2::new(3.0, 4.0), 0.0),
+    );
+    assert_approx_eq!(d, 3.5, 1e-4);
+    assert_approx_eq!(pa, Vec2::new(0.6, 0.8), 1e-4);
+    assert_approx_eq!(pb, Vec2::new(2.7, 3.6), 1e-4);
+}
+
+#[test]
+fn overlapping_circles_report_zero() {
+    let a = Collider2D::Circle { radius: 1.0 };
+    let (pa, pb, d) = closest_points(
+        &a,
+        &at(Vec2::zero(), 0.0),
+        &a,
+        &at(Vec2::new(1.5, 0.0), 0.0),
+    );
+    assert_approx_eq!(d, 0.0, 1e-4);
+    assert_approx_eq!(pa, pb, 1e-4);
+}
+
+#[test]
+fn boxes_face_to_face() {
+    // Unit squares at x = 0 and x = 3: faces at 0.5 and 2.5.
+    let (pa, pb, d) = closest_points(
+        &square(1.0),
+        &at(Vec2::zero(), 0.0),
+        &square(1.0),
+        &at(Vec2::new(3.0, 0.2), 0.0),
+    );
+    assert_approx_eq!(d, 2.0, 1e-4);
+    assert_approx_eq!(pa.x, 0.5, 1e-4);
+    assert_approx_eq!(pb.x, 2.5, 1e-4);
+    assert_approx_eq!(pa.y, pb.y, 1e-4);
+}
+
+#[test]
+fn rotated_box_corner_to_face() {
+    // A square turned 45°: its left corner sits sqrt(2)/2 from its center.
+    let offset = 3.0;
+    let (pa, pb, d) = closest_points(
+        &square(1.0),
+        &at(Vec2::zero(), 0.0),
+        &square(1.0),
+        &at(Vec2::new(offset, 0.0), consts::FRAC_PI_4),
+    );
+    let corner = offset - consts::FRAC_1_SQRT_2;
+    assert_approx_eq!(d, corner - 0.5, 1e-4);
+    assert_approx_eq!(pa, Vec2::new(0.5, 0.0), 1e-4);
+    assert_approx_eq!(pb, Vec2::new(corner, 0.0), 1e-4);
+}
+
+#[test]
+fn box_corner_to_corner() {
+    // Diagonal neighbours: closest points are the facing corners.
+    let (pa, pb, d) = closest_points(
+        &square(2.0),
+        &at(Vec2::zero(), 0.0),
+        &square(2.0),
+        &at(Vec2::new(5.0, 6.0), 0.0),
+    );
+    assert_approx_eq!(d, 5.0, 1e-4);
+    assert_approx_eq!(pa, Vec2::new(1.0, 1.0), 1e-4);
+    assert_approx_eq!(pb, Vec2::new(4.0, 5.0), 1e-4);
+}
+
+#[test]
+fn circle_to_box_face() {
+    let (pa, pb, d) = closest_points(
+        &Collider2D::Circle { radius: 0.5 },
+        &at(Vec2::new(0.3, 4.0), 0.0),
+        &square(2.0),
+        &at(Vec2::zero(), 0.0),
+    );
+    assert_approx_eq!(d, 2.5, 1e-4);
+    assert_approx_eq!(pa, Vec2::new(0.3, 3.5), 1e-4);
+    assert_approx_eq!(pb, Vec2::new(0.3, 1.0), 1e-4);
+}
+
+#[test]
+fn overlapping_boxes_report_zero() {
+    let (_, _, d) = closest_points(
+        &square(2.0),
+        &at(Vec2::zero(), 0.0),
+        &square(2.0),
+        &at(Vec2::new(1.5, 0.5), 0.3),
+    );
+    assert_approx_eq!(d, 0.0, 1e-4);
+}
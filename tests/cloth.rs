@@ -0,0 +1,81 @@
+//! Grid cloth helper: layout, spring count and a hanging cloth.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, World};
+use tiny_physics_engine::forces::cloth::{GridCloth, build_grid_cloth};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+fn falling_world() -> World {
+    World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler)
+}
+
+#[test]
+fn grid_indices_and_spring_counts() {
+    let (cols, rows) = (5, 4);
+    let mut cloth = GridCloth::new(Vec2::new(1.0, 2.0), cols, rows, 0.5, 100.0, 0.1, 0.1);
+
+    let mut world = falling_world();
+    let grid = build_grid_cloth(&mut world, &cloth);
+    assert_eq!(world.entities.len(), cols * rows);
+    assert_eq!(grid.len(), rows);
+    let at = |row: usize, col: usize| *world.entities[grid[row][col]].pos();
+    assert_approx_eq!(at(0, 0), Vec2::new(1.0, 2.0), 1e-6);
+    assert_approx_eq!(at(3, 4), Vec2::new(3.0, 0.5), 1e-6);
+    let structural = rows * (cols - 1) + (rows - 1) * cols;
+    assert_eq!(world.forces.len(), structural);
+
+    cloth.shear = true;
+    cloth.bend = true;
+    let mut world = falling_world();
+    build_grid_cloth(&mut world, &cloth);
+    let shear = 2 * (rows - 1) * (cols - 1);
+    let bend = rows * (cols - 2) + (rows - 2) * cols;
+    assert_eq!(world.forces.len(), structural + shear + bend);
+}
+
+#[test]
+fn cloth_hangs_from_pinned_top_row() {
+    let cloth = GridCloth {
+        shear: true,
+        ..GridCloth::new(Vec2::zero(), 6, 6, 0.2, 200.0, 0.5, 0.05)
+    };
+    let mut world = falling_world();
+    let grid = build_grid_cloth(&mut world, &cloth);
+    let top: Vec<Vec2> = grid[0].iter().map(|&i| *world.entities[i].pos()).collect();
+
+    for _ in 0..600 {
+        world.step(1.0 / 120.0);
+    }
+
+    for (&i, start) in grid[0].iter().zip(&top) {
+        let pos = *world.entities[i].pos();
+        assert_approx_eq!(pos, *start, 0.0, "pinned particle {i} moved");
+    }
+    // The rest hangs below, stretched a little by its weight but not torn apart.
+    let bottom = world.entities[grid[5][0]].pos().y;
+    assert!(bottom < -1.0 && bottom > -1.5, "bottom row at {bottom}");
+    let speed = world.entities[grid[5][3]].vel().length();
+    assert!(speed < 0.05, "cloth still swinging at {speed} m/s");
+}
+
+#[test]
+fn unpinned_cloth_falls_freely() {
+    let cloth = GridCloth {
+        pin_top_row: false,
+        ..GridCloth::new(Vec2::zero(), 3, 3, 0.2, 200.0, 0.5, 0.05)
+    };
+    let mut world = falling_world();
+    build_grid_cloth(&mut world, &cloth);
+    for _ in 0..60 {
+        world.step(1.0 / 60.0);
+    }
+    let momentum = world.total_momentum();
+    let expected: Scalar = -9.81 * 9.0 * 0.05;
+    assert_approx_eq!(
+        momentum.y,
+        expected,
+        1e-3 * expected.abs(),
+        "momentum {momentum:?}, expected {expected}"
+    );
+}
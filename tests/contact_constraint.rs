@@ -0,0 +1,93 @@
+//! Driving `ContactConstraint`s with a hand-written solver loop.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::collision::narrow_phase;
+use tiny_physics_engine::core::{
+    BodyStore, Constraint, ContactConstraint, PhysicalEntity, RigidBodyBuilder, SimParams,
+    SolverParams,
+};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A ball falling at `speed` just touching a static ground, with `restitution` on both.
+fn landing(speed: Scalar, restitution: Scalar) -> Vec<Box<dyn PhysicalEntity>> {
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .restitution(restitution)
+        .build();
+    let mut ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.25))
+        .circle(0.25)
+        .restitution(restitution)
+        .friction(0.0)
+        .build();
+    ball.vel = Vec2::new(0.0, -speed);
+    vec![Box::new(ground), Box::new(ball)]
+}
+
+/// One step of the documented schedule; returns the solved velocity of entity 1.
+fn solve(entities: &[Box<dyn PhysicalEntity>], params: &SolverParams) -> Vec2 {
+    let manifolds = narrow_phase::detect(
+        entities,
+        &[(0, 1)],
+        SimParams::default(),
+        DT,
+        params.default_material(),
+    );
+    assert_eq!(manifolds.len(), 1);
+    let mut constraints = ContactConstraint::from_manifold(&manifolds[0], entities, params);
+    assert!(!constraints.is_empty());
+
+    let mut bodies = BodyStore::default();
+    bodies.load(entities);
+    bodies.init_deltas(DT);
+    for c in &constraints {
+        c.warm_start(&mut bodies);
+    }
+    for _ in 0..params.velocity_iterations {
+        for c in &mut constraints {
+            c.solve_normal(&mut bodies, DT, params, None, true);
+        }
+        for c in &mut constraints {
+            c.solve_tangent(&mut bodies, DT);
+        }
+    }
+    for c in &mut constraints {
+        c.apply_restitution(&mut bodies, DT, params);
+    }
+    bodies.vel[1]
+}
+
+#[test]
+fn hand_solved_landing_stops_the_ball() {
+    let params = SolverParams::default();
+    let vel = solve(&landing(3.0, 0.0), &params);
+    assert_approx_eq!(vel, Vec2::zero(), 1e-4, "ball still moving");
+}
+
+#[test]
+fn hand_solved_landing_bounces_with_the_restitution() {
+    let params = SolverParams::default();
+    let vel = solve(&landing(4.0, 0.5), &params);
+    assert_approx_eq!(vel.y, 2.0, 1e-3);
+}
+
+#[test]
+fn manifold_with_a_missing_body_builds_nothing() {
+    let params = SolverParams::default();
+    let entities = landing(3.0, 0.0);
+    let mut manifold = narrow_phase::detect(
+        &entities,
+        &[(0, 1)],
+        SimParams::default(),
+        DT,
+        params.default_material(),
+    )
+    .remove(0);
+    manifold.b = 7;
+    assert!(ContactConstraint::from_manifold(&manifold, &entities, &params).is_empty());
+}
@@ -0,0 +1,758 @@
+//! Collision response against closed-form results: elastic exchange, sliding and sticking on
+//! a slope, and restitution bounce height. Headless, so also runs without `visualize`.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::vec::Vec2;
+use tiny_physics_engine::math::{Scalar, consts};
+
+const DT: Scalar = 1.0 / 60.0;
+const G: Scalar = 9.81;
+
+fn world(gravity: Scalar) -> World {
+    World::new(Vec2::new(0.0, -gravity), Integrator::SemiImplicitEuler)
+}
+
+/// A static slope at angle `theta` through the origin, and a 1 kg box resting on it.
+/// Both use `friction`. Returns the world and the direction down the slope.
+fn box_on_slope(theta: Scalar, friction: Scalar) -> (World, Vec2) {
+    let mut world = world(G);
+    let slope = RigidBodyBuilder::new()
+        .angle(theta)
+        .mass(0.0)
+        .box_shape(40.0, 1.0)
+        .friction(friction)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(slope));
+
+    let (sin, cos) = theta.sin_cos();
+    let normal = Vec2::new(-sin, cos);
+    let down = Vec2::new(-cos, -sin);
+    // Surface at 0.5 along the normal, box half height 0.5 on top of that.
+    let body = RigidBodyBuilder::new()
+        .position(normal * 1.0)
+        .angle(theta)
+        .box_shape(1.0, 1.0)
+        .friction(friction)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(body));
+    (world, down)
+}
+
+#[test]
+fn equal_elastic_circles_exchange_velocities() {
+    let mut world = world(0.0);
+    world.solver.params.restitution_threshold = 0.0;
+    for (x, vx) in [(-2.0, 3.0), (2.0, 0.0)] {
+        let circle = RigidBodyBuilder::new()
+            .position(Vec2::new(x, 0.0))
+            .velocity(Vec2::new(vx, 0.0))
+            .circle(0.5)
+            .restitution(1.0)
+            .friction(0.0)
+            .build();
+        world.add(Box::new(circle));
+    }
+    let momentum_before = world.total_momentum();
+    let energy_before = world.total_kinetic_energy();
+
+    for _ in 0..120 {
+        world.step(DT);
+    }
+
+    let (a, b) = (*world.entities[0].vel(), *world.entities[1].vel());
+    assert_approx_eq!(a, Vec2::zero(), 0.05, "first circle kept moving");
+    assert_approx_eq!(b.x, 3.0, 0.05, "second circle at {b:?}");
+    assert_approx_eq!(b.y, 0.0, 1e-4, "second circle at {b:?}");
+    assert_approx_eq!(world.total_momentum(), momentum_before, 1e-3, "momentum");
+    let energy = world.total_kinetic_energy();
+    assert_approx_eq!(
+        energy,
+        energy_before,
+        0.02 * energy_before,
+        "energy {energy_before} -> {energy}"
+    );
+}
+
+#[test]
+fn box_slides_down_frictionless_slope_at_g_sin_theta() {
+    let theta = Scalar::to_radians(30.0);
+    let (mut world, down) = box_on_slope(theta, 0.0);
+
+    // Let the contact settle, then time the acceleration along the slope.
+    for _ in 0..10 {
+        world.step(DT);
+    }
+    let v0 = world.entities[1].vel().dot(down);
+    let steps = 60;
+    for _ in 0..steps {
+        world.step(DT);
+    }
+    let v1 = world.entities[1].vel().dot(down);
+
+    let accel = (v1 - v0) / (steps as Scalar * DT);
+    let expected = G * theta.sin();
+    assert_approx_eq!(
+        accel,
+        expected,
+        0.02 * expected,
+        "acceleration {accel}, expected {expected}"
+    );
+}
+
+#[test]
+fn box_sticks_on_slope_below_friction_angle() {
+    // tan(20°) ≈ 0.36, well below a friction coefficient of 1.
+    let theta = Scalar::to_radians(20.0);
+    let (mut world, down) = box_on_slope(theta, 1.0);
+    let start = *world.entities[1].pos();
+
+    for _ in 0..180 {
+        world.step(DT);
+    }
+
+    let slid = (*world.entities[1].pos() - start).dot(down);
+    assert_approx_eq!(slid, 0.0, 0.01, "box slid");
+    let speed = world.entities[1].vel().length();
+    assert!(speed < 0.01, "box still moving at {speed} m/s");
+}
+
+#[test]
+fn box_on_level_floor_does_not_creep() {
+    // Two contact points clamped one by one let a resting box creep sideways; the shared
+    // friction cone must not, and neither may the per-point mode on a level floor.
+    for block_friction in [true, false] {
+        let (mut world, _) = box_on_slope(0.0, 0.8);
+        world.solver.params.block_friction = block_friction;
+        let start = world.entities[1].pos().x;
+        for _ in 0..2000 {
+            world.step(DT);
+        }
+        let x = world.entities[1].pos().x;
+        assert_approx_eq!(x, start, 1e-5, "block_friction {block_friction}");
+    }
+}
+
+#[test]
+fn frictionless_column_does_not_walk_sideways() {
+    // Solving a box's two contact normals one after the other turns it a little, which tilts
+    // the next box's contact normal; without friction to hold it, a column of perfectly
+    // aligned boxes used to walk off. The block normal solve pushes both points evenly.
+    let mut world = world(G);
+    world.add_ground(0.0, 0.0, 0.0);
+    for i in 0..10 {
+        let block = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 0.25 + 0.5 * i as Scalar))
+            .box_shape(1.0, 0.5)
+            .friction(0.0)
+            .build();
+        world.add(Box::new(block));
+    }
+    for step in 0..600 {
+        world.step(DT);
+        for (i, e) in world.entities.iter().enumerate().skip(1) {
+            assert_approx_eq!(e.pos().x, 0.0, 1e-4, "box {i} at step {step}");
+        }
+    }
+}
+
+#[test]
+fn ball_bounces_to_restitution_squared_height() {
+    let restitution = 0.5;
+    let drop_height = 2.0;
+    let radius = 0.25;
+
+    let mut world = world(G);
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(ground));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, radius + drop_height))
+        .circle(radius)
+        .restitution(restitution)
+        .build();
+    world.add(Box::new(ball));
+
+    // Fall, bounce, and record the top of the first rebound.
+    let mut bounced = false;
+    let mut apex = Scalar::NEG_INFINITY;
+    for _ in 0..240 {
+        world.step(DT);
+        let ball = &world.entities[1];
+        if ball.vel().y > 0.0 {
+            bounced = true;
+        }
+        if bounced {
+            apex = apex.max(ball.pos().y - radius);
+            if ball.vel().y < 0.0 {
+                break;
+            }
+        }
+    }
+
+    assert!(bounced, "ball never bounced");
+    let expected = restitution * restitution * drop_height;
+    assert_approx_eq!(
+        apex,
+        expected,
+        0.1 * expected,
+        "rebound height {apex}, expected {expected}"
+    );
+}
+
+/// Gap between the centers of two unit boxes spawned overlapping by half their width, after
+/// each of `steps` steps without gravity.
+fn spawn_overlap_gaps(max_correction_per_step: Scalar, steps: usize) -> Vec<Scalar> {
+    let mut world = world(0.0);
+    world.solver.params.max_correction_per_step = max_correction_per_step;
+    for x in [0.0, 0.5] {
+        let body = RigidBodyBuilder::new()
+            .position(Vec2::new(x, 0.0))
+            .box_shape(1.0, 1.0)
+            .build();
+        world.add(Box::new(body));
+    }
+    (0..steps)
+        .map(|_| {
+            world.step(DT);
+            world.entities[1].pos().x - world.entities[0].pos().x
+        })
+        .collect()
+}
+
+#[test]
+fn penetration_recovery_is_capped_per_step() {
+    let cap = 0.01;
+    let uncapped = spawn_overlap_gaps(Scalar::INFINITY, 1);
+    assert!(
+        uncapped[0] - 0.5 > cap,
+        "default recovery {}",
+        uncapped[0] - 0.5
+    );
+
+    let gaps = spawn_overlap_gaps(cap, 240);
+    let mut previous = 0.5;
+    for (step, &gap) in gaps.iter().enumerate() {
+        let recovered = gap - previous;
+        assert!(
+            recovered <= cap + 1e-4,
+            "step {step}: recovered {recovered}"
+        );
+        previous = gap;
+    }
+    // Eased apart over many steps, but fully.
+    assert!(gaps[10] < 0.65, "separated too fast: {}", gaps[10]);
+    assert!(gaps[239] > 0.98, "still overlapping: {}", gaps[239]);
+}
+
+#[test]
+fn relax_pass_friction_is_not_pushed_into_the_position() {
+    // A settled box (resting in the slop, so the bias isn't pushing it) kicked sideways:
+    // the relax iteration still changes its friction impulse, which is not push-out, so
+    // the box moves exactly as its velocity says.
+    let mut world = world(G);
+    world.solver.params.velocity_iterations = 1;
+    world.add_ground(0.0, 0.5, 0.0);
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.5))
+        .box_shape(1.0, 1.0)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(block));
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    *world.entities[1].vel_mut() = Vec2::new(3.0, 0.0);
+
+    for step in 0..10 {
+        let x = world.entities[1].pos().x;
+        world.step(DT);
+        let e = &world.entities[1];
+        let pushed = e.pos().x - x - e.vel().x * DT;
+        assert_approx_eq!(pushed, 0.0, 1e-6, "step {step}: pushed sideways");
+    }
+}
+
+#[test]
+fn contact_age_counts_steps_in_contact() {
+    let mut world = world(G);
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .build();
+    world.add(Box::new(ground));
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.5))
+        .box_shape(1.0, 1.0)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(block));
+
+    world.step(DT);
+    assert!(world.contacts().all(|c| c.age == 0), "first contact is new");
+    for step in 1..10 {
+        world.step(DT);
+        for c in world.contacts() {
+            assert_eq!(c.age, step, "resting contact age");
+        }
+    }
+}
+
+#[test]
+fn only_the_impact_step_bounces() {
+    let mut world = world(G);
+    world.solver.params.restitution_threshold = 0.0;
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .restitution(1.0)
+        .build();
+    world.add(Box::new(ground));
+    // Fully elastic, but resting: the contact is old after its first step, so gravity
+    // pressing it into the ground never turns into a bounce.
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.5))
+        .box_shape(1.0, 1.0)
+        .restitution(1.0)
+        .build();
+    world.add(Box::new(block));
+
+    for _ in 0..300 {
+        world.step(DT);
+    }
+    let speed = world.entities[1].vel().length();
+    assert!(speed < 1e-3, "resting block moving at {speed} m/s");
+}
+
+/// Drop a ball with restitution `e` from 1 m and watch it for 15 s. Returns the number of
+/// bounces, the slowest take-off speed of any bounce, and the ball's final speed.
+fn bounces(e: Scalar) -> (usize, Scalar, Scalar) {
+    let radius = 0.25;
+    let mut world = world(G);
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(ground));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, radius + 1.0))
+        .circle(radius)
+        .restitution(e)
+        .build();
+    world.add(Box::new(ball));
+
+    let mut count = 0;
+    let mut slowest = Scalar::INFINITY;
+    let mut rising = false;
+    for _ in 0..900 {
+        world.step(DT);
+        let vy = world.entities[1].vel().y;
+        if vy > 0.0 && !rising {
+            count += 1;
+            slowest = slowest.min(vy);
+        }
+        rising = vy > 0.0;
+    }
+    (count, slowest, world.entities[1].vel().length())
+}
+
+#[test]
+fn bouncy_ball_bounces_off_slow_landings_then_settles() {
+    // A fixed 1 m/s threshold would stop a 0.9 ball at the first landing slower than that;
+    // scaled by (1 - e) it keeps bouncing down to 0.1 m/s, where the floor ends it.
+    let (count, slowest, speed) = bounces(0.9);
+    assert!(slowest < 0.5, "slowest bounce {slowest} m/s");
+    assert!(
+        speed < 1e-3,
+        "still moving at {speed} m/s after {count} bounces"
+    );
+
+    let (dull, dull_slowest, _) = bounces(0.3);
+    assert!(dull < count, "{dull} vs {count} bounces");
+    assert!(dull_slowest > slowest);
+}
+
+#[test]
+fn elastic_circle_collision_conserves_energy() {
+    // The `circle_circle_elastic` scene: a slightly off-center hit, no gravity.
+    let mut world = world(0.0);
+    for (pos, vel) in [
+        (Vec2::new(-3.0, 0.0), Vec2::new(6.0, 0.5)),
+        (Vec2::new(3.0, 0.2), Vec2::new(-6.0, -0.3)),
+    ] {
+        let circle = RigidBodyBuilder::new()
+            .position(pos)
+            .velocity(vel)
+            .circle(0.45)
+            .restitution(1.0)
+            .friction(0.0)
+            .build();
+        world.add(Box::new(circle));
+    }
+    let energy_before = world.total_kinetic_energy();
+    let mut collided = false;
+    for _ in 0..240 {
+        world.step(1.0 / 240.0);
+        collided |= world.contacts().any(|c| c.normal_impulse > 0.0);
+    }
+
+    assert!(collided, "the circles never hit");
+    let energy = world.total_kinetic_energy();
+    assert_approx_eq!(
+        energy,
+        energy_before,
+        1e-3 * energy_before,
+        "energy {energy_before} -> {energy}"
+    );
+}
+
+#[test]
+fn elastic_ball_rebounds_to_its_drop_height() {
+    // The bounce reverses the speed the ball hit with, not that plus the gravity of the
+    // step it was detected in.
+    let (radius, start) = (0.25, 2.25);
+    let mut world = world(G);
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .restitution(1.0)
+        .build();
+    world.add(Box::new(ground));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, start))
+        .circle(radius)
+        .restitution(1.0)
+        .friction(0.0)
+        .build();
+    world.add(Box::new(ball));
+
+    let mut bounced = false;
+    let mut apex = Scalar::NEG_INFINITY;
+    for _ in 0..200 {
+        world.step(DT);
+        let vy = world.entities[1].vel().y;
+        bounced |= vy > 0.0;
+        if bounced {
+            apex = apex.max(world.entities[1].pos().y);
+            if vy < 0.0 {
+                break;
+            }
+        }
+    }
+    assert_approx_eq!(
+        apex,
+        start,
+        1e-3 * start,
+        "rebound apex {apex}, dropped from {start}"
+    );
+}
+
+/// Deepest penetration of a 1 kg box spawned 5 cm deep into a static ground, after `steps`
+/// steps. (A box landing from a drop is caught by its speculative contact and never sinks.)
+fn resting_penetration(flush_after: Option<u32>, steps: usize) -> Scalar {
+    let mut world = world(G);
+    world.solver.params.flush_after = flush_after;
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .build();
+    world.add(Box::new(ground));
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.45))
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(block));
+    for _ in 0..steps {
+        world.step(DT);
+    }
+    world
+        .contacts()
+        .map(|c| c.penetration)
+        .fold(Scalar::NEG_INFINITY, Scalar::max)
+}
+
+#[test]
+fn resting_box_sinks_by_the_slop() {
+    let slop = tiny_physics_engine::core::SolverParams::default().slop;
+    let penetration = resting_penetration(None, 100);
+    assert_approx_eq!(penetration, slop, 0.2 * slop);
+}
+
+#[test]
+fn settled_box_is_pushed_flush() {
+    let slop = tiny_physics_engine::core::SolverParams::default().slop;
+    let penetration = resting_penetration(Some(10), 100);
+    assert_approx_eq!(penetration, 0.0, 0.1 * slop);
+}
+
+#[test]
+fn tilted_box_lands_flat_on_the_ground_helper() {
+    let mut world = world(G);
+    let ground = world.add_ground(-1.0, 0.6, 0.0);
+    assert_eq!(ground, 0);
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(3.0, 1.0))
+        .angle(0.4)
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(block));
+    for _ in 0..240 {
+        world.step(DT);
+    }
+
+    // Tipped onto a face: resting half a box above the surface, angle a multiple of 90°.
+    let body = &world.entities[1];
+    let quarter = consts::FRAC_PI_2;
+    let off_face = (body.angle() / quarter).round() * quarter - body.angle();
+    assert_approx_eq!(off_face, 0.0, 0.01, "angle {}", body.angle());
+    assert_approx_eq!(body.pos().y, -0.5, 0.02, "at {:?}", body.pos());
+    assert!(body.vel().length() < 0.01 && body.omega().abs() < 0.01);
+}
+
+/// A 4 x 0.2 rod at the origin spinning at `omega` (no gravity), 0.25 rad short of a small
+/// static peg at radius 1.9. Returns the rod's angular velocity after `steps` steps.
+fn rod_spinning_into_peg(omega: Scalar, max_angular_speed: Option<Scalar>, steps: usize) -> Scalar {
+    let mut world = world(0.0);
+    let mut rod = RigidBodyBuilder::new()
+        .angle(-0.25)
+        .angular_velocity(omega)
+        .box_shape(4.0, 0.2);
+    if let Some(limit) = max_angular_speed {
+        rod = rod.max_angular_speed(limit);
+    }
+    world.add(Box::new(rod.build()));
+    let peg = RigidBodyBuilder::new()
+        .position(Vec2::new(1.9, 0.0))
+        .mass(0.0)
+        .box_shape(0.1, 0.1)
+        .build();
+    world.add(Box::new(peg));
+    for _ in 0..steps {
+        world.step(DT);
+    }
+    world.entities[0].omega()
+}
+
+#[test]
+fn fast_spinning_rod_hits_a_peg_its_corner_would_skip() {
+    // At 20 rad/s the rod turns 0.33 rad per step and would be past the peg after one step
+    // without the spin margin.
+    let omega = rod_spinning_into_peg(20.0, None, 10);
+    assert!(omega < 5.0, "still spinning at {omega} rad/s");
+}
+
+#[test]
+fn per_body_angular_speed_limit() {
+    let omega = rod_spinning_into_peg(-20.0, Some(8.0), 1);
+    assert_eq!(omega, -8.0);
+}
+
+#[test]
+fn bad_speed_limits_do_not_panic_mid_step() {
+    // A negative limit counts by its size, a NaN one as no limit.
+    assert_eq!(rod_spinning_into_peg(-20.0, Some(-8.0), 1), -8.0);
+    assert_eq!(rod_spinning_into_peg(-20.0, Some(Scalar::NAN), 1), -20.0);
+
+    let mut world = world(0.0);
+    world.params.max_linear_speed = -2.0;
+    world.params.max_angular_speed = Scalar::NAN;
+    let body = RigidBodyBuilder::new()
+        .velocity(Vec2::new(5.0, 0.0))
+        .angular_velocity(3.0)
+        .max_angular_speed(Scalar::NAN)
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(body));
+    world.step(DT);
+    assert_approx_eq!(*world.entities[0].vel(), Vec2::new(2.0, 0.0), 1e-6);
+    assert_eq!(world.entities[0].omega(), 3.0);
+}
+
+#[test]
+fn spinning_box_is_not_touched_before_its_corner_lands() {
+    // A unit box spinning at 30 rad/s just above the floor: its corners sweep down to 4.3 cm
+    // above it, inside the speculative margin but never touching, until it has fallen that far
+    // (about six steps). Before that no contact may take energy out of or put it into the
+    // spin or the sideways motion. Substeps follow the corner along its arc; a single solve
+    // extrapolates it in a straight line, into the floor, and does catch it.
+    for substeps in [4, 8] {
+        let mut world = world(G);
+        world.solver.params.substeps = substeps;
+        world.add_ground(0.0, 0.5, 0.0);
+        let block = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 0.75))
+            .angular_velocity(30.0)
+            .box_shape(1.0, 1.0)
+            .friction(0.5)
+            .restitution(0.0)
+            .build();
+        world.add(Box::new(block));
+        for step in 0..4 {
+            world.step(DT);
+            let body = &world.entities[1];
+            assert_eq!(
+                (body.omega(), body.vel().x),
+                (30.0, 0.0),
+                "{substeps} substeps, step {step}"
+            );
+        }
+    }
+}
+
+/// A 1 kg body of diameter 1 spinning at `omega` on the floor (friction 0.5, no bounce),
+/// stepped with the default single solve. Returns the world, and asserts that no step raised
+/// its kinetic plus potential energy: friction at the contact only ever takes energy out.
+fn spin_down_on_floor(circle: bool, omega: Scalar) -> World {
+    let mut world = world(G);
+    world.add_ground(0.0, 0.5, 0.0);
+    let body = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.5))
+        .angular_velocity(omega)
+        .friction(0.5)
+        .restitution(0.0);
+    let body = if circle {
+        body.circle(0.5)
+    } else {
+        body.box_shape(1.0, 1.0)
+    };
+    world.add(Box::new(body.build()));
+    assert_eq!(world.solver.params.substeps, 1);
+
+    let energy = |w: &World| w.total_kinetic_energy() + G * w.entities[1].pos().y;
+    let mut last = energy(&world);
+    for step in 0..180 {
+        world.step(DT);
+        let e = energy(&world);
+        assert!(
+            e <= last + 1e-4,
+            "omega {omega}, step {step}: energy rose from {last} to {e}"
+        );
+        last = e;
+    }
+    world
+}
+
+#[test]
+fn fast_spinning_contact_gains_no_energy_without_substeps() {
+    for omega in [20.0, 40.0, 80.0] {
+        // A wheel set down spinning slides until it rolls: v = r * omega' with
+        // omega' = omega / 3, keeping a third of its spin energy.
+        let world = spin_down_on_floor(true, omega);
+        let wheel = &world.entities[1];
+        let expected = omega / 3.0;
+        assert_approx_eq!(
+            wheel.omega(),
+            expected,
+            1e-3 * omega,
+            "omega {omega}: rolls at {}",
+            wheel.omega()
+        );
+        assert_approx_eq!(
+            wheel.vel().x,
+            -0.5 * wheel.omega(),
+            1e-3 * omega,
+            "omega {omega}: slipping"
+        );
+        let spin_energy = 0.5 * wheel.inertia() * omega * omega;
+        let kinetic = world.total_kinetic_energy();
+        assert_approx_eq!(
+            kinetic,
+            spin_energy / 3.0,
+            1e-3 * spin_energy,
+            "omega {omega}: {kinetic} J of {spin_energy} J left"
+        );
+
+        // A spinning box hammers the floor with its corners.
+        spin_down_on_floor(false, omega);
+    }
+}
+
+/// A 1 kg unit box `gap` to the left of a static wall, moving towards it at `speed`, without
+/// gravity. Returns the gap and the box's velocity after each of 60 steps.
+fn drift_into_wall(gap: Scalar, speed: Scalar) -> Vec<(Scalar, Scalar)> {
+    let mut world = world(0.0);
+    let wall = RigidBodyBuilder::new()
+        .position(Vec2::new(0.5, 0.0))
+        .mass(0.0)
+        .box_shape(1.0, 4.0)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(wall));
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(-0.5 - gap, 0.0))
+        .velocity(Vec2::new(speed, 0.0))
+        .box_shape(1.0, 1.0)
+        .friction(0.0)
+        .restitution(0.0)
+        .build();
+    world.add(Box::new(block));
+    (0..60)
+        .map(|_| {
+            world.step(DT);
+            let body = &world.entities[1];
+            (-0.5 - body.pos().x, body.vel().x)
+        })
+        .collect()
+}
+
+#[test]
+fn drifting_box_stops_flush_against_a_wall() {
+    let slop = tiny_physics_engine::core::SolverParams::default().slop;
+    // From outside the speculative distance, and from inside it.
+    for (gap, speed) in [(0.3, 0.5), (0.03, 0.2)] {
+        let track = drift_into_wall(gap, speed);
+        for &(g, v) in &track {
+            assert!(v <= speed, "sped up to {v} at gap {g}, started at {speed}");
+            assert!(g > -slop, "sucked in to gap {g}");
+        }
+        let (g, v) = track[track.len() - 1];
+        assert_approx_eq!(g, 0.0, 1e-3, "stopped short, started at {gap}");
+        assert_approx_eq!(v, 0.0, 1e-3, "still moving");
+    }
+}
+
+#[test]
+fn box_at_rest_within_speculative_distance_stays_put() {
+    for (g, v) in drift_into_wall(0.03, 0.0) {
+        assert_approx_eq!(g, 0.03, 1e-6);
+        assert_eq!(v, 0.0);
+    }
+}
+
+#[test]
+fn pick_contact_finds_the_nearest_contact_point() {
+    let mut world = world(G);
+    world.add_ground(0.0, 0.5, 0.0);
+    for y in [0.5, 1.5] {
+        let block = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, y))
+            .box_shape(1.0, 1.0)
+            .build();
+        world.add(Box::new(block));
+    }
+    for _ in 0..30 {
+        world.step(DT);
+    }
+
+    // Near the right end of the seam between the two boxes.
+    let c = world
+        .pick_contact(Vec2::new(0.45, 1.05), 0.2)
+        .expect("a contact at the seam");
+    assert_eq!((c.body_a, c.body_b), (1, 2));
+    assert_approx_eq!(c.point, Vec2::new(0.5, 1.0), 0.05);
+    assert!(c.normal_impulse > 0.0, "the top box rests on it");
+    // Nothing within reach in mid-air.
+    assert!(world.pick_contact(Vec2::new(3.0, 3.0), 0.5).is_none());
+}
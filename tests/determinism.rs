@@ -0,0 +1,204 @@
+//! Headless determinism harness: the same scene stepped the same way must end in
+//! bit-identical states. Lockstep networking and regression fixtures rely on this.
+
+use tiny_physics_engine::core::{Integrator, RigidBody, WeldJoint, World};
+use tiny_physics_engine::forces::drag::LinearDrag;
+use tiny_physics_engine::forces::spring::Spring;
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+/// The `box_stack_stress` example scene, without the renderer.
+fn box_stack_scene() -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.solver.params.restitution = 0.1;
+    world.solver.params.friction = 0.8;
+    world.solver.params.velocity_iterations = 5;
+
+    let ground = RigidBody::box_xy(Vec2::new(0.0, -6.0), 0.0, 0.0, 30.0, 1.0);
+    world.add(Box::new(ground));
+
+    let box_w = 1.0;
+    let box_h = 0.5;
+    let rows = 14;
+    let gap = 0.02;
+    let start_y = -6.0 + 1.0 * 0.5 + box_h * 0.5 + 0.02;
+    for row in 0..rows {
+        let count = rows - row;
+        let y = start_y + (box_h + gap) * row as Scalar;
+        let row_width = count as Scalar * box_w + (count as Scalar - 1.0) * gap;
+        let x_start = -0.5 * row_width + 0.5 * box_w;
+        for i in 0..count {
+            let x = x_start + i as Scalar * (box_w + gap);
+            let mut rb = RigidBody::box_xy(Vec2::new(x, y), 0.0, 1.0, box_w, box_h);
+            rb.angle = (row as Scalar * 0.001) - 0.007;
+            world.add(Box::new(rb));
+        }
+    }
+    world
+}
+
+/// Bit pattern of a `Scalar`.
+#[cfg(not(feature = "f64"))]
+type Bits = u32;
+#[cfg(feature = "f64")]
+type Bits = u64;
+
+/// Bit patterns of every body's position, angle and velocities.
+fn state_bits(world: &World) -> Vec<[Bits; 6]> {
+    world
+        .entities
+        .iter()
+        .map(|e| {
+            [
+                e.pos().x.to_bits(),
+                e.pos().y.to_bits(),
+                e.angle().to_bits(),
+                e.vel().x.to_bits(),
+                e.vel().y.to_bits(),
+                e.omega().to_bits(),
+            ]
+        })
+        .collect()
+}
+
+fn run(steps: usize) -> Vec<[Bits; 6]> {
+    let mut world = box_stack_scene();
+    for _ in 0..steps {
+        world.step(1.0 / 30.0);
+    }
+    state_bits(&world)
+}
+
+#[test]
+fn box_stack_is_bitwise_deterministic() {
+    let first = run(1000);
+    let second = run(1000);
+    assert_eq!(first.len(), second.len());
+    for (i, (a, b)) in first.iter().zip(&second).enumerate() {
+        assert_eq!(a, b, "body {i} diverged between runs");
+    }
+}
+
+#[test]
+fn incremental_broad_phase_matches_full_sort() {
+    let mut sorted = box_stack_scene();
+    let mut incremental = box_stack_scene();
+    incremental.set_incremental_broad_phase(true);
+    // The pyramid collapses, so bodies cross each other's intervals along the way.
+    for step in 0..600 {
+        sorted.step(1.0 / 30.0);
+        incremental.step(1.0 / 30.0);
+        assert_eq!(
+            state_bits(&sorted),
+            state_bits(&incremental),
+            "diverged at step {step}"
+        );
+    }
+}
+
+#[test]
+fn static_bvh_matches_full_sort() {
+    let mut sorted = box_stack_scene();
+    let mut with_bvh = box_stack_scene();
+    with_bvh.rebuild_static_bvh();
+    // The BVH lists the pyramid's pairs with the ground after the box-on-box ones. The
+    // boxes are all coupled, so solving the contacts in that order would change the result.
+    for step in 0..300 {
+        sorted.step(1.0 / 30.0);
+        with_bvh.step(1.0 / 30.0);
+        assert_eq!(
+            state_bits(&sorted),
+            state_bits(&with_bvh),
+            "diverged at step {step}"
+        );
+    }
+}
+
+/// Ground plus a slightly staggered, tilted four-box stack, its boxes added bottom-up or
+/// top-down.
+fn one_stack(top_down: bool) -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        20.0,
+        1.0,
+    )));
+    let rows: Vec<usize> = if top_down {
+        (0..4).rev().collect()
+    } else {
+        (0..4).collect()
+    };
+    for row in rows {
+        let y = 0.5 + row as Scalar * 1.02;
+        let mut rb = RigidBody::box_xy(Vec2::new(0.05 * row as Scalar, y), 0.0, 1.0, 1.0, 1.0);
+        rb.angle = 0.01;
+        world.add(Box::new(rb));
+    }
+    world
+}
+
+/// Every box touches the ones above and below it, so adding them in the other order
+/// renumbers every contact pair.
+#[test]
+fn stack_added_in_either_order_ends_alike() {
+    let mut bottom_up = one_stack(false);
+    let mut top_down = one_stack(true);
+    for step in 0..300 {
+        bottom_up.step(1.0 / 60.0);
+        top_down.step(1.0 / 60.0);
+        let (a, b) = (state_bits(&bottom_up), state_bits(&top_down));
+        assert_eq!(a[0], b[0], "ground moved at step {step}");
+        // Box `row` is body `1 + row` bottom-up and body `4 - row` top-down.
+        for row in 0..4 {
+            assert_eq!(a[1 + row], b[4 - row], "box {row} diverged at step {step}");
+        }
+    }
+}
+
+/// The box stack with a weld in the bottom row, a disabled drag and a spring that pulls
+/// the top box off and snaps under its weight.
+fn rigged_box_stack() -> World {
+    let mut world = box_stack_scene();
+    let top = world.entities.len() - 1;
+    let (a, b) = (&*world.entities[1], &*world.entities[2]);
+    let weld = WeldJoint::new(a, b, 1, 2, (*a.pos() + *b.pos()) * 0.5);
+    world.add_joint(weld);
+    let drag = world.add_force(Box::new(LinearDrag { k: 5.0 }));
+    world.set_force_enabled(drag, false);
+    let anchor = *world.entities[top].pos() + Vec2::new(2.0, 0.5);
+    let spring = Spring::to_anchor(top, anchor, 10.0, 1.0, 1.0).with_max_strain(1.2);
+    world.add_force(Box::new(spring));
+    world
+}
+
+#[test]
+fn restored_world_steps_like_the_original() {
+    let mut world = rigged_box_stack();
+    for _ in 0..30 {
+        world.step(1.0 / 30.0);
+    }
+    let state = world.checkpoint();
+    let mut original = Vec::new();
+    for _ in 0..300 {
+        world.step(1.0 / 30.0);
+        original.push((state_bits(&world), world.removed_forces.clone()));
+    }
+    assert!(
+        original.iter().any(|(_, removed)| !removed.is_empty()),
+        "the spring didn't snap after the checkpoint"
+    );
+
+    // Back into the same world, and into a fresh copy of the scene.
+    let mut fresh = rigged_box_stack();
+    fresh.step(1.0 / 60.0);
+    for restored in [&mut world, &mut fresh] {
+        restored.restore(&state);
+        for (step, expected) in original.iter().enumerate() {
+            restored.step(1.0 / 30.0);
+            let actual = (state_bits(restored), restored.removed_forces.clone());
+            assert!(actual == *expected, "diverged at step {step}");
+        }
+    }
+}
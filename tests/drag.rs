@@ -0,0 +1,150 @@
+//! Drag generators: `RegionDrag` inside a zone and `AngularDrag` on spinning bodies.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::collision::Aabb;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::forces::drag::{AngularDrag, RegionDrag};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// Drag force on unit boxes centered at each of `xs` (at y = 5), all moving right at 1 m/s,
+/// in a 10 x 10 water zone with its corner at the origin and `k = 2`.
+fn region_drag_forces(xs: &[Scalar]) -> Vec<Scalar> {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    for &x in xs {
+        let body = RigidBodyBuilder::new()
+            .position(Vec2::new(x, 5.0))
+            .velocity(Vec2::new(1.0, 0.0))
+            .box_shape(1.0, 1.0)
+            .build();
+        world.add(Box::new(body));
+    }
+    world.add_force(Box::new(RegionDrag {
+        aabb: Aabb::new(Vec2::zero(), Vec2::new(10.0, 10.0)),
+        k: 2.0,
+    }));
+    world.step(DT);
+    world.entities.iter().map(|e| e.force().x).collect()
+}
+
+#[test]
+fn region_drag_acts_only_inside() {
+    let forces = region_drag_forces(&[5.0, 20.0, -3.0]);
+    assert_approx_eq!(forces[0], -2.0, 1e-5, "inside: {}", forces[0]);
+    assert_eq!(forces[1], 0.0, "outside");
+    assert_eq!(forces[2], 0.0, "outside");
+}
+
+#[test]
+fn region_drag_scales_with_the_part_inside() {
+    // Half in, a quarter in, and just touching the edge from outside.
+    let forces = region_drag_forces(&[10.0, 10.25, 10.5]);
+    assert_approx_eq!(forces[0], -1.0, 1e-5, "half in: {}", forces[0]);
+    assert_approx_eq!(forces[1], -0.5, 1e-5, "quarter in: {}", forces[1]);
+    assert_eq!(forces[2], 0.0, "touching");
+}
+
+#[test]
+fn boat_slows_down_in_the_water_only() {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    let boat = RigidBodyBuilder::new()
+        .position(Vec2::new(-5.0, 5.0))
+        .velocity(Vec2::new(4.0, 0.0))
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(boat));
+    world.add_force(Box::new(RegionDrag {
+        aabb: Aabb::new(Vec2::zero(), Vec2::new(10.0, 10.0)),
+        k: 2.0,
+    }));
+    // Coasting up to the water at full speed.
+    for _ in 0..60 {
+        world.step(DT);
+    }
+    assert_eq!(world.entities[0].vel().x, 4.0);
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    let vx = world.entities[0].vel().x;
+    assert!(vx > 0.0 && vx < 1.0, "still going {vx} m/s");
+}
+
+/// Two spinning boxes, 1 x 1 and 3 x 3 (both 1 kg, 5 rad/s), no gravity.
+fn spinning_boxes(drag: AngularDrag) -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    for (x, size) in [(-5.0, 1.0), (5.0, 3.0)] {
+        let body = RigidBodyBuilder::new()
+            .position(Vec2::new(x, 0.0))
+            .angular_velocity(5.0)
+            .box_shape(size, size)
+            .build();
+        world.add(Box::new(body));
+    }
+    world.add_force(Box::new(drag));
+    world
+}
+
+#[test]
+fn angular_drag_spins_boxes_down() {
+    let mut world = spinning_boxes(AngularDrag {
+        k: 0.1,
+        scale_by_radius: false,
+    });
+    world.step(DT);
+    // -k * omega, whatever the size.
+    for e in &world.entities {
+        assert_approx_eq!(e.torque(), -0.5, 1e-5, "torque {}", e.torque());
+        assert_approx_eq!(*e.force(), Vec2::zero(), 0.0);
+    }
+    for _ in 0..1200 {
+        world.step(DT);
+    }
+    // The small box has the least inertia, so it stops first.
+    let (small, big) = (world.entities[0].omega(), world.entities[1].omega());
+    assert_approx_eq!(small, 0.0, 1e-3, "small box still spinning");
+    assert!(big > 0.0 && big < 5.0, "big box at {big} rad/s");
+}
+
+#[test]
+fn angular_drag_scaled_by_radius_slows_big_bodies_more() {
+    let mut world = spinning_boxes(AngularDrag {
+        k: 0.1,
+        scale_by_radius: true,
+    });
+    world.step(DT);
+    // Characteristic radius: half the diagonal.
+    let radius = |size: Scalar| size * Scalar::sqrt(2.0) / 2.0;
+    let (small, big) = (world.entities[0].torque(), world.entities[1].torque());
+    assert_approx_eq!(small, -0.5 * radius(1.0), 1e-5, "torque {small}");
+    assert_approx_eq!(big, -0.5 * radius(3.0), 1e-5, "torque {big}");
+}
+
+#[test]
+fn angular_drag_skips_bodies_that_cannot_spin() {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    let locked = RigidBodyBuilder::new()
+        .box_shape(1.0, 1.0)
+        .fixed_rotation(true)
+        .build();
+    world.add(Box::new(locked));
+    // Static, but spinning on its own like a kinematic windmill.
+    let windmill = RigidBodyBuilder::new()
+        .position(Vec2::new(3.0, 0.0))
+        .mass(0.0)
+        .angular_velocity(2.0)
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(windmill));
+    world.add_force(Box::new(AngularDrag {
+        k: 1.0,
+        scale_by_radius: true,
+    }));
+    for _ in 0..60 {
+        world.step(DT);
+    }
+    assert_eq!(world.entities[0].torque(), 0.0);
+    assert_eq!(world.entities[1].torque(), 0.0);
+    assert_eq!(world.entities[1].omega(), 2.0);
+}
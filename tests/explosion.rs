@@ -0,0 +1,77 @@
+//! `World::apply_radial_impulse`: explosions pushing bodies away from a point.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Falloff, Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+/// 1 kg balls of radius 0.5 at each of `centers`, plus a static 1 x 1 box at (-4, 0) as the
+/// last body; no gravity.
+fn blast_site(centers: &[Vec2]) -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    for &c in centers {
+        world.add(Box::new(
+            RigidBodyBuilder::new().position(c).circle(0.5).build(),
+        ));
+    }
+    let wall = RigidBodyBuilder::new()
+        .position(Vec2::new(-4.0, 0.0))
+        .mass(0.0)
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(wall));
+    world
+}
+
+fn speed(world: &World, i: usize) -> Scalar {
+    world.entities[i].vel().length()
+}
+
+#[test]
+fn linear_falloff_weakens_with_distance() {
+    let mut world = blast_site(&[
+        Vec2::new(3.0, 0.0),
+        Vec2::new(0.0, 6.0),
+        Vec2::new(12.0, 0.0),
+    ]);
+    world.apply_radial_impulse(Vec2::zero(), 10.0, 10.0, Falloff::Linear);
+    // The nearest AABB points are 2.5 and 5.5 away: 75% and 45% of the strength.
+    assert_approx_eq!(world.entities[0].vel().x, 7.5, 1e-5);
+    assert_approx_eq!(world.entities[1].vel().y, 4.5, 1e-5);
+    assert_approx_eq!(world.entities[0].vel().y, 0.0, 1e-6);
+    assert_approx_eq!(world.entities[1].vel().x, 0.0, 1e-6);
+    // Out of range.
+    assert_eq!(speed(&world, 2), 0.0);
+    // Pushed through the center of mass: no spin.
+    assert_eq!(world.entities[0].omega(), 0.0);
+}
+
+#[test]
+fn inverse_square_falloff_weakens_with_distance() {
+    let mut world = blast_site(&[Vec2::new(1.5, 0.0), Vec2::new(3.5, 0.0)]);
+    world.apply_radial_impulse(Vec2::zero(), 3.0, 10.0, Falloff::InverseSquare);
+    // 2 / (1 + (d / 3)^2) - 1 at d = 1, and nothing at d = radius.
+    assert_approx_eq!(speed(&world, 0), 8.0, 1e-5);
+    assert_eq!(speed(&world, 1), 0.0);
+}
+
+#[test]
+fn inverse_square_falloff_scales_with_the_radius() {
+    // The same blast a hundred times larger: the ball a third of the radius away gets the
+    // same push.
+    for scale in [1.0, 100.0] {
+        let mut world = blast_site(&[Vec2::new(scale + 0.5, 0.0)]);
+        world.apply_radial_impulse(Vec2::zero(), 3.0 * scale, 10.0, Falloff::InverseSquare);
+        assert_approx_eq!(speed(&world, 0), 8.0, 1e-4, "scale {scale}");
+    }
+}
+
+#[test]
+fn static_bodies_are_not_pushed() {
+    let mut world = blast_site(&[Vec2::new(2.0, 0.0)]);
+    world.apply_radial_impulse(Vec2::new(-2.0, 0.0), 10.0, 10.0, Falloff::Linear);
+    world.apply_radial_impulse_with_spin(Vec2::new(-3.0, 2.0), 10.0, 10.0, Falloff::Linear);
+    assert!(speed(&world, 0) > 0.0);
+    assert_eq!(speed(&world, 1), 0.0);
+    assert_eq!(world.entities[1].omega(), 0.0);
+}
@@ -0,0 +1,67 @@
+//! Bodies with `fixed_rotation` translate and collide but never turn.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, PhysicalEntity, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// An upright 1 x 2 crate standing on the ground, hit high on its side by a fast ball.
+fn crate_hit_off_center(fixed: bool) -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    let upright = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 1.0))
+        .box_shape(1.0, 2.0)
+        .fixed_rotation(fixed)
+        .build();
+    world.add(Box::new(upright));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(-3.0, 1.8))
+        .velocity(Vec2::new(12.0, 0.0))
+        .mass(2.0)
+        .circle(0.2)
+        .build();
+    world.add(Box::new(ball));
+    world
+}
+
+#[test]
+fn locked_crate_slides_but_stays_level() {
+    let mut world = crate_hit_off_center(true);
+    for _ in 0..120 {
+        world.step(DT);
+        assert_eq!(world.entities[1].angle(), 0.0);
+        assert_eq!(world.entities[1].omega(), 0.0);
+    }
+    let pos = *world.entities[1].pos();
+    assert!(pos.x > 0.5, "crate didn't slide: {pos:?}");
+    assert_approx_eq!(pos.y, 1.0, 0.02, "crate left the ground: {pos:?}");
+}
+
+#[test]
+fn unlocked_crate_tips_over() {
+    let mut world = crate_hit_off_center(false);
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    let angle = world.entities[1].angle().abs();
+    assert!(angle > 1.0, "crate only tilted {angle} rad");
+}
+
+#[test]
+fn lock_survives_a_mass_change() {
+    let mut body = RigidBodyBuilder::new()
+        .box_shape(1.0, 2.0)
+        .fixed_rotation(true)
+        .build();
+    body.set_mass(5.0);
+    assert_eq!(body.inv_inertia(), 0.0);
+    assert!(body.inv_mass() > 0.0);
+
+    // Unlocking gives back the inertia of the new mass.
+    body.set_fixed_rotation(false);
+    let expected = 5.0 * (1.0 + 4.0) / 12.0;
+    assert_approx_eq!(body.inv_inertia(), 1.0 / expected, 1e-5);
+}
@@ -0,0 +1,80 @@
+//! Removing and temporarily disabling force generators.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, Particle, World};
+use tiny_physics_engine::forces::ForceEntry;
+use tiny_physics_engine::forces::drag::LinearDrag;
+use tiny_physics_engine::forces::spring::Spring;
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// One particle at rest at the origin, no gravity.
+fn world() -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    world.add(Box::new(Particle::new(Vec2::zero(), Vec2::zero(), 1.0)));
+    world
+}
+
+fn mouse_spring(target: Vec2) -> Box<Spring> {
+    Box::new(Spring::to_anchor(0, target, 50.0, 1.0, 0.0))
+}
+
+#[test]
+fn disabled_generator_applies_no_force() {
+    let mut world = world();
+    let spring = world.add_force(mouse_spring(Vec2::new(5.0, 0.0)));
+    world.set_force_enabled(spring, false);
+    assert!(!world.force_enabled(spring));
+    assert_eq!(world.spring_potential_energy(), 0.0);
+
+    for _ in 0..30 {
+        world.step(DT);
+    }
+    assert_eq!(world.entities[0].pos().x, 0.0);
+
+    world.set_force_enabled(spring, true);
+    world.step(DT);
+    assert!(world.entities[0].vel().x > 0.0);
+    assert_eq!(world.forces.len(), 1);
+}
+
+#[test]
+fn removing_shifts_later_generators_and_their_flags() {
+    let mut world = world();
+    world.add_force(mouse_spring(Vec2::new(5.0, 0.0)));
+    // Pushed directly rather than through `add_force`: starts enabled.
+    world
+        .forces
+        .push(ForceEntry::new(mouse_spring(Vec2::new(0.0, 5.0))));
+    world.add_force(Box::new(LinearDrag { k: 0.1 }));
+    assert!(world.force_enabled(1));
+    world.set_force_enabled(1, false);
+
+    assert!(world.remove_force(0).is_some());
+    assert!(world.remove_force(5).is_none());
+    assert_eq!(world.forces.len(), 2);
+    assert!(!world.force_enabled(0), "flag didn't follow its generator");
+    assert!(world.force_enabled(1));
+    assert!(!world.force_enabled(2));
+
+    // Only the drag is active now, and the particle is at rest.
+    world.step(DT);
+    assert_eq!(world.entities[0].vel().length(), 0.0);
+}
+
+#[test]
+fn released_mouse_spring_stops_pulling() {
+    let mut world = world();
+    let mouse = world.add_force(mouse_spring(Vec2::new(0.0, 2.0)));
+    for _ in 0..10 {
+        world.step(DT);
+    }
+    let released = world.remove_force(mouse);
+    assert!(released.is_some());
+
+    let vel = *world.entities[0].vel();
+    world.step(DT);
+    assert_approx_eq!(*world.entities[0].vel(), vel, 1e-6);
+}
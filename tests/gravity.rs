@@ -0,0 +1,37 @@
+//! Runtime gravity changes.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, Particle, World};
+use tiny_physics_engine::math::vec::Vec2;
+
+#[test]
+fn flipping_gravity_reverses_acceleration() {
+    let dt = 1.0 / 60.0;
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(Particle::new(Vec2::zero(), Vec2::zero(), 1.0)));
+
+    let vy = |world: &World| world.entities[0].vel().y;
+
+    let before = vy(&world);
+    world.step(dt);
+    let falling = (vy(&world) - before) / dt;
+    assert_approx_eq!(falling, -9.81, 1e-3);
+
+    world.set_gravity(-world.gravity());
+    let before = vy(&world);
+    world.step(dt);
+    let flipped = (vy(&world) - before) / dt;
+    assert_approx_eq!(flipped, 9.81, 1e-3);
+}
+
+#[test]
+fn applied_force_is_readable_after_step() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(Particle::new(Vec2::zero(), Vec2::zero(), 0.5)));
+
+    for _ in 0..3 {
+        world.step(1.0 / 60.0);
+        // Weight of the 2 kg particle, not accumulated across steps.
+        assert_approx_eq!(*world.entities[0].force(), Vec2::new(0.0, -19.62), 1e-4);
+    }
+}
@@ -0,0 +1,150 @@
+//! Collider mass properties against the analytic formulas, and hand-set inertia.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Collider2D, Integrator, RigidBody, RigidBodyBuilder, World};
+use tiny_physics_engine::math::mat::Mat2;
+use tiny_physics_engine::math::transform::Transform2D;
+use tiny_physics_engine::math::vec::Vec2;
+use tiny_physics_engine::math::{Scalar, consts};
+
+fn box_shape(w: Scalar, h: Scalar) -> Collider2D {
+    Collider2D::Box {
+        half_extents: Vec2::new(0.5 * w, 0.5 * h),
+    }
+}
+
+/// Analytic inertia of a solid `w` x `h` box about its center.
+fn box_inertia(mass: Scalar, w: Scalar, h: Scalar) -> Scalar {
+    mass * (w * w + h * h) / 12.0
+}
+
+#[test]
+fn square_built_from_quarters_matches_box_formula() {
+    // A 2 x 2 square made of four 1 x 1 boxes around the origin.
+    let parts = [(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)].map(|(x, y)| {
+        (
+            Transform2D::from_translation(Vec2::new(x, y)),
+            box_shape(1.0, 1.0),
+        )
+    });
+    let square = Collider2D::Compound {
+        parts: Vec::from(parts),
+    };
+
+    assert!(square.centroid().length() < 1e-6);
+    assert_approx_eq!(
+        square.inertia_about_center(3.0),
+        box_inertia(3.0, 2.0, 2.0),
+        1e-5
+    );
+}
+
+#[test]
+fn offset_box_uses_parallel_axis_shift() {
+    let offset = Vec2::new(1.5, -2.0);
+    let shape = box_shape(1.0, 0.5).with_offset(offset);
+    let mass = 2.0;
+
+    let c = shape.centroid();
+    assert_approx_eq!(c, offset, 1e-6);
+    assert_approx_eq!(
+        shape.inertia_about_centroid(mass),
+        box_inertia(mass, 1.0, 0.5),
+        1e-5,
+    );
+    assert_approx_eq!(
+        shape.inertia_about_center(mass),
+        box_inertia(mass, 1.0, 0.5) + mass * offset.length_squared(),
+        1e-5,
+    );
+}
+
+#[test]
+fn nested_compound_is_shifted_from_its_own_centroid() {
+    // An offset box nested inside a rotated, translated compound part.
+    let inner = box_shape(1.0, 1.0).with_offset(Vec2::new(1.0, 0.0));
+    let outer = Collider2D::Compound {
+        parts: vec![(
+            Transform2D::new(Mat2::rotation(consts::FRAC_PI_2), Vec2::new(0.0, 1.0)),
+            inner,
+        )],
+    };
+    let mass = 1.0;
+
+    // The box ends up centered at (0, 2).
+    let c = outer.centroid();
+    assert_approx_eq!(c, Vec2::new(0.0, 2.0), 1e-5);
+    assert_approx_eq!(
+        outer.inertia_about_center(mass),
+        box_inertia(mass, 1.0, 1.0) + mass * 4.0,
+        1e-5,
+    );
+}
+
+#[test]
+fn inertia_override_survives_mass_and_shape_changes() {
+    let mut flywheel = RigidBody::circle(Vec2::zero(), 0.0, 2.0, 0.1).with_inertia(5.0);
+    assert_approx_eq!(flywheel.inertia, 5.0, 1e-5);
+    assert_approx_eq!(flywheel.inv_inertia, 0.2, 1e-5);
+
+    flywheel.set_mass(4.0);
+    flywheel.set_radius(0.2);
+    assert_approx_eq!(flywheel.inertia, 5.0, 1e-5);
+    assert_approx_eq!(flywheel.inv_mass, 0.25, 1e-5);
+
+    flywheel.set_mass(0.0);
+    assert_eq!(flywheel.inv_inertia, 0.0, "static flywheel can still turn");
+    flywheel.set_mass(4.0);
+    assert_approx_eq!(flywheel.inertia, 5.0, 1e-5);
+
+    flywheel.set_inertia(None);
+    assert_approx_eq!(flywheel.inertia, 0.5 * 4.0 * 0.2 * 0.2, 1e-5);
+}
+
+#[test]
+fn builder_takes_inertia_or_its_inverse() {
+    let derived = RigidBodyBuilder::new()
+        .mass(3.0)
+        .box_shape(1.0, 2.0)
+        .build();
+    assert_eq!(derived.inertia_override, None);
+    assert_approx_eq!(derived.inertia, box_inertia(3.0, 1.0, 2.0), 1e-5);
+
+    let by_inertia = RigidBodyBuilder::new()
+        .box_shape(1.0, 2.0)
+        .inertia(4.0)
+        .build();
+    let by_inverse = RigidBodyBuilder::new()
+        .box_shape(1.0, 2.0)
+        .inv_inertia(0.25)
+        .build();
+    for body in [&by_inertia, &by_inverse] {
+        assert_eq!(body.inertia_override, Some(4.0));
+        assert_approx_eq!(body.inv_inertia, 0.25, 1e-5);
+    }
+}
+
+#[test]
+fn zero_inertia_body_lands_corner_first_without_turning() {
+    for body in [
+        RigidBodyBuilder::new().inertia(0.0),
+        RigidBodyBuilder::new().inv_inertia(0.0),
+    ] {
+        let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+        world.add_ground(0.0, 0.5, 0.0);
+        let tilted = body
+            .position(Vec2::new(0.0, 1.5))
+            .angle(0.3)
+            .box_shape(1.0, 1.0)
+            .build();
+        assert_eq!((tilted.inertia, tilted.inv_inertia), (0.0, 0.0));
+        world.add(Box::new(tilted));
+        for _ in 0..120 {
+            world.step(1.0 / 60.0);
+        }
+        let block = &world.entities[1];
+        assert!(block.inv_inertia().is_finite());
+        assert_eq!((block.angle(), block.omega()), (0.3, 0.0));
+        assert!(block.pos().y < 1.0, "never landed: {:?}", block.pos());
+    }
+}
@@ -0,0 +1,66 @@
+//! Island labels: bodies linked through contacts or joints, but not through static bodies.
+
+use tiny_physics_engine::core::{Integrator, RigidBody, WeldJoint, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+fn unit_box(x: Scalar, y: Scalar) -> Box<RigidBody> {
+    Box::new(RigidBody::box_xy(Vec2::new(x, y), 0.0, 1.0, 1.0, 1.0))
+}
+
+#[test]
+fn stacks_on_shared_ground_are_separate_islands() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        20.0,
+        1.0,
+    )));
+    // Two stacks of two, far apart: bodies 1-2 and 3-4.
+    for x in [-4.0, 4.0] {
+        world.add(unit_box(x, 0.5));
+        world.add(unit_box(x, 1.5));
+    }
+    // A lone box in the air.
+    world.add(unit_box(0.0, 10.0));
+    assert_eq!(world.island_of(1), None, "no islands before the first step");
+
+    for _ in 0..10 {
+        world.step(1.0 / 60.0);
+    }
+
+    assert_eq!(
+        world.island_of(0),
+        None,
+        "static ground belongs to no island"
+    );
+    assert_eq!(world.island_of(1), Some(1));
+    assert_eq!(world.island_of(2), Some(1));
+    assert_eq!(world.island_of(3), Some(3));
+    assert_eq!(world.island_of(4), Some(3));
+    assert_eq!(world.island_of(5), Some(5));
+    assert_eq!(world.island_of(6), None, "out of range");
+}
+
+#[test]
+fn joints_join_islands() {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    for x in [0.0, 3.0, 6.0] {
+        world.add(unit_box(x, 0.0));
+    }
+    let weld = WeldJoint::new(
+        &*world.entities[0],
+        &*world.entities[2],
+        0,
+        2,
+        Vec2::new(3.0, 0.0),
+    );
+    world.add_joint(weld);
+    world.step(1.0 / 60.0);
+
+    assert_eq!(world.island_of(0), Some(0));
+    assert_eq!(world.island_of(1), Some(1));
+    assert_eq!(world.island_of(2), Some(0));
+}
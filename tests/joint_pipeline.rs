@@ -0,0 +1,161 @@
+//! The order in which the solver drives a joint through the `Constraint` phases, and what
+//! the relax phase leaves of its position correction.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{
+    BodyStore, Constraint, Integrator, PhysicalEntity, RigidBody, SolverParams, WeldJoint, World,
+};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+/// A joint that applies nothing and logs every call.
+#[derive(Debug, Clone)]
+struct NoOpJoint {
+    log: Rc<RefCell<Vec<&'static str>>>,
+}
+
+impl Constraint for NoOpJoint {
+    fn bodies(&self) -> (usize, usize) {
+        (0, 1)
+    }
+    fn prepare(&mut self, _bodies: &BodyStore, _dt: Scalar, _dt_ratio: Scalar) {
+        self.log.borrow_mut().push("prepare");
+    }
+    fn warm_start(&self, _bodies: &mut BodyStore) {
+        self.log.borrow_mut().push("warm_start");
+    }
+    fn solve_velocity(
+        &mut self,
+        _bodies: &mut BodyStore,
+        _dt: Scalar,
+        _params: &SolverParams,
+        use_bias: bool,
+    ) {
+        self.log
+            .borrow_mut()
+            .push(if use_bias { "biased" } else { "relax" });
+    }
+    fn solve_position(&mut self, _entities: &mut [Box<dyn PhysicalEntity>]) {
+        self.log.borrow_mut().push("position");
+    }
+    fn reset_impulses(&mut self) {}
+    fn clone_box(&self) -> Box<dyn Constraint> {
+        Box::new(self.clone())
+    }
+}
+
+fn world_with_no_op_joint(
+    iterations: usize,
+    relax: usize,
+) -> (World, Rc<RefCell<Vec<&'static str>>>) {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    for x in [0.0, 2.0] {
+        world.add(Box::new(RigidBody::box_xy(
+            Vec2::new(x, 0.0),
+            0.0,
+            1.0,
+            1.0,
+            1.0,
+        )));
+    }
+    world.solver.params.velocity_iterations = iterations;
+    world.solver.params.relax_iterations = relax;
+    let log = Rc::new(RefCell::new(Vec::new()));
+    world.add_joint(NoOpJoint { log: log.clone() });
+    (world, log)
+}
+
+#[test]
+fn phases_run_in_order() {
+    let (mut world, log) = world_with_no_op_joint(2, 1);
+    world.step(1.0 / 60.0);
+    assert_eq!(
+        *log.borrow(),
+        [
+            "prepare",
+            "warm_start",
+            "biased",
+            "biased",
+            "relax",
+            "position"
+        ]
+    );
+}
+
+#[test]
+fn substeps_repeat_warm_start_and_iterations() {
+    let (mut world, log) = world_with_no_op_joint(1, 1);
+    world.solver.params.substeps = 2;
+    world.step(1.0 / 60.0);
+    assert_eq!(
+        *log.borrow(),
+        [
+            "prepare",
+            "warm_start",
+            "biased",
+            "relax",
+            "warm_start",
+            "biased",
+            "relax",
+            "position"
+        ]
+    );
+}
+
+#[test]
+fn joints_are_read_back_by_type() {
+    let (mut world, _) = world_with_no_op_joint(1, 0);
+    let weld = WeldJoint::new(
+        &*world.entities[0],
+        &*world.entities[1],
+        0,
+        1,
+        Vec2::new(1.0, 0.0),
+    );
+    let index = world.add_joint(weld);
+    world.step(1.0 / 60.0);
+
+    assert!(world.joint::<NoOpJoint>(0).is_some());
+    assert!(world.joint::<WeldJoint>(0).is_none());
+    assert_eq!(world.joint::<WeldJoint>(index).unwrap().bodies(), (0, 1));
+    // Checkpoints clone the joints.
+    let state = world.checkpoint();
+    world.joints.clear();
+    world.restore(&state);
+    assert_eq!(world.joints.len(), 2);
+}
+
+#[test]
+fn relax_pass_keeps_the_joint_position_correction() {
+    // A box welded to a static one, then knocked out of place: the relax iterations take
+    // the joint's bias back out of the velocity, but the correction still moves the box.
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    for (x, mass) in [(0.0, 0.0), (1.0, 1.0)] {
+        world.add(Box::new(RigidBody::box_xy(
+            Vec2::new(x, 0.0),
+            0.0,
+            mass,
+            1.0,
+            1.0,
+        )));
+    }
+    let weld = WeldJoint::new(
+        &*world.entities[0],
+        &*world.entities[1],
+        0,
+        1,
+        Vec2::new(0.5, 0.0),
+    );
+    world.add_joint(weld);
+    *world.entities[1].pos_mut() = Vec2::new(1.1, -0.1);
+    assert_eq!(world.solver.params.relax_iterations, 1);
+
+    for _ in 0..60 {
+        world.step(1.0 / 60.0);
+    }
+    let pos = *world.entities[1].pos();
+    assert_approx_eq!(pos, Vec2::new(1.0, 0.0), 1e-3, "joint still off");
+}
@@ -0,0 +1,64 @@
+//! `World::step` catches NaN/inf body state and repairs it instead of letting it spread.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// Two boxes stacked on the ground, settled.
+fn settled_stack() -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    for i in 0..2 {
+        let block = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 0.5 + i as Scalar))
+            .box_shape(1.0, 1.0)
+            .build();
+        world.add(Box::new(block));
+    }
+    for _ in 0..60 {
+        world.step(DT);
+    }
+    world
+}
+
+#[test]
+fn nan_velocity_is_reset() {
+    let mut world = settled_stack();
+    *world.entities[2].vel_mut() = Vec2::new(Scalar::NAN, 0.0);
+    world.step(DT);
+    assert_eq!(world.invalid_bodies, [2]);
+    assert!(world.entities[2].vel().x.is_finite());
+}
+
+#[test]
+fn nan_position_and_angle_go_back_to_the_last_finite_pose() {
+    for (x, angle) in [
+        (Scalar::NAN, 0.0),
+        (0.0, Scalar::NAN),
+        (Scalar::INFINITY, Scalar::NAN),
+    ] {
+        let mut world = settled_stack();
+        let pos = *world.entities[2].pos();
+        let was = world.entities[2].angle();
+        *world.entities[2].pos_mut() = pos + Vec2::new(x, 0.0);
+        *world.entities[2].angle_mut() += angle;
+        world.step(DT);
+
+        assert_eq!(world.invalid_bodies, [2]);
+        let e = &world.entities[2];
+        assert_approx_eq!(*e.pos(), pos, 0.01);
+        assert_approx_eq!(e.angle(), was, 0.01);
+        // Nothing spread to the box below.
+        for _ in 0..30 {
+            world.step(DT);
+        }
+        assert!(world.invalid_bodies.is_empty());
+        for e in &world.entities {
+            assert!(e.pos().x.is_finite() && e.pos().y.is_finite() && e.angle().is_finite());
+        }
+        assert!(world.entities[1].vel().length() < 0.05);
+    }
+}
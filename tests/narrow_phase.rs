@@ -0,0 +1,542 @@
+//! Contact normals point from A to B whichever way round a shape pair is detected.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::collision::narrow_phase;
+use tiny_physics_engine::core::collision::{ContactPoint, Manifold};
+use tiny_physics_engine::core::{
+    Collider2D, Integrator, Material, PhysicalEntity, RigidBody, RigidBodyBuilder, SimParams,
+    SolverParams, World,
+};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::mat::Mat2;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// The single manifold between `entities[0]` and `entities[1]`: its body A, and its normal
+/// turned to point from body 0 to body 1. Listing the pair either way round gives the same
+/// manifold, with its normal pointing from A to B.
+fn normal(entities: &[Box<dyn PhysicalEntity>]) -> (usize, Vec2) {
+    let detect = |pair| {
+        let manifolds = narrow_phase::detect(
+            entities,
+            &[pair],
+            SimParams::default(),
+            DT,
+            SolverParams::default().default_material(),
+        );
+        assert_eq!(manifolds.len(), 1, "expected one manifold for {pair:?}");
+        manifolds.into_iter().next().unwrap()
+    };
+    let (m, swapped) = (detect((0, 1)), detect((1, 0)));
+    assert_eq!((m.a, m.b), (swapped.a, swapped.b));
+    assert_approx_eq!(m.normal, swapped.normal, 0.0);
+    let centers = *entities[m.b].pos() - *entities[m.a].pos();
+    assert!(
+        centers.dot(m.normal) > 0.0,
+        "normal {:?} of ({}, {}) points from B to A",
+        m.normal,
+        m.a,
+        m.b
+    );
+    (m.a, if m.a == 0 { m.normal } else { -m.normal })
+}
+
+/// `scene(1.0)` and `scene(-1.0)`, the same scene turned half a turn, have their bodies the
+/// other way round as A and B, so between them each shape is detected as A once.
+fn assert_normals_opposite(scene: impl Fn(Scalar) -> Vec<Box<dyn PhysicalEntity>>) {
+    let (a, n) = normal(&scene(1.0));
+    let (turned_a, m) = normal(&scene(-1.0));
+    assert_ne!(a, turned_a, "body {a} is A both times");
+    // Half a turn reverses every direction.
+    assert_approx_eq!(n, -m, 1e-5, "normals are not opposite");
+}
+
+// Boxes look the same turned half a turn, so only the positions change sign.
+
+#[test]
+fn circle_box_normals_are_opposite() {
+    assert_normals_opposite(|s| {
+        vec![
+            Box::new(RigidBody::box_xy(Vec2::zero(), 0.3, 1.0, 2.0, 1.0)),
+            Box::new(RigidBody::circle(Vec2::new(1.2, 0.4) * s, 0.0, 1.0, 0.5)),
+        ]
+    });
+}
+
+#[test]
+fn circle_inside_box_normals_are_opposite() {
+    assert_normals_opposite(|s| {
+        vec![
+            Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 1.0, 2.0, 1.0)),
+            Box::new(RigidBody::circle(Vec2::new(-0.2, 0.3) * s, 0.0, 1.0, 0.1)),
+        ]
+    });
+}
+
+#[test]
+fn box_box_normals_are_opposite() {
+    assert_normals_opposite(|s| {
+        vec![
+            Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 1.0, 2.0, 1.0)),
+            Box::new(RigidBody::box_xy(
+                Vec2::new(0.4, 0.9) * s,
+                0.2,
+                1.0,
+                1.0,
+                1.0,
+            )),
+        ]
+    });
+}
+
+#[test]
+fn circle_circle_normals_are_opposite() {
+    assert_normals_opposite(|s| {
+        vec![
+            Box::new(RigidBody::circle(Vec2::zero(), 0.0, 1.0, 0.5)),
+            Box::new(RigidBody::circle(Vec2::new(-0.6, 0.5) * s, 0.0, 1.0, 0.4)),
+        ]
+    });
+}
+
+/// Number of points in the single manifold between `entities[0]` and `entities[1]`.
+fn contact_count(entities: &[Box<dyn PhysicalEntity>]) -> usize {
+    let manifolds = narrow_phase::detect(
+        entities,
+        &[(0, 1)],
+        SimParams::default(),
+        DT,
+        SolverParams::default().default_material(),
+    );
+    assert_eq!(manifolds.len(), 1, "expected one manifold");
+    manifolds[0].points.len()
+}
+
+#[test]
+fn stacked_aligned_boxes_touch_at_two_points() {
+    for overlap in [0.01, 0.0, -0.01] {
+        let entities: Vec<Box<dyn PhysicalEntity>> = vec![
+            Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 1.0, 1.0, 0.5)),
+            Box::new(RigidBody::box_xy(
+                Vec2::new(0.1, 0.5 - overlap),
+                0.0,
+                1.0,
+                1.0,
+                0.5,
+            )),
+        ];
+        assert_eq!(contact_count(&entities), 2, "overlap {overlap}");
+    }
+}
+
+#[test]
+fn slightly_tilted_resting_box_keeps_two_points() {
+    // One end touches, the other lifts just past the default speculative distance (0.05).
+    let tilt: Scalar = 0.06;
+    // Lowest corner exactly on the surface; the far one ends up sin(tilt) ≈ 0.06 above it.
+    let lift = 0.5 * tilt.sin() + 0.25 * tilt.cos();
+    let entities: Vec<Box<dyn PhysicalEntity>> = vec![
+        Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 0.0, 4.0, 0.5)),
+        Box::new(RigidBody::box_xy(
+            Vec2::new(0.0, 0.25 + lift),
+            tilt,
+            1.0,
+            1.0,
+            0.5,
+        )),
+    ];
+    assert_eq!(contact_count(&entities), 2);
+}
+
+fn flat_contact(xs: &[Scalar], penetrations: &[Scalar]) -> Manifold {
+    let points = xs
+        .iter()
+        .zip(penetrations)
+        .enumerate()
+        .map(|(id, (&x, &penetration))| ContactPoint {
+            point: Vec2::new(x, 0.0),
+            penetration,
+            id: id as u32,
+        })
+        .collect();
+    Manifold::new(0, 1, Vec2::new(0.0, 1.0), points, Material::new(0.0, 0.5))
+}
+
+fn kept_xs(m: &Manifold) -> Vec<Scalar> {
+    m.points.iter().map(|p| p.point.x).collect()
+}
+
+#[test]
+fn capped_wide_flat_contact_keeps_its_outermost_points() {
+    // A wide flat face on a floor, its points listed out of order, all equally deep. There
+    // is no polygon collider to clip one from, so the manifold is built by hand.
+    let mut m = flat_contact(&[0.0, -1.0, 2.0, -2.0, 1.0], &[0.01; 5]);
+    m.reduce_points(2);
+    assert_eq!(kept_xs(&m), [2.0, -2.0]);
+
+    let mut m = flat_contact(&[0.0, -1.0, 2.0, -2.0, 1.0], &[0.01; 5]);
+    m.reduce_points(3);
+    assert_eq!(kept_xs(&m), [0.0, 2.0, -2.0]);
+}
+
+#[test]
+fn capped_contact_keeps_the_deepest_point() {
+    let mut m = flat_contact(&[-2.0, -1.0, 0.0, 1.0, 2.0], &[0.0, 0.01, 0.05, 0.01, 0.0]);
+    m.reduce_points(1);
+    assert_eq!(kept_xs(&m), [0.0]);
+
+    let mut m = flat_contact(&[-2.0, -1.0, 0.0, 1.0, 2.0], &[0.0, 0.01, 0.05, 0.01, 0.0]);
+    m.reduce_points(2);
+    assert_eq!(m.points.len(), 2);
+    assert!(kept_xs(&m).contains(&0.0));
+    // A cap of 0 still keeps one point.
+    m.reduce_points(0);
+    assert_eq!(kept_xs(&m), [0.0]);
+}
+
+#[test]
+fn max_contact_points_caps_box_manifolds() {
+    let entities: Vec<Box<dyn PhysicalEntity>> = vec![
+        Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 0.0, 4.0, 0.5)),
+        Box::new(RigidBody::box_xy(Vec2::new(0.0, 0.49), 0.1, 1.0, 1.0, 0.5)),
+    ];
+    let params = SimParams {
+        max_contact_points: 1,
+        ..SimParams::default()
+    };
+    let manifolds = narrow_phase::detect(
+        &entities,
+        &[(0, 1)],
+        params,
+        DT,
+        SolverParams::default().default_material(),
+    );
+    let all = narrow_phase::detect(
+        &entities,
+        &[(0, 1)],
+        SimParams::default(),
+        DT,
+        SolverParams::default().default_material(),
+    );
+    assert_eq!(all[0].points.len(), 2);
+    assert_eq!(manifolds[0].points.len(), 1);
+    let deepest = all[0]
+        .points
+        .iter()
+        .map(|p| p.penetration)
+        .fold(Scalar::NEG_INFINITY, Scalar::max);
+    assert_eq!(manifolds[0].points[0].penetration, deepest);
+}
+
+#[test]
+fn deeply_overlapping_boxes_use_least_penetration_axis() {
+    // Overlap is 1.7 along x but only 0.9 along y: the normal must be +y.
+    let entities: Vec<Box<dyn PhysicalEntity>> = vec![
+        Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 1.0, 2.0, 1.0)),
+        Box::new(RigidBody::box_xy(Vec2::new(0.3, 0.1), 0.05, 1.0, 2.0, 1.0)),
+    ];
+    let (_, n) = normal(&entities);
+    assert!(n.y > 0.99, "normal {n:?}");
+}
+
+#[test]
+fn spawn_overlap_ejects_cleanly_along_minimal_axis() {
+    // Spawned mostly on top of each other: least overlap is along y.
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::zero(),
+        0.0,
+        1.0,
+        2.0,
+        1.0,
+    )));
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.3, 0.1),
+        0.05,
+        1.0,
+        2.0,
+        1.0,
+    )));
+
+    // Step until the contact is gone (`contacts` is empty until the first step).
+    for steps in 1.. {
+        world.step(1.0 / 60.0);
+        if world.contacts().next().is_none() {
+            break;
+        }
+        assert!(steps < 120, "still touching after {steps} steps");
+    }
+
+    let (a, b) = (&world.entities[0], &world.entities[1]);
+    let offset = *b.pos() - *a.pos();
+    assert!(offset.y > 0.95, "boxes still overlap: offset {offset:?}");
+    // Pushed apart vertically, not flung sideways. The two deep points are solved one after
+    // the other, so the first one kicks the box a little sideways.
+    let v = *b.vel() - *a.vel();
+    assert!(v.y > 0.0 && v.x.abs() < 0.25 * v.y, "separating at {v:?}");
+    assert_approx_eq!(offset.x, 0.3, 0.15 * offset.y, "sideways drift");
+}
+
+/// Least depth to push `b` out of `a`, projecting both onto all four face normals (for boxes
+/// the least-penetration axis is always one of them). Each normal is oriented from A's
+/// center towards B's, as contact normals must be.
+fn box_overlap_depth(a: &RigidBody, b: &RigidBody) -> Scalar {
+    let corners = |body: &RigidBody| {
+        let Some(Collider2D::Box { half_extents: h }) = body.collider else {
+            unreachable!()
+        };
+        let rot = Mat2::rotation(body.angle);
+        [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(sx, sy)| body.pos + rot.mul_vec2(Vec2::new(sx * h.x, sy * h.y)))
+    };
+    let (ca, cb) = (corners(a), corners(b));
+    let axes = [a.angle, b.angle].into_iter().flat_map(|angle| {
+        let rot = Mat2::rotation(angle);
+        [
+            rot.mul_vec2(Vec2::new(1.0, 0.0)),
+            rot.mul_vec2(Vec2::new(0.0, 1.0)),
+        ]
+    });
+    let towards_b = b.pos - a.pos;
+    axes.map(|n| if n.dot(towards_b) < 0.0 { -n } else { n })
+        .map(|n| {
+            let span = |cs: &[Vec2; 4]| {
+                cs.iter()
+                    .map(|c| c.dot(n))
+                    .fold((Scalar::INFINITY, Scalar::NEG_INFINITY), |(lo, hi), d| {
+                        (lo.min(d), hi.max(d))
+                    })
+            };
+            let ((_, max_a), (min_b, _)) = (span(&ca), span(&cb));
+            max_a - min_b
+        })
+        .fold(Scalar::INFINITY, Scalar::min)
+}
+
+#[test]
+fn deep_overlap_depth_is_least_penetration() {
+    let a = || RigidBody::box_xy(Vec2::zero(), 0.0, 1.0, 2.0, 1.0);
+    let mut checked = 0;
+    for i in 0..12 {
+        for j in 0..6 {
+            let angle = 0.25 * i as Scalar;
+            let b = RigidBody::box_xy(
+                Vec2::new(-0.4 + 0.15 * j as Scalar, 0.1),
+                angle,
+                1.0,
+                0.8 + 0.2 * j as Scalar,
+                1.5,
+            );
+            let depth = box_overlap_depth(&a(), &b);
+            if depth < 0.25 * 0.4 {
+                continue;
+            }
+            let entities: Vec<Box<dyn PhysicalEntity>> = vec![Box::new(a()), Box::new(b)];
+            let manifolds = narrow_phase::detect(
+                &entities,
+                &[(0, 1)],
+                SimParams::default(),
+                DT,
+                SolverParams::default().default_material(),
+            );
+            let deepest = manifolds[0]
+                .points
+                .iter()
+                .map(|p| p.penetration)
+                .fold(Scalar::NEG_INFINITY, Scalar::max);
+            assert_approx_eq!(
+                deepest,
+                depth,
+                1e-3,
+                "angle {angle}, case {j}: penetration {deepest}, least {depth}"
+            );
+            checked += 1;
+        }
+    }
+    assert!(checked > 20, "only {checked} deep cases");
+}
+
+#[test]
+fn manifold_carries_combined_material() {
+    let ground = RigidBody::box_xy(Vec2::new(0.0, -0.5), 0.0, 0.0, 10.0, 1.0);
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.49))
+        .circle(0.5)
+        .friction(0.2)
+        .restitution(0.9)
+        .build();
+    let entities: Vec<Box<dyn PhysicalEntity>> = vec![Box::new(ground), Box::new(ball)];
+    let defaults = Material::new(0.1, 0.8); // restitution 0.1, friction 0.8
+
+    let manifolds = narrow_phase::detect(&entities, &[(0, 1)], SimParams::default(), DT, defaults);
+    assert_eq!(manifolds.len(), 1);
+    let m = &manifolds[0];
+    // The ground has no material of its own and uses the defaults.
+    assert_approx_eq!(m.friction, (0.2 as Scalar * 0.8).sqrt(), 1e-6);
+    assert_eq!(m.restitution, 0.9);
+}
+
+#[test]
+fn unset_material_values_follow_the_world_solver_params() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.solver.params.friction = 0.0;
+    world.solver.params.rolling_resistance = 0.3;
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        10.0,
+        1.0,
+    )));
+    // Only the restitution is set; friction and rolling resistance come from the world.
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.49))
+        .circle(0.5)
+        .restitution(0.9)
+        .build();
+    world.add(Box::new(ball));
+    world.step(1.0 / 60.0);
+
+    let m = &world.manifolds[0];
+    assert_eq!(m.friction, 0.0);
+    assert_eq!(m.rolling_resistance, 0.3);
+    assert_eq!(m.restitution, 0.9);
+}
+
+#[test]
+fn manifolds_come_out_in_pair_order_whatever_the_pair_order() {
+    let entities: Vec<Box<dyn PhysicalEntity>> = vec![
+        Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 0.0, 10.0, 1.0)),
+        Box::new(RigidBody::box_xy(
+            Vec2::new(-2.0, 0.95),
+            0.05,
+            1.0,
+            1.0,
+            1.0,
+        )),
+        Box::new(RigidBody::box_xy(
+            Vec2::new(2.0, 0.95),
+            -0.05,
+            1.0,
+            1.0,
+            1.0,
+        )),
+        Box::new(RigidBody::box_xy(Vec2::new(2.0, 1.9), 0.0, 1.0, 1.0, 1.0)),
+    ];
+    let detect = |pairs: &[(usize, usize)]| {
+        narrow_phase::detect(
+            &entities,
+            pairs,
+            SimParams::default(),
+            DT,
+            SolverParams::default().default_material(),
+        )
+    };
+    let keys = |pairs: &[(usize, usize)]| -> Vec<(usize, usize, Vec<u32>)> {
+        detect(pairs)
+            .iter()
+            .map(|m| (m.a, m.b, m.points.iter().map(|cp| cp.id).collect()))
+            .collect()
+    };
+    let sorted = keys(&[(0, 1), (0, 2), (2, 3)]);
+    assert_eq!(
+        sorted.iter().map(|k| (k.0, k.1)).collect::<Vec<_>>(),
+        [(0, 1), (0, 2), (2, 3)]
+    );
+    assert_eq!(keys(&[(2, 3), (0, 2), (0, 1)]), sorted);
+}
+
+#[test]
+fn points_come_out_in_feature_id_order() {
+    // The box-box clipping lists these two points highest id first; they are solved in id
+    // order, so the result doesn't hang on the order the detector happens to find them in.
+    let entities: Vec<Box<dyn PhysicalEntity>> = vec![
+        Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 1.0, 2.0, 1.0)),
+        Box::new(RigidBody::box_xy(Vec2::new(0.3, 0.1), 0.05, 1.0, 2.0, 1.0)),
+    ];
+    let manifolds = narrow_phase::detect(
+        &entities,
+        &[(0, 1)],
+        SimParams::default(),
+        DT,
+        SolverParams::default().default_material(),
+    );
+    let ids: Vec<u32> = manifolds[0].points.iter().map(|cp| cp.id).collect();
+    assert_eq!(ids.len(), 2);
+    assert!(ids[0] < ids[1], "ids {ids:?}");
+}
+
+#[test]
+fn stacked_equal_boxes_keep_their_ids_through_a_hair_of_drift() {
+    // The top box's corners sit right on the lower box's side planes: drifting a fraction of
+    // a millimeter either way must not change which features the points come from, or the
+    // warm start is lost.
+    let ids = |dx: Scalar| -> Vec<u32> {
+        let entities: Vec<Box<dyn PhysicalEntity>> = vec![
+            Box::new(RigidBody::box_xy(Vec2::zero(), 0.0, 1.0, 1.0, 1.0)),
+            Box::new(RigidBody::box_xy(Vec2::new(dx, 0.995), 0.0, 1.0, 1.0, 1.0)),
+        ];
+        let manifolds = narrow_phase::detect(
+            &entities,
+            &[(0, 1)],
+            SimParams::default(),
+            DT,
+            SolverParams::default().default_material(),
+        );
+        assert_eq!(manifolds.len(), 1);
+        manifolds[0].points.iter().map(|cp| cp.id).collect()
+    };
+    let centered = ids(0.0);
+    assert_eq!(centered.len(), 2);
+    for dx in [-5e-4, -1e-5, 1e-5, 5e-4] {
+        assert_eq!(ids(dx), centered, "drifted by {dx}");
+    }
+}
+
+/// A rounded box (core 1 x 1, radius 0.2) at the origin and a unit box at `offset`.
+fn rounded_box_and_box(offset: Vec2) -> Vec<Box<dyn PhysicalEntity>> {
+    let rounded = RigidBodyBuilder::new()
+        .collider(Collider2D::RoundedBox {
+            half_extents: Vec2::new(0.5, 0.5),
+            radius: 0.2,
+        })
+        .build();
+    vec![
+        Box::new(rounded),
+        Box::new(RigidBody::box_xy(offset, 0.0, 1.0, 1.0, 1.0)),
+    ]
+}
+
+#[test]
+fn rounded_corner_touches_along_its_arc() {
+    // Corner to corner: the box's corner is 0.1 * sqrt(2) from the core's, inside the arc.
+    // The box is B above the rounded box and A below it.
+    let diagonal = Vec2::new(1.0, 1.0).normalized();
+    for (s, a) in [(1.0, 0), (-1.0, 1)] {
+        let entities = rounded_box_and_box(Vec2::new(1.1, 1.1) * s);
+        let manifolds = narrow_phase::detect(
+            &entities,
+            &[(0, 1)],
+            SimParams::default(),
+            DT,
+            SolverParams::default().default_material(),
+        );
+        assert_eq!(manifolds.len(), 1, "no contact at {s}");
+        let m = &manifolds[0];
+        assert_eq!(m.a, a);
+        assert_approx_eq!(m.normal, diagonal, 1e-4);
+        assert_eq!(m.points.len(), 1);
+        let depth = m.points[0].penetration;
+        let arc_depth = 0.2 - 0.1 * Scalar::sqrt(2.0);
+        assert_approx_eq!(depth, arc_depth, 1e-4);
+    }
+}
+
+#[test]
+fn rounded_box_face_keeps_two_points() {
+    // Side by side, the flat part of the rounded side against the box's face.
+    let entities = rounded_box_and_box(Vec2::new(1.1, 0.5));
+    assert_eq!(contact_count(&entities), 2);
+    assert_eq!(normal(&entities).1.x, 1.0);
+}
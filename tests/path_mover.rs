@@ -0,0 +1,139 @@
+//! Kinematic bodies driven along timed paths by `PathMover`.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::forces::kinematic::{LoopMode, PathMover};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A static 4 x 0.5 platform at the origin, moved by a `PathMover` along `waypoints`.
+fn platform_world(waypoints: Vec<(Vec2, Scalar)>, loop_mode: LoopMode) -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    let platform = RigidBodyBuilder::new()
+        .mass(0.0)
+        .box_shape(4.0, 0.5)
+        .build();
+    world.add(Box::new(platform));
+    world.add_force(Box::new(PathMover {
+        entity: 0,
+        waypoints,
+        loop_mode,
+    }));
+    world
+}
+
+#[test]
+fn world_time_sums_scaled_steps() {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    world.set_time_scale(0.5);
+    for _ in 0..60 {
+        world.step(DT);
+    }
+    assert_approx_eq!(world.time(), 0.5, 1e-4);
+    assert_approx_eq!(world.dt(), 0.5 * DT, 1e-7);
+}
+
+#[test]
+fn platform_passes_through_waypoints_on_time() {
+    let route = vec![
+        (Vec2::zero(), 0.0),
+        (Vec2::new(3.0, 0.0), 1.0),
+        (Vec2::new(3.0, 2.0), 2.0),
+        (Vec2::zero(), 3.0),
+    ];
+    let mut world = platform_world(route, LoopMode::Loop);
+
+    // Steps 60, 120, 180, 240 end on waypoint times; the loop wraps at 3 s.
+    let expected = [
+        Vec2::new(3.0, 0.0),
+        Vec2::new(3.0, 2.0),
+        Vec2::zero(),
+        Vec2::new(3.0, 0.0),
+    ];
+    for point in expected {
+        for _ in 0..60 {
+            world.step(DT);
+        }
+        let pos = *world.entities[0].pos();
+        assert_approx_eq!(pos, point, 1e-3, "at {pos:?}, expected {point:?}");
+    }
+}
+
+#[test]
+fn ping_pong_runs_the_path_backwards() {
+    let mover = PathMover {
+        entity: 0,
+        waypoints: vec![(Vec2::zero(), 1.0), (Vec2::new(2.0, 0.0), 2.0)],
+        loop_mode: LoopMode::PingPong,
+    };
+    let at = |t: Scalar| mover.position_at(t).unwrap().x;
+    assert_eq!(at(0.0), 0.0, "waits at the first waypoint");
+    assert_approx_eq!(at(1.5), 1.0, 1e-6);
+    assert_approx_eq!(at(2.5), 1.0, 1e-6);
+    assert_approx_eq!(at(3.25), 0.5, 1e-6);
+
+    let once = PathMover {
+        loop_mode: LoopMode::Once,
+        ..mover
+    };
+    assert_eq!(once.position_at(10.0).unwrap().x, 2.0);
+}
+
+#[test]
+fn moving_platform_carries_a_crate() {
+    let speed = 1.0;
+    let route = vec![(Vec2::zero(), 0.5), (Vec2::new(speed * 2.0, 0.0), 2.5)];
+    let mut world = platform_world(route, LoopMode::Once);
+    let crate_box = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.75))
+        .box_shape(0.5, 0.5)
+        .friction(1.0)
+        .build();
+    world.add(Box::new(crate_box));
+
+    // Settle, start moving, and let friction bring the crate up to speed.
+    for _ in 0..90 {
+        world.step(DT);
+    }
+    let vel = *world.entities[1].vel();
+    assert_approx_eq!(vel.x, speed, 0.05, "crate moving at {vel:?}");
+    let offset = |world: &World| *world.entities[1].pos() - *world.entities[0].pos();
+    let start = offset(&world);
+    for _ in 0..60 {
+        world.step(DT);
+    }
+    assert_approx_eq!(offset(&world), start, 1e-3, "crate slid on the platform");
+}
+
+#[test]
+fn moving_platform_in_the_static_bvh_still_holds_a_crate() {
+    let route = vec![(Vec2::zero(), 0.5), (Vec2::new(10.0, 0.0), 2.5)];
+    let crate_height = |bvh: bool| {
+        let mut world = platform_world(route.clone(), LoopMode::Once);
+        let crate_box = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 0.5))
+            .box_shape(0.5, 0.5)
+            .friction(1.0)
+            .build();
+        world.add(Box::new(crate_box));
+        if bvh {
+            world.rebuild_static_bvh();
+        }
+        // Ride the whole route and a second past its end, where the platform stops.
+        for _ in 0..210 {
+            world.step(DT);
+        }
+        world.entities[1].pos().y
+    };
+
+    let (with_bvh, without) = (crate_height(true), crate_height(false));
+    assert_approx_eq!(without, 0.5, 0.02, "crate at y = {without}");
+    assert_approx_eq!(
+        with_bvh,
+        without,
+        1e-3,
+        "crate at y = {with_bvh} with the BVH, {without} without"
+    );
+}
@@ -0,0 +1,87 @@
+//! `PulleyJoint`: two bodies on one rope over fixed pulleys.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, PulleyJoint, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+const G: Scalar = 9.81;
+
+/// Point masses `mass_a` at (-1, 2) and `mass_b` at (1, 2), hanging from pulleys at (-1, 5)
+/// and (1, 5).
+fn pulley_world(mass_a: Scalar, mass_b: Scalar, ratio: Scalar) -> World {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    for (x, mass) in [(-1.0, mass_a), (1.0, mass_b)] {
+        let body = RigidBodyBuilder::new()
+            .position(Vec2::new(x, 2.0))
+            .mass(mass)
+            .build();
+        world.add(Box::new(body));
+    }
+    let pulley = PulleyJoint::new(
+        &*world.entities[0],
+        &*world.entities[1],
+        0,
+        1,
+        [Vec2::new(-1.0, 5.0), Vec2::new(1.0, 5.0)],
+        [Vec2::new(-1.0, 2.0), Vec2::new(1.0, 2.0)],
+        ratio,
+    );
+    world.add_joint(pulley);
+    world
+}
+
+fn run(world: &mut World, steps: usize) {
+    for _ in 0..steps {
+        world.step(DT);
+    }
+}
+
+#[test]
+fn heavier_mass_descends_at_the_atwood_acceleration() {
+    let (m1, m2) = (2.0, 1.0);
+    let mut world = pulley_world(m1, m2, 1.0);
+    run(&mut world, 60);
+
+    // Atwood machine: a = g (m1 - m2) / (m1 + m2).
+    let expected = G * (m1 - m2) / (m1 + m2);
+    let (va, vb) = (world.entities[0].vel().y, world.entities[1].vel().y);
+    assert_approx_eq!(va, -expected, 0.01 * expected, "A falls at {va}");
+    assert_approx_eq!(vb, expected, 0.01 * expected, "B rises at {vb}");
+    assert_approx_eq!(world.entities[0].vel().x, 0.0, 1e-4);
+
+    let rope = |w: &World| {
+        (*w.entities[0].pos() - Vec2::new(-1.0, 5.0)).length()
+            + (*w.entities[1].pos() - Vec2::new(1.0, 5.0)).length()
+    };
+    assert_approx_eq!(rope(&world), 6.0, 1e-3);
+}
+
+#[test]
+fn block_and_tackle_trades_speed_for_force() {
+    // Ratio 2: B moves half as far as A, and is pulled up with twice the rope tension.
+    let mut balanced = pulley_world(1.0, 2.0, 2.0);
+    run(&mut balanced, 60);
+    for e in &balanced.entities {
+        assert_approx_eq!(*e.vel(), Vec2::zero(), 1e-3, "still moving");
+    }
+
+    // Equal masses: B rises. a_B = g (r m_a - m_b) / (m_b + r^2 m_a) = g / 5.
+    let mut world = pulley_world(1.0, 1.0, 2.0);
+    run(&mut world, 60);
+    let (va, vb) = (world.entities[0].vel().y, world.entities[1].vel().y);
+    let expected = G / 5.0;
+    assert_approx_eq!(vb, expected, 0.01 * expected, "B rises at {vb}");
+    assert_approx_eq!(va, -2.0 * vb, 0.01 * expected, "A falls at {va}");
+}
+
+#[test]
+fn pulley_impulse_matches_the_rope_tension() {
+    let mut world = pulley_world(2.0, 1.0, 1.0);
+    run(&mut world, 10);
+    // Rope tension T = 2 g m1 m2 / (m1 + m2); the impulse holds A's side up against it.
+    let tension = 2.0 * G * 2.0 * 1.0 / 3.0;
+    let impulse = world.joint::<PulleyJoint>(0).unwrap().impulse;
+    assert_approx_eq!(impulse, -tension * DT, 0.01 * tension * DT);
+}
@@ -0,0 +1,155 @@
+//! `World::raycast_ccd` sweeps the bodies' motion over the step against the ray.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Collider2D, Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+fn world_with(bodies: Vec<RigidBodyBuilder>) -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    for body in bodies {
+        world.add(Box::new(body.build()));
+    }
+    world
+}
+
+#[test]
+fn static_box_is_hit_on_its_near_face() {
+    let world = world_with(vec![
+        RigidBodyBuilder::new()
+            .position(Vec2::new(5.0, 0.0))
+            .mass(0.0)
+            .box_shape(2.0, 2.0),
+    ]);
+    let hit = world
+        .raycast_ccd(Vec2::zero(), Vec2::new(2.0, 0.0), 10.0, 0.0)
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    assert_eq!(hit.toi, 0.0);
+    assert_approx_eq!(hit.distance, 4.0, 1e-4);
+    assert_approx_eq!(hit.point, Vec2::new(4.0, 0.0), 1e-3);
+    assert_approx_eq!(hit.normal, Vec2::new(-1.0, 0.0), 1e-3);
+
+    assert!(
+        world
+            .raycast_ccd(Vec2::zero(), Vec2::new(1.0, 0.0), 3.5, 0.0)
+            .is_none()
+    );
+}
+
+#[test]
+fn nearest_of_two_static_bodies_wins() {
+    let world = world_with(vec![
+        RigidBodyBuilder::new()
+            .position(Vec2::new(8.0, 0.0))
+            .mass(0.0)
+            .circle(0.5),
+        RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 3.0))
+            .mass(0.0)
+            .collider(Collider2D::Segment {
+                a: Vec2::new(-1.0, 0.0),
+                b: Vec2::new(1.0, 0.0),
+            }),
+    ]);
+    // Running along the segment doesn't hit it.
+    assert!(
+        world
+            .raycast_ccd(Vec2::new(-3.0, 3.0), Vec2::new(1.0, 0.0), 20.0, DT)
+            .is_none()
+    );
+
+    let hit = world
+        .raycast_ccd(Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), 20.0, DT)
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    assert_approx_eq!(hit.distance, 7.5, 1e-4);
+    assert_approx_eq!(hit.normal, Vec2::new(-1.0, 0.0), 1e-3);
+
+    let hit = world
+        .raycast_ccd(Vec2::new(0.0, 0.0), Vec2::new(0.0, 1.0), 20.0, DT)
+        .unwrap();
+    assert_eq!(hit.entity, 1);
+    assert_approx_eq!(hit.distance, 3.0, 1e-4);
+    assert_approx_eq!(hit.normal, Vec2::new(0.0, -1.0), 1e-3);
+}
+
+#[test]
+fn fast_target_crossing_the_ray_mid_step_is_caught() {
+    // Moves 6 units down over the step, from above the ray to below it: a raycast at either
+    // end of the step misses it.
+    let world = world_with(vec![
+        RigidBodyBuilder::new()
+            .position(Vec2::new(5.0, 3.0))
+            .velocity(Vec2::new(0.0, -6.0 / DT))
+            .circle(0.5),
+    ]);
+    assert!(
+        world
+            .raycast_ccd(Vec2::zero(), Vec2::new(1.0, 0.0), 10.0, 0.0)
+            .is_none()
+    );
+
+    let hit = world
+        .raycast_ccd(Vec2::zero(), Vec2::new(1.0, 0.0), 10.0, DT)
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    // The bottom of the circle reaches the ray after 2.5 of the 6 units.
+    assert_approx_eq!(hit.toi, 2.5 / 6.0, 1e-2);
+    assert_approx_eq!(hit.point.x, 5.0, 0.2, "{:?}", hit.point);
+    // It reaches the ray with its bottom.
+    assert!(hit.normal.y < -0.9, "{:?}", hit.normal);
+}
+
+#[test]
+fn target_moving_along_the_ray_is_only_hit_within_its_radius() {
+    let beside = |y: Scalar| {
+        world_with(vec![
+            RigidBodyBuilder::new()
+                .position(Vec2::new(5.0, y))
+                .velocity(Vec2::new(-3.0 / DT, 0.0))
+                .circle(0.5),
+        ])
+    };
+    let ray = |world: &World| world.raycast_ccd(Vec2::zero(), Vec2::new(1.0, 0.0), 10.0, DT);
+
+    assert!(ray(&beside(0.6)).is_none());
+    let hit = ray(&beside(0.4)).unwrap();
+    assert_eq!(hit.toi, 0.0);
+    assert_approx_eq!(hit.distance, 4.7, 1e-4);
+}
+
+#[test]
+fn fast_oblique_target_is_caught() {
+    // Sweeps 50 units sideways and 2 down over the step: it closes the 1 unit gap to the ray
+    // half way through, while stepping by its gap over its full speed would take 50 steps.
+    let world = world_with(vec![
+        RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 1.5))
+            .velocity(Vec2::new(50.0 / DT, -2.0 / DT))
+            .circle(0.5),
+    ]);
+    let hit = world
+        .raycast_ccd(Vec2::new(-100.0, 0.0), Vec2::new(1.0, 0.0), 200.0, DT)
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    assert_approx_eq!(hit.toi, 0.5, 1e-3);
+    assert_approx_eq!(hit.point, Vec2::new(25.0, 0.0), 0.05);
+    assert!(hit.normal.y < -0.9, "{:?}", hit.normal);
+
+    // A box sweeping at a shallow angle to a diagonal ray.
+    let world = world_with(vec![
+        RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 3.0))
+            .velocity(Vec2::new(40.0 / DT, 10.0 / DT))
+            .box_shape(1.0, 1.0),
+    ]);
+    let hit = world
+        .raycast_ccd(Vec2::new(-10.0, -10.0), Vec2::new(1.0, 1.0), 100.0, DT)
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    // Its corner reaches the ray once the box has closed 2 of its 3 units to it.
+    assert_approx_eq!(hit.toi, 1.0 / 15.0, 1e-3);
+}
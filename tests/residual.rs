@@ -0,0 +1,134 @@
+//! `ConstraintSolver::last_residual` as a convergence measure.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBody, WeldJoint, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A column of `count` unit boxes on a static ground.
+fn column(count: usize) -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        20.0,
+        1.0,
+    )));
+    for i in 0..count {
+        world.add(Box::new(RigidBody::box_xy(
+            Vec2::new(0.0, 0.5 + i as Scalar),
+            0.0,
+            1.0,
+            1.0,
+            1.0,
+        )));
+    }
+    world
+}
+
+/// Residual of a `count` box column after `steps` steps with `iterations` biased iterations.
+fn column_residual(count: usize, iterations: usize, steps: usize) -> Scalar {
+    let mut world = column(count);
+    world.solver.params.velocity_iterations = iterations;
+    for _ in 0..steps {
+        world.step(DT);
+    }
+    world.solver.last_residual()
+}
+
+#[test]
+fn residual_is_zero_without_contacts() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::zero(),
+        0.0,
+        1.0,
+        1.0,
+        1.0,
+    )));
+    world.step(DT);
+    assert_eq!(world.solver.last_residual(), 0.0);
+}
+
+#[test]
+fn resting_box_converges() {
+    // Warm started from the last step, a single resting box is solved before the last
+    // iteration even starts.
+    let residual = column_residual(1, 10, 60);
+    assert!(residual < 1e-5 * 9.81 * DT, "residual {residual}");
+}
+
+#[test]
+fn more_iterations_shrink_the_residual_of_a_tall_stack() {
+    // Fresh contacts (first step): nothing to warm start from, so iterations matter.
+    let few = column_residual(14, 2, 1);
+    let many = column_residual(14, 40, 1);
+    assert!(few > 0.0);
+    assert!(many < 0.5 * few, "{many} vs {few}");
+}
+
+/// Biased iterations of the last of `steps` steps of a `count` box column, in adaptive mode.
+fn adaptive_iterations(count: usize, steps: usize) -> usize {
+    let mut world = column(count);
+    world.solver.params.convergence_tolerance = Some(1e-4);
+    world.solver.params.max_iterations = 25;
+    for _ in 0..steps {
+        world.step(DT);
+    }
+    world.solver.last_iterations()
+}
+
+#[test]
+fn adaptive_mode_spends_iterations_where_needed() {
+    let resting = adaptive_iterations(1, 60);
+    assert!(resting <= 2, "resting box took {resting} iterations");
+
+    let impact = adaptive_iterations(14, 1);
+    assert!(impact > 10, "fresh stack took only {impact} iterations");
+    assert!(impact <= 25, "cap exceeded: {impact}");
+}
+
+/// A static block with a two-link arm welded on to its right; nothing touches.
+fn welded_arm() -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::zero(),
+        0.0,
+        0.0,
+        0.5,
+        0.5,
+    )));
+    for i in 1..=2 {
+        let link = RigidBody::box_xy(Vec2::new(i as Scalar, 0.0), 0.0, 1.0, 0.5, 0.5);
+        world.add(Box::new(link));
+        let (a, b) = (&*world.entities[i - 1], &*world.entities[i]);
+        let weld = WeldJoint::new(a, b, i - 1, i, Vec2::new(i as Scalar - 0.5, 0.0));
+        world.add_joint(weld);
+    }
+    world
+}
+
+#[test]
+fn joint_impulses_count_toward_the_residual() {
+    let mut world = welded_arm();
+    world.step(DT);
+    assert!(world.solver.last_residual() > 0.0);
+
+    let mut world = welded_arm();
+    world.solver.params.convergence_tolerance = Some(1e-4);
+    world.solver.params.max_iterations = 25;
+    world.step(DT);
+    // The welds start from zero impulse: one iteration can't have converged.
+    let fresh = world.solver.last_iterations();
+    assert!(fresh > 10, "fresh welds took only {fresh} iterations");
+    for _ in 0..300 {
+        world.step(DT);
+    }
+    let settled = world.solver.last_iterations();
+    assert!(settled <= 2, "settled arm took {settled} iterations");
+    let tip = world.entities[2].pos();
+    assert_approx_eq!(tip.y, 0.0, 0.01, "arm sagged to {tip:?}");
+}
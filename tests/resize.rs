@@ -0,0 +1,82 @@
+//! Growing and shrinking bodies at runtime with `RigidBody::rescale` and friends.
+
+use std::any::Any;
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Collider2D, Integrator, RigidBody, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A 2 kg ball of radius 0.5 resting on the ground.
+fn ball_on_ground() -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    world.add(Box::new(RigidBody::circle(
+        Vec2::new(0.0, 0.5),
+        0.0,
+        2.0,
+        0.5,
+    )));
+    for _ in 0..30 {
+        world.step(DT);
+    }
+    world
+}
+
+fn rigid_body(world: &mut World, index: usize) -> &mut RigidBody {
+    let any: &mut dyn Any = world.entities[index].as_mut();
+    any.downcast_mut().unwrap()
+}
+
+#[test]
+fn grown_ball_keeps_its_mass_and_rises_onto_the_ground() {
+    let mut world = ball_on_ground();
+    rigid_body(&mut world, 1).rescale(2.0);
+    world.invalidate_body(1);
+    assert_eq!(world.contact_impulse(0, 1), Some(0.0));
+
+    let ball = &world.entities[1];
+    assert_eq!(ball.mass(), 2.0);
+    // 1/2 m r^2 with the new radius of 1.
+    assert_approx_eq!(ball.inertia(), 1.0, 1e-5);
+    assert!(matches!(ball.collider(), Some(Collider2D::Circle { radius }) if *radius == 1.0));
+
+    // It now overlaps the ground by 0.5 m and is pushed out to rest on it.
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    let y = world.entities[1].pos().y;
+    assert_approx_eq!(y, 1.0, 0.02, "ball at y = {y}");
+    assert_approx_eq!(*world.entities[1].vel(), Vec2::zero(), 0.05);
+}
+
+#[test]
+fn shrunk_crate_settles_lower() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, 1.0),
+        0.0,
+        3.0,
+        2.0,
+        2.0,
+    )));
+    for _ in 0..30 {
+        world.step(DT);
+    }
+
+    rigid_body(&mut world, 1).set_half_extents(Vec2::new(0.5, 0.25));
+    world.invalidate_body(1);
+    let body = &world.entities[1];
+    assert_eq!(body.mass(), 3.0);
+    // m (w^2 + h^2) / 12 for the new 1 x 0.5 box.
+    assert_approx_eq!(body.inertia(), 3.0 * 1.25 / 12.0, 1e-5);
+
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    let y = world.entities[1].pos().y;
+    assert_approx_eq!(y, 0.25, 0.02, "crate at y = {y}");
+}
@@ -0,0 +1,68 @@
+//! Rolling resistance brings a rolling circle to a stop, and leaves sliding alone.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+const G: Scalar = 9.81;
+
+/// A disc of radius 0.5 resting on the ground at the origin, moving at `vel` and spinning at
+/// `omega`, with rolling resistance `resistance`.
+fn disc_on_floor(vel: Scalar, omega: Scalar, resistance: Scalar) -> World {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    let disc = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.5))
+        .velocity(Vec2::new(vel, 0.0))
+        .angular_velocity(omega)
+        .circle(0.5)
+        .friction(0.5)
+        .rolling_resistance(resistance)
+        .build();
+    world.add(Box::new(disc));
+    world
+}
+
+#[test]
+fn rolling_disc_coasts_to_a_stop() {
+    // Rolling without slipping at 1 m/s.
+    let mut world = disc_on_floor(1.0, -2.0, 0.05);
+    for _ in 0..300 {
+        world.step(DT);
+    }
+    let disc = &world.entities[1];
+    assert_approx_eq!(*disc.vel(), Vec2::zero(), 1e-3, "still moving");
+    assert_approx_eq!(disc.omega(), 0.0, 2e-3, "still spinning");
+    // Torque c * r * m g on a solid disc rolls it down at c g / (1 + 1/2): about 1.5 m to
+    // stop from 1 m/s.
+    let decel = 0.05 * G / 1.5;
+    let expected = 1.0 / (2.0 * decel);
+    let x = disc.pos().x;
+    assert_approx_eq!(x, expected, 0.1 * expected, "stopped after {x} m");
+}
+
+#[test]
+fn without_resistance_it_rolls_on() {
+    let mut world = disc_on_floor(1.0, -2.0, 0.0);
+    for _ in 0..300 {
+        world.step(DT);
+    }
+    let v = world.entities[1].vel().x;
+    assert_approx_eq!(v, 1.0, 1e-2, "rolling at {v} m/s");
+}
+
+#[test]
+fn sliding_disc_is_left_to_friction() {
+    // Thrown along the floor without spin: it slides until friction has spun it up.
+    let mut with = disc_on_floor(3.0, 0.0, 0.05);
+    let mut without = disc_on_floor(3.0, 0.0, 0.0);
+    for step in 0..5 {
+        with.step(DT);
+        without.step(DT);
+        let (a, b) = (&with.entities[1], &without.entities[1]);
+        assert_eq!(a.vel().x, b.vel().x, "step {step}");
+        assert_eq!(a.omega(), b.omega(), "step {step}");
+    }
+}
@@ -0,0 +1,263 @@
+//! `Collider2D::Segment` and `Collider2D::Chain`: thin static walls and terrain.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Collider2D, Integrator, RigidBody, RigidBodyBuilder, World};
+use tiny_physics_engine::math::transform::Transform2D;
+use tiny_physics_engine::math::vec::Vec2;
+use tiny_physics_engine::math::{Scalar, consts};
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A static segment from `a` to `b`, on a body whose origin is at `origin`.
+fn add_segment(world: &mut World, origin: Vec2, a: Vec2, b: Vec2) -> usize {
+    let wall = RigidBodyBuilder::new()
+        .position(origin)
+        .mass(0.0)
+        .collider(Collider2D::Segment { a, b })
+        .build();
+    add(world, wall)
+}
+
+fn add(world: &mut World, body: RigidBody) -> usize {
+    world.add(Box::new(body));
+    world.entities.len() - 1
+}
+
+fn run(world: &mut World, steps: usize) {
+    for _ in 0..steps {
+        world.step(DT);
+    }
+}
+
+#[test]
+fn segment_aabb_bounds_the_transformed_end_points() {
+    let segment = Collider2D::Segment {
+        a: Vec2::new(1.0, 0.0),
+        b: Vec2::new(3.0, 0.0),
+    };
+    let xf = Transform2D::from_pose(Vec2::new(10.0, 5.0), consts::FRAC_PI_2);
+    let aabb = segment.aabb(&xf);
+    assert_approx_eq!(aabb.min, Vec2::new(10.0, 6.0), 1e-5, "{aabb:?}");
+    assert_approx_eq!(aabb.max, Vec2::new(10.0, 8.0), 1e-5, "{aabb:?}");
+}
+
+#[test]
+fn ball_rests_on_a_segment_away_from_its_body_origin() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    // The ground line sits 5 m below and 20 m left of the body it belongs to.
+    add_segment(
+        &mut world,
+        Vec2::new(20.0, 5.0),
+        Vec2::new(-25.0, -5.0),
+        Vec2::new(-15.0, -5.0),
+    );
+    let ball = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 2.0))
+            .circle(0.5)
+            .build(),
+    );
+    run(&mut world, 180);
+    let pos = *world.entities[ball].pos();
+    assert_approx_eq!(pos.y, 0.5, 0.02, "ball at {pos:?}");
+    assert!(world.entities[ball].vel().length() < 0.05);
+}
+
+#[test]
+fn box_lands_flat_on_a_segment() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    add_segment(
+        &mut world,
+        Vec2::zero(),
+        Vec2::new(-5.0, 0.0),
+        Vec2::new(5.0, 0.0),
+    );
+    let block = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(0.3, 1.5))
+            .box_shape(1.0, 1.0)
+            .build(),
+    );
+    run(&mut world, 180);
+    let e = &world.entities[block];
+    assert_approx_eq!(e.pos().y, 0.5, 0.02, "box at {:?}", e.pos());
+    assert_approx_eq!(e.angle(), 0.0, 1e-3, "box tilted");
+    let manifold = world
+        .manifolds
+        .iter()
+        .find(|m| m.a == block || m.b == block)
+        .expect("box touches the segment");
+    assert_eq!(manifold.points.len(), 2);
+}
+
+#[test]
+fn segment_is_solid_from_both_sides() {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    add_segment(
+        &mut world,
+        Vec2::zero(),
+        Vec2::new(0.0, -2.0),
+        Vec2::new(0.0, 2.0),
+    );
+    let from_left = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(-2.0, 0.0))
+            .velocity(Vec2::new(5.0, 0.0))
+            .circle(0.25)
+            .build(),
+    );
+    let from_right = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(2.0, 1.0))
+            .velocity(Vec2::new(-5.0, 0.0))
+            .box_shape(0.5, 0.5)
+            .build(),
+    );
+    run(&mut world, 60);
+    let left = world.entities[from_left].pos().x;
+    let right = world.entities[from_right].pos().x;
+    assert!(left < -0.2, "ball passed the wall, at x = {left}");
+    assert!(right > 0.2, "box passed the wall, at x = {right}");
+}
+
+#[test]
+fn ball_rolls_across_a_chain_of_segments() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    let points = [
+        Vec2::new(-4.0, 2.0),
+        Vec2::new(-2.0, 1.0),
+        Vec2::new(0.0, 0.5),
+        Vec2::new(2.0, 0.5),
+        Vec2::new(6.0, 0.5),
+    ];
+    let parts = points
+        .windows(2)
+        .map(|w| {
+            let segment = Collider2D::Segment { a: w[0], b: w[1] };
+            (Transform2D::identity(), segment)
+        })
+        .collect();
+    let terrain = RigidBodyBuilder::new()
+        .mass(0.0)
+        .collider(Collider2D::Compound { parts })
+        .build();
+    add(&mut world, terrain);
+    let ball = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(-3.8, 2.6))
+            .circle(0.3)
+            .build(),
+    );
+    run(&mut world, 180);
+    let pos = *world.entities[ball].pos();
+    assert!(pos.x > 2.0, "ball stuck at {pos:?}");
+    assert_approx_eq!(pos.y, 0.8, 0.05, "ball off the terrain at {pos:?}");
+}
+
+/// A static body with a chain through `vertices`.
+fn add_chain(world: &mut World, vertices: Vec<Vec2>) -> usize {
+    let terrain = RigidBodyBuilder::new()
+        .mass(0.0)
+        .friction(0.0)
+        .collider(Collider2D::Chain { vertices })
+        .build();
+    add(world, terrain)
+}
+
+/// How far `p` is from the nearest segment of the chain through `vertices`.
+fn distance_to_chain(p: Vec2, vertices: &[Vec2]) -> Scalar {
+    vertices
+        .windows(2)
+        .map(|w| {
+            let edge = w[1] - w[0];
+            let t = ((p - w[0]).dot(edge) / edge.length_squared()).clamp(0.0, 1.0);
+            (p - (w[0] + edge * t)).length()
+        })
+        .fold(Scalar::INFINITY, Scalar::min)
+}
+
+#[test]
+fn box_slides_over_chain_joints_without_snagging() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    // A flat floor of 0.5 m pieces: every joint is an internal edge the box could catch on.
+    let floor = (0..=20)
+        .map(|i| Vec2::new(-2.0 + i as Scalar * 0.5, 0.0))
+        .collect();
+    add_chain(&mut world, floor);
+    let block = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(-1.5, 0.25))
+            .velocity(Vec2::new(3.0, 0.0))
+            .box_shape(0.5, 0.5)
+            .friction(0.0)
+            .build(),
+    );
+    for _ in 0..120 {
+        world.step(DT);
+        let e = &world.entities[block];
+        assert!(
+            e.vel().x > 2.99,
+            "box caught at {:?}, {:?}",
+            e.pos(),
+            e.vel()
+        );
+        assert_approx_eq!(e.angle(), 0.0, 1e-3, "box tipped");
+    }
+    assert!(world.entities[block].pos().x > 4.0);
+}
+
+#[test]
+fn ball_settles_in_a_chain_valley() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    let valley = vec![Vec2::new(-2.0, 2.0), Vec2::zero(), Vec2::new(2.0, 2.0)];
+    add_chain(&mut world, valley);
+    let ball = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(0.3, 3.0))
+            .circle(0.5)
+            .build(),
+    );
+    run(&mut world, 600);
+    // Touching both slopes: sqrt(2) * radius above the bottom.
+    let pos = *world.entities[ball].pos();
+    let expected = Vec2::new(0.0, consts::SQRT_2 * 0.5);
+    assert_approx_eq!(pos, expected, 0.02, "ball at {pos:?}");
+}
+
+#[test]
+fn ball_rolls_over_a_chain_corner_without_sinking() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    let plateau = vec![
+        Vec2::new(-3.0, 0.0),
+        Vec2::new(-1.0, 1.0),
+        Vec2::new(1.0, 1.0),
+        Vec2::new(3.0, 0.0),
+    ];
+    add_chain(&mut world, plateau.clone());
+    // Dropped onto the right-hand corner, which only a vertex contact can hold.
+    let radius = 0.25;
+    let ball = add(
+        &mut world,
+        RigidBodyBuilder::new()
+            .position(Vec2::new(1.05, 2.0))
+            .circle(radius)
+            .build(),
+    );
+    for _ in 0..90 {
+        world.step(DT);
+        let pos = *world.entities[ball].pos();
+        let gap = distance_to_chain(pos, &plateau) - radius;
+        assert!(gap > -0.03, "ball sank {} into the chain at {pos:?}", -gap);
+    }
+    assert!(
+        world.entities[ball].pos().x > 1.5,
+        "ball stayed on the corner"
+    );
+}
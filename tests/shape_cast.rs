@@ -0,0 +1,130 @@
+//! `World::shape_cast`: sweeping a circle or box through the world.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Collider2D, Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::transform::Transform2D;
+use tiny_physics_engine::math::vec::Vec2;
+
+/// A static 2 x 2 box at (5, 0) and a dynamic unit circle at (0, 3).
+fn world_with_wall() -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    let wall = RigidBodyBuilder::new()
+        .position(Vec2::new(5.0, 0.0))
+        .mass(0.0)
+        .box_shape(2.0, 2.0)
+        .build();
+    world.add(Box::new(wall));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 3.0))
+        .circle(1.0)
+        .build();
+    world.add(Box::new(ball));
+    world
+}
+
+#[test]
+fn circle_hits_the_near_face() {
+    let world = world_with_wall();
+    let circle = Collider2D::Circle { radius: 0.5 };
+    let hit = world
+        .shape_cast(&circle, Vec2::zero(), Vec2::new(10.0, 0.0))
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    // The circle touches the face at x = 4 once its center reaches x = 3.5.
+    assert_approx_eq!(hit.toi, 0.35, 1e-3);
+    assert_approx_eq!(hit.point, Vec2::new(4.0, 0.0), 1e-2);
+    assert_approx_eq!(hit.normal, Vec2::new(-1.0, 0.0), 1e-3);
+}
+
+#[test]
+fn box_hits_the_near_face() {
+    let world = world_with_wall();
+    let square = Collider2D::Box {
+        half_extents: Vec2::new(0.5, 0.5),
+    };
+    let hit = world
+        .shape_cast(&square, Vec2::new(5.0, -6.0), Vec2::new(5.0, 0.0))
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    // The top of the box reaches the wall's bottom face at y = -1 after 4.5 of the 6 m.
+    assert_approx_eq!(hit.toi, 0.75, 1e-3);
+    assert_approx_eq!(hit.normal, Vec2::new(0.0, -1.0), 1e-3);
+    assert_approx_eq!(hit.point.y, -1.0, 1e-2);
+}
+
+#[test]
+fn rounded_and_compound_shapes_hit_the_near_face() {
+    let world = world_with_wall();
+    let rounded = Collider2D::RoundedBox {
+        half_extents: Vec2::new(0.5, 0.5),
+        radius: 0.25,
+    };
+    let circle_at = |x| {
+        (
+            Transform2D::from_translation(Vec2::new(x, 0.0)),
+            Collider2D::Circle { radius: 0.5 },
+        )
+    };
+    let dumbbell = Collider2D::Compound {
+        parts: vec![circle_at(-1.0), circle_at(1.0)],
+    };
+    // The wall's near face is at x = 4; the shapes reach 0.75 and 1.5 ahead of their center.
+    for (name, shape, toi) in [("rounded", rounded, 0.325), ("dumbbell", dumbbell, 0.25)] {
+        let hit = world
+            .shape_cast(&shape, Vec2::zero(), Vec2::new(10.0, 0.0))
+            .unwrap();
+        assert_eq!(hit.entity, 0);
+        assert_approx_eq!(hit.toi, toi, 1e-3, "{name}");
+        assert_approx_eq!(hit.normal, Vec2::new(-1.0, 0.0), 1e-3, "{name}");
+    }
+}
+
+#[test]
+fn sweep_that_stops_short_or_passes_by_misses() {
+    let world = world_with_wall();
+    let circle = Collider2D::Circle { radius: 0.5 };
+    assert!(
+        world
+            .shape_cast(&circle, Vec2::zero(), Vec2::new(3.0, 0.0))
+            .is_none()
+    );
+    // Below the wall.
+    assert!(
+        world
+            .shape_cast(&circle, Vec2::new(0.0, -2.0), Vec2::new(10.0, -2.0))
+            .is_none()
+    );
+}
+
+#[test]
+fn starting_inside_reports_toi_zero_and_a_push_out_normal() {
+    let world = world_with_wall();
+    let circle = Collider2D::Circle { radius: 0.5 };
+    // Overlapping the wall's left face by 0.3 m, sweeping further in.
+    let hit = world
+        .shape_cast(&circle, Vec2::new(3.8, 0.0), Vec2::new(6.0, 0.0))
+        .unwrap();
+    assert_eq!(hit.entity, 0);
+    assert_eq!(hit.toi, 0.0);
+    assert_approx_eq!(hit.normal, Vec2::new(-1.0, 0.0), 1e-3);
+}
+
+#[test]
+fn filtered_cast_skips_excluded_bodies() {
+    let mut world = world_with_wall();
+    world.add_ground(-2.0, 0.5, 0.0);
+    // The ball's own collider, swept down from where the ball is: it starts inside the
+    // ball, so only the filter lets it see past it.
+    let ball = Collider2D::Circle { radius: 1.0 };
+    let (from, to) = (Vec2::new(0.0, 3.0), Vec2::new(0.0, -3.0));
+    let hit = world.shape_cast(&ball, from, to).unwrap();
+    assert_eq!((hit.entity, hit.toi), (1, 0.0));
+
+    let hit = world
+        .shape_cast_filtered(&ball, from, to, |i| i != 1)
+        .unwrap();
+    assert_eq!(hit.entity, 2);
+    // Resting on the ground at y = -2 puts the center at y = -1, 4 m down the 6 m sweep.
+    assert_approx_eq!(hit.toi, 2.0 / 3.0, 1e-3);
+    assert_approx_eq!(hit.normal, Vec2::new(0.0, 1.0), 1e-3);
+}
@@ -0,0 +1,81 @@
+//! `Spring`'s force clamp and its behaviour with coincident ends.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, Particle, World};
+use tiny_physics_engine::forces::spring::Spring;
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// One 1 kg particle at `pos` moving at `vel`, held by `spring`; no gravity.
+fn world_with(pos: Vec2, vel: Vec2, spring: Spring) -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    world.add(Box::new(Particle::new(pos, vel, 1.0)));
+    world.add_force(Box::new(spring));
+    world
+}
+
+#[test]
+fn max_force_caps_spring_and_damper_together() {
+    // Unclamped: 100 N/m * 10 m = 1000 N toward the anchor.
+    let spring = Spring::to_anchor(0, Vec2::new(10.0, 0.0), 100.0, 0.0, 0.0);
+    let mut world = world_with(Vec2::zero(), Vec2::zero(), spring.clone());
+    world.step(DT);
+    assert_approx_eq!(world.entities[0].force().x, 1000.0, 1e-2);
+
+    let mut world = world_with(Vec2::zero(), Vec2::zero(), spring.with_max_force(5.0));
+    world.step(DT);
+    let force = *world.entities[0].force();
+    assert_approx_eq!(force, Vec2::new(5.0, 0.0), 1e-5);
+
+    // A spring force below the cap, pushed over it by the damper: the sum is capped.
+    let damped = Spring::to_anchor(0, Vec2::new(1.0, 0.0), 3.0, 2.0, 0.0).with_max_force(5.0);
+    let mut world = world_with(Vec2::zero(), Vec2::new(-2.0, 0.0), damped);
+    world.step(DT);
+    let force = *world.entities[0].force();
+    assert_approx_eq!(force.length(), 5.0, 1e-5, "force {force:?}");
+    assert!(force.x > 0.0);
+}
+
+#[test]
+fn zero_rest_length_is_defined_at_coincident_ends() {
+    let anchor = Vec2::new(1.0, 2.0);
+    // Exactly on the anchor: no force, and nothing turns NaN.
+    let mut world = world_with(
+        anchor,
+        Vec2::zero(),
+        Spring::to_anchor(0, anchor, 50.0, 1.0, 0.0),
+    );
+    for _ in 0..10 {
+        world.step(DT);
+    }
+    assert_approx_eq!(*world.entities[0].force(), Vec2::zero(), 0.0);
+    assert_approx_eq!(*world.entities[0].pos(), anchor, 0.0);
+    assert!(world.invalid_bodies.is_empty());
+
+    // Closer than the direction guard: still -k * displacement.
+    let offset = Vec2::new(3e-7, -4e-7);
+    let spring = Spring::to_anchor(0, Vec2::zero(), 50.0, 1.0, 0.0);
+    let mut world = world_with(offset, Vec2::zero(), spring);
+    world.step(DT);
+    let force = *world.entities[0].force();
+    assert_approx_eq!(force, offset * -50.0, 1e-9, "force {force:?}");
+}
+
+#[test]
+fn positive_rest_length_skips_coincident_ends() {
+    let anchor = Vec2::new(1.0, 2.0);
+    let spring = Spring::to_anchor(0, anchor, 50.0, 1.0, 0.5);
+    let mut world = world_with(anchor, Vec2::new(0.0, 1.0), spring);
+    world.step(DT);
+    // No direction to push along: no force, rather than a NaN one.
+    assert_approx_eq!(*world.entities[0].force(), Vec2::zero(), 0.0);
+    assert!(world.invalid_bodies.is_empty());
+    // Once apart, it holds the particle at its rest length.
+    for _ in 0..600 {
+        world.step(DT);
+    }
+    let distance = (*world.entities[0].pos() - anchor).length();
+    assert_approx_eq!(distance, 0.5, 0.02, "distance {distance}");
+}
@@ -0,0 +1,114 @@
+//! `World::set_static` / `set_dynamic`: freezing a body into scenery and releasing it.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// Ground at y = 0, a unit box falling from y = 5 and a ball of radius 0.25 above it.
+fn placing_scene() -> World {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 5.0))
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(block));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 8.0))
+        .circle(0.25)
+        .build();
+    world.add(Box::new(ball));
+    world
+}
+
+#[test]
+fn placed_block_stays_put_and_still_collides() {
+    let mut world = placing_scene();
+    for _ in 0..20 {
+        world.step(DT);
+    }
+    world.set_static(1);
+    let placed = *world.entities[1].pos();
+    let block = &world.entities[1];
+    assert_eq!(block.vel().length(), 0.0);
+    assert_eq!((block.inv_mass(), block.inv_inertia()), (0.0, 0.0));
+
+    // Gravity no longer moves it, and the ball comes to rest on top of it.
+    for _ in 0..180 {
+        world.step(DT);
+    }
+    assert_eq!((*world.entities[1].pos() - placed).length(), 0.0);
+    let ball = *world.entities[2].pos();
+    let resting = placed.y + 0.5 + 0.25;
+    assert_approx_eq!(ball.y, resting, 0.02, "ball at {ball:?}");
+    assert!(world.entities[2].vel().length() < 0.05);
+}
+
+#[test]
+fn released_block_falls_with_its_new_mass() {
+    let mut world = placing_scene();
+    for _ in 0..20 {
+        world.step(DT);
+    }
+    world.set_static(1);
+    for _ in 0..180 {
+        world.step(DT);
+    }
+
+    world.set_dynamic(1, 2.0);
+    let block = &world.entities[1];
+    assert_eq!(block.inv_mass(), 0.5);
+    // Inertia of a 2 kg unit box, recomputed from the collider.
+    assert_approx_eq!(block.inv_inertia(), 3.0, 1e-5);
+    for _ in 0..180 {
+        world.step(DT);
+    }
+    // Block and ball end up stacked on the ground.
+    let (block, ball) = (world.entities[1].pos().y, world.entities[2].pos().y);
+    assert_approx_eq!(block, 0.5, 0.02, "block at y = {block}");
+    assert_approx_eq!(ball, 1.25, 0.02, "ball at y = {ball}");
+}
+
+#[test]
+fn converting_drops_only_that_body_s_warm_start() {
+    // Ground, a block with a ball resting on it, and another box next to them.
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    for (pos, body) in [
+        (
+            Vec2::new(0.0, 0.5),
+            RigidBodyBuilder::new().box_shape(1.0, 1.0),
+        ),
+        (Vec2::new(0.0, 1.25), RigidBodyBuilder::new().circle(0.25)),
+        (
+            Vec2::new(3.0, 0.5),
+            RigidBodyBuilder::new().box_shape(1.0, 1.0),
+        ),
+    ] {
+        world.add(Box::new(body.position(pos).build()));
+    }
+    for _ in 0..60 {
+        world.step(DT);
+    }
+    for (a, b) in [(0, 1), (1, 2), (0, 3)] {
+        assert!(
+            world.contact_impulse(a, b).unwrap() > 0.0,
+            "no contact {a}-{b}"
+        );
+    }
+
+    world.set_static(1);
+    assert_eq!(world.contact_impulse(0, 1), Some(0.0));
+    assert_eq!(world.contact_impulse(1, 2), Some(0.0));
+    assert!(world.contact_impulse(0, 3).unwrap() > 0.0);
+
+    world.set_dynamic(1, 1.0);
+    world.step(DT);
+    assert!(world.contact_impulse(1, 2).unwrap() > 0.0);
+    world.set_dynamic(1, 3.0);
+    assert_eq!(world.contact_impulse(1, 2), Some(0.0));
+    assert!(world.contact_impulse(0, 3).unwrap() > 0.0);
+}
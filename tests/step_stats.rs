@@ -0,0 +1,53 @@
+//! `World::step` reports what it did in a `StepStats`.
+
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, SolverParams, StepStats, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+#[test]
+fn resting_box_counts_one_pair_and_two_contacts() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .build();
+    world.add(Box::new(ground));
+    let block = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, 0.5))
+        .box_shape(1.0, 1.0)
+        .build();
+    world.add(Box::new(block));
+    // Far away, so it only adds to the body count.
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(20.0, 5.0))
+        .circle(0.5)
+        .build();
+    world.add(Box::new(ball));
+
+    let mut stats = StepStats::default();
+    for _ in 0..30 {
+        stats = world.step(DT);
+    }
+    assert_eq!(
+        stats,
+        StepStats {
+            broad_pairs: 1,
+            manifolds: 1,
+            contacts: 2,
+            solver_iterations: SolverParams::default().velocity_iterations,
+            active_bodies: 2,
+            sleeping_bodies: 0,
+        }
+    );
+    assert_eq!(stats.broad_pairs, world.pairs.len());
+}
+
+#[test]
+fn zero_dt_step_reports_nothing() {
+    let mut world = World::new(Vec2::new(0.0, -9.81), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBodyBuilder::new().circle(0.5).build()));
+    assert_eq!(world.step(0.0), StepStats::default());
+}
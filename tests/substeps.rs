@@ -0,0 +1,182 @@
+//! Substepped solving (`SolverParams::substeps`).
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, Particle, RigidBody, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+const G: Scalar = 9.81;
+
+fn world(substeps: usize) -> World {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    world.solver.params.substeps = substeps;
+    world
+}
+
+/// A column of `count` 1 x 0.5 boxes on a static ground, shifted alternately by `stagger`
+/// and `-stagger` sideways.
+fn column(world: &mut World, count: usize, stagger: Scalar) {
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        20.0,
+        1.0,
+    )));
+    for i in 0..count {
+        let x = if i % 2 == 0 { stagger } else { -stagger };
+        let y = 0.25 + 0.5 * i as Scalar;
+        world.add(Box::new(RigidBody::box_xy(
+            Vec2::new(x, y),
+            0.0,
+            1.0,
+            1.0,
+            0.5,
+        )));
+    }
+}
+
+#[test]
+fn free_fall_is_integrated_per_substep() {
+    let substeps = 4;
+    let mut world = world(substeps);
+    world.add(Box::new(Particle::new(Vec2::zero(), Vec2::zero(), 1.0)));
+    let steps = 60;
+    for _ in 0..steps {
+        world.step(DT);
+    }
+
+    let t = steps as Scalar * DT;
+    let vy = world.entities[0].vel().y;
+    assert_approx_eq!(vy, -G * t, 1e-3, "velocity");
+    // Semi-implicit Euler over n substeps overshoots g t^2 / 2 by a factor (1 + 1/n).
+    let n = (steps * substeps) as Scalar;
+    let expected = -0.5 * G * t * t * (1.0 + 1.0 / n);
+    let y = world.entities[0].pos().y;
+    assert_approx_eq!(y, expected, 1e-3);
+}
+
+#[test]
+fn resting_impulse_is_per_substep() {
+    let substeps = 4;
+    let mut world = world(substeps);
+    column(&mut world, 1, 0.0);
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    let impulse: Scalar = world.contacts().map(|c| c.normal_impulse).sum();
+    let expected = G * DT / substeps as Scalar;
+    assert_approx_eq!(
+        impulse,
+        expected,
+        0.01 * expected,
+        "normal impulse {impulse}, expected {expected}"
+    );
+}
+
+/// Checked on every step: a column that sways instead of standing still passes whenever
+/// the last step happens to land near the middle of the swing.
+#[test]
+fn tall_column_stands_with_substeps() {
+    let count = 20;
+    let mut world = world(4);
+    world.solver.params.velocity_iterations = 2;
+    column(&mut world, count, 0.0);
+
+    for step in 0..600 {
+        world.step(DT);
+
+        for (i, e) in world.entities.iter().enumerate().skip(1) {
+            // The boxes start perfectly aligned: nothing should push them sideways.
+            assert_approx_eq!(e.pos().x, 0.0, 1e-3, "box {i} drifted at step {step}");
+            assert_approx_eq!(e.angle(), 0.0, 1e-3, "box {i} tilted at step {step}");
+        }
+        if step >= 300 {
+            let energy = world.total_kinetic_energy();
+            assert!(
+                energy < 0.01,
+                "column still moving at step {step}: {energy} J"
+            );
+        }
+    }
+}
+
+/// Largest sideways drift of any box of a staggered 20-box column over 600 steps, sampled
+/// on every step.
+fn staggered_column_drift(substeps: usize, iterations: usize) -> Scalar {
+    let mut world = world(substeps);
+    world.solver.params.velocity_iterations = iterations;
+    column(&mut world, 20, 0.05);
+    let start: Vec<Scalar> = world.entities.iter().map(|e| e.pos().x).collect();
+
+    let mut worst: Scalar = 0.0;
+    for _ in 0..600 {
+        world.step(DT);
+        for (e, x) in world.entities.iter().zip(&start) {
+            worst = worst.max((e.pos().x - x).abs());
+        }
+    }
+    worst
+}
+
+#[test]
+fn substeps_hold_a_column_a_single_step_drops() {
+    // The same 8 iterations per step: all in one solve, or 2 in each of 4 substeps. The
+    // staggered boxes lean on each other, so the column has to be held, not just balanced.
+    let single = staggered_column_drift(1, 8);
+    let split = staggered_column_drift(4, 2);
+    assert!(single > 1.0, "single step only drifted {single} m");
+    assert!(split < 0.02, "substepped column drifted {split} m");
+}
+
+/// Apex of the first rebound of a ball dropped from `start` onto a restitution-0.8 floor.
+fn rebound_apex(substeps: usize, start: Scalar) -> Scalar {
+    let mut world = world(substeps);
+    let ground = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, -0.5))
+        .mass(0.0)
+        .box_shape(10.0, 1.0)
+        .restitution(0.8)
+        .build();
+    world.add(Box::new(ground));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, start))
+        .circle(0.25)
+        .restitution(0.8)
+        .friction(0.0)
+        .build();
+    world.add(Box::new(ball));
+
+    let mut bounced = false;
+    let mut apex = Scalar::NEG_INFINITY;
+    for _ in 0..300 {
+        world.step(DT);
+        let vy = world.entities[1].vel().y;
+        bounced |= vy > 0.0;
+        if bounced {
+            apex = apex.max(world.entities[1].pos().y);
+            if vy < 0.0 {
+                break;
+            }
+        }
+    }
+    apex
+}
+
+#[test]
+fn bounce_height_does_not_depend_on_substeps() {
+    // The bounce is taken from the approach speed at the start of the step, once, not
+    // re-applied in every substep. Substepped, the ball leaves the floor a step later (the
+    // bounce comes after the positions are integrated), hence the tolerance.
+    let start = 2.25;
+    let single = rebound_apex(1, start);
+    let split = rebound_apex(8, start);
+    assert!(single < 0.9 * start, "single step rebound apex {single}");
+    assert_approx_eq!(
+        single,
+        split,
+        0.03 * single,
+        "rebound apex {single} with 1 substep, {split} with 8"
+    );
+}
@@ -0,0 +1,131 @@
+//! `Collider2D::support` for every collider kind.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::Collider2D;
+use tiny_physics_engine::math::transform::Transform2D;
+use tiny_physics_engine::math::vec::Vec2;
+use tiny_physics_engine::math::{Scalar, consts};
+
+#[test]
+fn circle_support_is_on_the_rim() {
+    let circle = Collider2D::Circle { radius: 2.0 };
+    assert_approx_eq!(
+        circle.support(Vec2::new(3.0, 4.0)),
+        Vec2::new(1.2, 1.6),
+        1e-5
+    );
+    assert_approx_eq!(
+        circle.support(Vec2::new(-1.0, 0.0)),
+        Vec2::new(-2.0, 0.0),
+        1e-5
+    );
+    assert_approx_eq!(circle.support(Vec2::zero()), Vec2::zero(), 1e-5);
+}
+
+#[test]
+fn box_support_is_a_corner() {
+    let b = Collider2D::Box {
+        half_extents: Vec2::new(1.0, 0.5),
+    };
+    assert_approx_eq!(b.support(Vec2::new(1.0, 1.0)), Vec2::new(1.0, 0.5), 1e-5);
+    assert_approx_eq!(b.support(Vec2::new(-0.1, 1.0)), Vec2::new(-1.0, 0.5), 1e-5);
+    assert_approx_eq!(
+        b.support(Vec2::new(-2.0, -0.3)),
+        Vec2::new(-1.0, -0.5),
+        1e-5
+    );
+    assert_approx_eq!(b.support(Vec2::new(0.2, -5.0)), Vec2::new(1.0, -0.5), 1e-5);
+}
+
+#[test]
+fn rounded_box_support_is_corner_plus_radius() {
+    let b = Collider2D::RoundedBox {
+        half_extents: Vec2::new(1.0, 0.5),
+        radius: 0.25,
+    };
+    let diagonal = Vec2::new(1.0, -1.0);
+    let expected = Vec2::new(1.0, -0.5) + diagonal.normalized() * 0.25;
+    assert_approx_eq!(b.support(diagonal), expected, 1e-5);
+}
+
+#[test]
+fn support_is_farthest_along_direction() {
+    // Checked against the shape's AABB: the support never reaches past it, and along the
+    // axes it reaches exactly to it.
+    let shapes = [
+        Collider2D::Circle { radius: 0.7 },
+        Collider2D::Box {
+            half_extents: Vec2::new(0.4, 1.3),
+        },
+        Collider2D::RoundedBox {
+            half_extents: Vec2::new(0.4, 1.3),
+            radius: 0.2,
+        },
+        Collider2D::Segment {
+            a: Vec2::new(-0.5, 1.0),
+            b: Vec2::new(2.0, -0.3),
+        },
+        Collider2D::Chain {
+            vertices: vec![
+                Vec2::new(-1.0, 0.0),
+                Vec2::new(0.0, 0.8),
+                Vec2::new(1.5, -0.4),
+            ],
+        },
+    ];
+    for shape in &shapes {
+        let aabb = shape.aabb(&Transform2D::identity());
+        for k in 0..32 {
+            let angle = k as Scalar * consts::TAU / 32.0;
+            let dir = Vec2::new(angle.cos(), angle.sin());
+            let reach = shape.support(dir).dot(dir);
+            let aabb_reach = [aabb.min.x, aabb.max.x]
+                .into_iter()
+                .flat_map(|x| [aabb.min.y, aabb.max.y].map(|y| Vec2::new(x, y).dot(dir)))
+                .fold(Scalar::NEG_INFINITY, Scalar::max);
+            assert!(
+                reach <= aabb_reach + 1e-5,
+                "support beyond AABB along {dir:?}"
+            );
+        }
+        for (dir, extent) in [
+            (Vec2::new(1.0, 0.0), aabb.max.x),
+            (Vec2::new(0.0, 1.0), aabb.max.y),
+            (Vec2::new(-1.0, 0.0), -aabb.min.x),
+            (Vec2::new(0.0, -1.0), -aabb.min.y),
+        ] {
+            assert_approx_eq!(shape.support(dir).dot(dir), extent, 1e-5);
+        }
+    }
+}
+
+#[test]
+fn compound_support_uses_the_farthest_part() {
+    let circle_at = |x: Scalar| {
+        (
+            Transform2D::from_translation(Vec2::new(x, 0.0)),
+            Collider2D::Circle { radius: 0.5 },
+        )
+    };
+    let compound = Collider2D::Compound {
+        parts: vec![circle_at(-2.0), circle_at(3.0)],
+    };
+
+    assert_approx_eq!(
+        compound.support(Vec2::new(1.0, 0.0)),
+        Vec2::new(3.5, 0.0),
+        1e-5
+    );
+    assert_approx_eq!(
+        compound.support(Vec2::new(-1.0, 0.0)),
+        Vec2::new(-2.5, 0.0),
+        1e-5
+    );
+    // Slightly left of straight up: the left circle's rim is further.
+    let dir = Vec2::new(-0.1, 1.0);
+    assert_approx_eq!(
+        compound.support(dir),
+        Vec2::new(-2.0, 0.0) + dir.normalized() * 0.5,
+        1e-5,
+    );
+}
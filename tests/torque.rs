@@ -0,0 +1,84 @@
+//! Torque generators: `ConstantTorque` and the `Flywheel` motor.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::forces::torque::{ConstantTorque, Flywheel};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+
+/// A 1 kg disc of radius 0.5 (inertia 0.125) at rest, no gravity.
+fn disc_world() -> World {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBodyBuilder::new().circle(0.5).build()));
+    world
+}
+
+#[test]
+fn constant_torque_spins_up_at_a_steady_rate() {
+    let mut world = disc_world();
+    world.add_force(Box::new(ConstantTorque {
+        entity: 0,
+        torque: 0.25,
+    }));
+    for _ in 0..60 {
+        world.step(DT);
+        assert_eq!(world.entities[0].torque(), 0.25);
+    }
+    // alpha = 0.25 / 0.125 = 2 rad/s^2 for one second.
+    let omega = world.entities[0].omega();
+    assert_approx_eq!(omega, 2.0, 1e-4, "omega {omega}");
+    assert_approx_eq!(*world.entities[0].vel(), Vec2::zero(), 0.0);
+}
+
+#[test]
+fn flywheel_spins_up_within_max_torque_and_settles_at_target() {
+    let mut world = disc_world();
+    let (target, max_torque) = (10.0, 0.5);
+    world.add_force(Box::new(Flywheel {
+        entity: 0,
+        target_omega: target,
+        gain: 2.0,
+        max_torque,
+    }));
+
+    let mut saturated = 0;
+    for _ in 0..600 {
+        let before = world.entities[0].omega();
+        world.step(DT);
+        let torque = world.entities[0].torque();
+        assert!(torque.abs() <= max_torque, "torque {torque}");
+        if torque == max_torque {
+            saturated += 1;
+            // Full torque: alpha = 0.5 / 0.125 = 4 rad/s^2.
+            let gained = world.entities[0].omega() - before;
+            assert_approx_eq!(gained, 4.0 * DT, 1e-4, "gained {gained}");
+        }
+    }
+    // The gain asks for 20 N m at rest, so it starts out clamped.
+    assert!(saturated > 60, "only {saturated} steps at full torque");
+
+    let omega = world.entities[0].omega();
+    assert_approx_eq!(omega, target, 1e-3, "omega {omega}");
+    assert_approx_eq!(world.entities[0].torque(), 0.0, 1e-3);
+}
+
+#[test]
+fn flywheel_brakes_a_disc_spinning_too_fast() {
+    let mut world = disc_world();
+    *world.entities[0].omega_mut() = 30.0;
+    world.add_force(Box::new(Flywheel {
+        entity: 0,
+        target_omega: 10.0,
+        gain: 2.0,
+        max_torque: 0.5,
+    }));
+    world.step(DT);
+    assert_eq!(world.entities[0].torque(), -0.5);
+    for _ in 0..600 {
+        world.step(DT);
+    }
+    let omega = world.entities[0].omega();
+    assert_approx_eq!(omega, 10.0, 1e-3, "omega {omega}");
+}
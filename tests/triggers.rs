@@ -0,0 +1,92 @@
+//! Sensor bodies: no collision response, enter/exit events per overlapping pair.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+const G: Scalar = 9.81;
+
+/// A static 4 x 1 sensor centered at the origin and a ball falling from `drop` above it.
+fn world_with_gate(drop: Scalar) -> World {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    let gate = RigidBodyBuilder::new()
+        .mass(0.0)
+        .box_shape(4.0, 1.0)
+        .sensor(true)
+        .build();
+    world.add(Box::new(gate));
+    let ball = RigidBodyBuilder::new()
+        .position(Vec2::new(0.0, drop))
+        .circle(0.25)
+        .build();
+    world.add(Box::new(ball));
+    world
+}
+
+#[test]
+fn ball_falls_through_a_sensor_with_one_enter_and_one_exit() {
+    let mut world = world_with_gate(2.0);
+    let mut entered = Vec::new();
+    let mut exited = Vec::new();
+    for step in 0..90 {
+        world.step(DT);
+        let events = world.trigger_events();
+        entered.extend(events.entered.iter().map(|&pair| (step, pair)));
+        exited.extend(events.exited.iter().map(|&pair| (step, pair)));
+    }
+
+    assert_eq!(entered.len(), 1, "entered: {entered:?}");
+    assert_eq!(exited.len(), 1, "exited: {exited:?}");
+    assert_eq!(entered[0].1, (0, 1));
+    assert_eq!(exited[0].1, (0, 1));
+    assert!(entered[0].0 < exited[0].0);
+
+    // The sensor never pushed back: the ball is in free fall.
+    let expected = -G * 90.0 * DT;
+    let vy = world.entities[1].vel().y;
+    assert_approx_eq!(vy, expected, 1e-3);
+    assert_eq!(world.contacts().count(), 0);
+}
+
+#[test]
+fn removing_an_overlapping_body_counts_as_exit() {
+    let mut world = world_with_gate(0.0);
+    world.step(DT);
+    assert_eq!(world.trigger_events().entered, [(0, 1)]);
+
+    world.entities.pop();
+    world.step(DT);
+    assert_eq!(world.trigger_events().exited, [(0, 1)]);
+    assert!(world.trigger_events().entered.is_empty());
+}
+
+#[test]
+fn removing_a_body_keeps_the_other_overlaps() {
+    let mut world = world_with_gate(0.0);
+    *world.entities[1].pos_mut() = Vec2::new(-1.0, 0.0);
+    let other = RigidBodyBuilder::new()
+        .position(Vec2::new(1.0, 0.0))
+        .circle(0.25)
+        .build();
+    world.add(Box::new(other));
+    world.step(DT);
+    assert_eq!(world.trigger_events().entered, [(0, 1), (0, 2)]);
+
+    // The removed ball exits under its old index; the other one moves down to index 1 and
+    // is still inside.
+    assert!(world.remove(1).is_some());
+    world.step(DT);
+    assert!(world.trigger_events().entered.is_empty());
+    assert_eq!(world.trigger_events().exited, [(0, 1)]);
+
+    // The other ball falls out under its new index.
+    let mut exited = Vec::new();
+    for _ in 0..60 {
+        world.step(DT);
+        assert!(world.trigger_events().entered.is_empty());
+        exited.extend_from_slice(&world.trigger_events().exited);
+    }
+    assert_eq!(exited, [(0, 1)]);
+}
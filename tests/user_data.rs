@@ -0,0 +1,37 @@
+//! Bodies carry a `user_data` handle that leads from a hit back to the game object.
+
+use tiny_physics_engine::core::{Integrator, Particle, PhysicalEntity, RigidBodyBuilder, World};
+use tiny_physics_engine::math::vec::Vec2;
+
+#[test]
+fn user_data_defaults_to_zero_and_can_be_set() {
+    let mut body = RigidBodyBuilder::new().circle(0.5).build();
+    assert_eq!(body.user_data(), 0);
+    body.set_user_data(7);
+    assert_eq!(body.user_data(), 7);
+
+    let mut particle = Particle::new(Vec2::zero(), Vec2::zero(), 1.0);
+    assert_eq!(particle.user_data(), 0);
+    particle.set_user_data(u64::MAX);
+    assert_eq!(particle.user_data(), u64::MAX);
+}
+
+#[test]
+fn ray_hit_maps_back_to_the_game_object() {
+    let mut world = World::new(Vec2::zero(), Integrator::SemiImplicitEuler);
+    for (x, id) in [(3.0, 101), (6.0, 202)] {
+        let body = RigidBodyBuilder::new()
+            .position(Vec2::new(x, 0.0))
+            .circle(0.5)
+            .user_data(id)
+            .build();
+        world.add(Box::new(body));
+    }
+    world.entities[1].set_user_data(303);
+    world.step(1.0 / 60.0);
+
+    let hit = world
+        .raycast_ccd(Vec2::new(10.0, 0.0), Vec2::new(-1.0, 0.0), 20.0, 0.0)
+        .unwrap();
+    assert_eq!(world.entities[hit.entity].user_data(), 303);
+}
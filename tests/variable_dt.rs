@@ -0,0 +1,131 @@
+//! Warm starting when `World::step` is driven with a changing dt (real frame times).
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBody, WeldJoint, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const G: Scalar = 9.81;
+/// Alternating step lengths, like a game loop that doesn't hold a steady frame rate.
+const DTS: [Scalar; 2] = [1.0 / 120.0, 1.0 / 90.0];
+
+fn ground(world: &mut World) {
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(0.0, -0.5),
+        0.0,
+        0.0,
+        20.0,
+        1.0,
+    )));
+}
+
+/// A column of `count` 1 kg boxes resting on a static ground.
+fn stack(count: usize) -> World {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    ground(&mut world);
+    for i in 0..count {
+        let y = 0.25 + 0.5 * i as Scalar;
+        world.add(Box::new(RigidBody::box_xy(
+            Vec2::new(0.0, y),
+            0.0,
+            1.0,
+            1.0,
+            0.5,
+        )));
+    }
+    world
+}
+
+#[test]
+fn resting_contact_impulse_tracks_dt() {
+    let mut world = stack(1);
+    for _ in 0..120 {
+        for dt in DTS {
+            world.step(dt);
+        }
+    }
+
+    // Resting 1 kg box: the ground pushes back with m * g * dt per step, whatever dt is.
+    for dt in DTS {
+        world.step(dt);
+        let impulse: Scalar = world.contacts().map(|c| c.normal_impulse).sum();
+        let expected = G * dt;
+        assert_approx_eq!(
+            impulse,
+            expected,
+            0.01 * expected,
+            "dt {dt}: normal impulse {impulse}, expected {expected}"
+        );
+    }
+}
+
+#[test]
+fn stack_stays_at_rest_under_alternating_dt() {
+    let count = 5;
+    let mut world = stack(count);
+    for _ in 0..60 {
+        for dt in DTS {
+            world.step(dt);
+        }
+    }
+    let settled: Vec<(Vec2, Scalar)> = world
+        .entities
+        .iter()
+        .map(|e| (*e.pos(), e.angle()))
+        .collect();
+
+    let mut peak_energy: Scalar = 0.0;
+    for _ in 0..300 {
+        for dt in DTS {
+            world.step(dt);
+            peak_energy = peak_energy.max(world.total_kinetic_energy());
+        }
+    }
+
+    assert!(peak_energy < 1e-3, "stack gained energy: {peak_energy} J");
+    for (i, (e, (pos, angle))) in world.entities.iter().zip(&settled).enumerate() {
+        assert_approx_eq!(*e.pos(), *pos, 1e-3, "box {i} drifted");
+        assert_approx_eq!(e.angle(), *angle, 1e-4, "box {i} tipped");
+    }
+}
+
+#[test]
+fn weld_impulse_tracks_dt() {
+    // A 1 kg box welded to a static anchor: the weld carries m * g * dt per step.
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::zero(),
+        0.0,
+        0.0,
+        0.5,
+        0.5,
+    )));
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::new(1.0, 0.0),
+        0.0,
+        1.0,
+        0.5,
+        0.5,
+    )));
+    let weld = WeldJoint::new(
+        &*world.entities[0],
+        &*world.entities[1],
+        0,
+        1,
+        Vec2::new(0.5, 0.0),
+    );
+    world.add_joint(weld);
+
+    for _ in 0..120 {
+        for dt in DTS {
+            world.step(dt);
+        }
+    }
+    for dt in DTS {
+        world.step(dt);
+        let impulse = world.joint::<WeldJoint>(0).unwrap().linear_impulse;
+        let expected = G * dt;
+        assert_approx_eq!(impulse.y, expected, 0.01 * expected, "dt {dt}");
+        assert_approx_eq!(impulse.x, 0.0, 1e-3 * expected, "dt {dt}");
+    }
+}
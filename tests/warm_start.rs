@@ -0,0 +1,150 @@
+//! Warm starting from last step's contact impulses: switching it off, and dropping the
+//! impulses of a teleported body (`World::set_transform`).
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBodyBuilder, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+const G: Scalar = 9.81;
+
+/// A 1 kg unit box (entity 1) on the ground under a 50 kg one (entity 2), settled.
+fn loaded_box() -> World {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    for (y, mass) in [(0.5, 1.0), (1.5, 50.0)] {
+        let block = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, y))
+            .mass(mass)
+            .box_shape(1.0, 1.0)
+            .restitution(0.0)
+            .build();
+        world.add(Box::new(block));
+    }
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    world
+}
+
+#[test]
+fn teleported_body_starts_its_contacts_from_zero() {
+    let mut world = loaded_box();
+    let y = world.entities[1].pos().y;
+    world.set_transform(1, Vec2::new(5.0, y), 0.0);
+    let touching = |a: usize, b: usize| a == 1 || b == 1;
+    assert!(
+        world
+            .solver
+            .contact_impulses()
+            .iter()
+            .filter(|c| touching(c.a, c.b))
+            .all(|c| c.normal_impulse == 0.0 && c.tangent_impulse == 0.0)
+    );
+
+    // Resting on its own now: it carries its weight, not the load it had.
+    world.step(DT);
+    let carried: Scalar = world
+        .contacts()
+        .filter(|c| touching(c.body_a, c.body_b))
+        .map(|c| c.normal_impulse)
+        .sum();
+    assert!(carried < 2.0 * G * DT, "normal impulse {carried}");
+    for _ in 0..30 {
+        world.step(DT);
+        let vy = world.entities[1].vel().y;
+        assert!(vy < 1e-3, "teleported box kicked up at {vy} m/s");
+    }
+}
+
+#[test]
+fn taking_the_top_box_off_a_stack_leaves_the_rest_alone() {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    world.add_ground(0.0, 0.5, 0.0);
+    for i in 0..3 {
+        let block = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 0.5 + i as Scalar))
+            .box_shape(1.0, 1.0)
+            .restitution(0.0)
+            .build();
+        world.add(Box::new(block));
+    }
+    for _ in 0..120 {
+        world.step(DT);
+    }
+    let impulses = |world: &World| {
+        world
+            .solver
+            .contact_impulses()
+            .iter()
+            .map(|c| ((c.a, c.b), c.normal_impulse))
+            .collect::<Vec<_>>()
+    };
+    let before = impulses(&world);
+
+    world.set_transform(3, Vec2::new(5.0, 0.5), 0.0);
+    for (((a, b), after), (_, was)) in impulses(&world).into_iter().zip(before) {
+        if b == 3 {
+            assert_eq!(after, 0.0, "({a}, {b}) kept its impulse");
+        } else {
+            assert_eq!(after, was, "({a}, {b}) was disturbed");
+        }
+    }
+
+    // The two left behind settle under their own weight without a kick.
+    world.step(DT);
+    let ground: Scalar = world
+        .contacts()
+        .filter(|c| (c.body_a, c.body_b) == (0, 1))
+        .map(|c| c.normal_impulse)
+        .sum();
+    let weight = 2.0 * G * DT;
+    assert_approx_eq!(
+        ground,
+        weight,
+        0.1 * weight,
+        "ground carries {ground}, two boxes weigh {weight}"
+    );
+    for i in [1, 2] {
+        let vy = world.entities[i].vel().y;
+        assert_approx_eq!(vy, 0.0, 0.01, "box {i} still moving");
+    }
+}
+
+#[test]
+fn without_warm_start_the_iterations_start_from_zero() {
+    let residual = |warm_start: bool| {
+        let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+        world.solver.warm_start_enabled = warm_start;
+        world.solver.params.velocity_iterations = 1;
+        world.solver.params.relax_iterations = 0;
+        world.add_ground(0.0, 0.5, 0.0);
+        let block = RigidBodyBuilder::new()
+            .position(Vec2::new(0.0, 0.5))
+            .box_shape(1.0, 1.0)
+            .build();
+        world.add(Box::new(block));
+        for _ in 0..120 {
+            world.step(DT);
+        }
+        world.solver.last_residual()
+    };
+    // The single iteration only has to touch up last step's impulses, or build them from
+    // scratch (half the weight on each of the two points).
+    assert!(residual(true) < 0.1 * G * DT);
+    assert!(residual(false) > 0.4 * G * DT);
+}
+
+#[test]
+fn clear_cache_zeroes_every_contact() {
+    let mut world = loaded_box();
+    world.solver.clear_cache();
+    assert!(
+        world
+            .solver
+            .contact_impulses()
+            .iter()
+            .all(|c| c.normal_impulse == 0.0 && c.tangent_impulse == 0.0)
+    );
+}
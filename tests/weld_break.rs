@@ -0,0 +1,75 @@
+//! Breakable welds: a loaded chain snaps at its weakest point and reports it once.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::{Integrator, RigidBody, WeldJoint, World};
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::vec::Vec2;
+
+const DT: Scalar = 1.0 / 60.0;
+const G: Scalar = 9.81;
+
+/// A static hook at the origin with three 1 kg links welded below it, each 1 m tall, and a
+/// `weight` kg block welded to the bottom link. The weld to the hook breaks above
+/// `hook_strength`; the others never break.
+fn hanging_chain(weight: Scalar, hook_strength: Scalar) -> World {
+    let mut world = World::new(Vec2::new(0.0, -G), Integrator::SemiImplicitEuler);
+    world.add(Box::new(RigidBody::box_xy(
+        Vec2::zero(),
+        0.0,
+        0.0,
+        1.0,
+        1.0,
+    )));
+    for i in 1..=4 {
+        let mass = if i == 4 { weight } else { 1.0 };
+        let y = -(i as Scalar);
+        world.add(Box::new(RigidBody::box_xy(
+            Vec2::new(0.0, y),
+            0.0,
+            mass,
+            0.5,
+            1.0,
+        )));
+        let (a, b) = (&*world.entities[i - 1], &*world.entities[i]);
+        let anchor = Vec2::new(0.0, y + 0.5);
+        let weld = WeldJoint::new(a, b, i - 1, i, anchor);
+        if i == 1 {
+            world.add_joint(weld.with_break_limits(hook_strength, Scalar::INFINITY));
+        } else {
+            world.add_joint(weld);
+        }
+    }
+    world
+}
+
+#[test]
+fn unbroken_chain_holds_its_load() {
+    // The hook carries the links and the weight: 13 kg.
+    let mut world = hanging_chain(10.0, 1.5 * 13.0 * G);
+    for _ in 0..120 {
+        world.step(DT);
+        assert!(world.joint_breaks.is_empty());
+    }
+    assert_eq!(world.joints.len(), 4);
+    let bottom = world.entities[4].pos().y;
+    assert_approx_eq!(bottom, -4.0, 0.05, "weight sagged to y = {bottom}");
+}
+
+#[test]
+fn overloaded_chain_breaks_off_the_hook_once() {
+    let mut world = hanging_chain(10.0, 10.0 * G);
+    let mut breaks = Vec::new();
+    for step in 0..120 {
+        world.step(DT);
+        breaks.extend(world.joint_breaks.iter().map(|b| (step, *b)));
+    }
+    assert_eq!(breaks.len(), 1, "breaks: {breaks:?}");
+    let (_, snap) = breaks[0];
+    assert_eq!((snap.joint, snap.body_a, snap.body_b), (0, 0, 1));
+
+    // The rest of the chain falls off the hook in one piece.
+    assert_eq!(world.joints.len(), 3);
+    let (top, bottom) = (world.entities[1].pos().y, world.entities[4].pos().y);
+    assert!(top < -2.0, "top link still at y = {top}");
+    assert_approx_eq!(top - bottom, 3.0, 0.05, "links {top}, {bottom}");
+}
@@ -0,0 +1,85 @@
+//! World-space outlines of colliders.
+
+use tiny_physics_engine::assert_approx_eq;
+use tiny_physics_engine::core::Collider2D;
+use tiny_physics_engine::math::Scalar;
+use tiny_physics_engine::math::consts::FRAC_PI_2;
+use tiny_physics_engine::math::mat::Mat2;
+use tiny_physics_engine::math::transform::Transform2D;
+use tiny_physics_engine::math::vec::Vec2;
+
+/// Shoelace area; positive for a counter-clockwise outline.
+fn signed_area(pts: &[Vec2]) -> Scalar {
+    let n = pts.len();
+    (0..n)
+        .map(|i| pts[i].cross(pts[(i + 1) % n]))
+        .sum::<Scalar>()
+        * 0.5
+}
+
+#[test]
+fn rotated_box_corners() {
+    let shape = Collider2D::Box {
+        half_extents: Vec2::new(2.0, 1.0),
+    };
+    let pts = shape.world_vertices(&Transform2D::from_pose(Vec2::new(10.0, 5.0), FRAC_PI_2));
+    assert_eq!(pts.len(), 4);
+    // A quarter turn maps the local (-2, -1) corner to (1, -2).
+    assert_approx_eq!(pts[0], Vec2::new(11.0, 3.0), 1e-5);
+    assert_approx_eq!(pts[2], Vec2::new(9.0, 7.0), 1e-5);
+    assert_approx_eq!(signed_area(&pts), 8.0, 1e-4);
+}
+
+#[test]
+fn rounded_box_outline_follows_the_arcs() {
+    let (half, radius) = (Vec2::new(1.0, 0.5), 0.25);
+    let shape = Collider2D::RoundedBox {
+        half_extents: half,
+        radius,
+    };
+    let pos = Vec2::new(-3.0, 2.0);
+    let pts = shape.world_vertices(&Transform2D::from_pose(pos, 0.3));
+    assert!(pts.len() > 8);
+
+    // Every vertex lies exactly `radius` outside the inner box.
+    let inner = Collider2D::Box { half_extents: half };
+    let box_pts = inner.world_vertices(&Transform2D::identity());
+    let rot = Mat2::rotation(-0.3);
+    for p in &pts {
+        let local = rot.mul_vec2(*p - pos);
+        let clamped = Vec2::new(
+            local.x.clamp(-half.x, half.x),
+            local.y.clamp(-half.y, half.y),
+        );
+        let d = (local - clamped).length();
+        assert_approx_eq!(d, radius, 1e-4, "vertex {p:?} is {d} from the inner box");
+    }
+    let area = signed_area(&pts);
+    assert!(area > 0.0 && area <= shape.area() + 1e-4, "area {area}");
+    assert!(signed_area(&box_pts) < area);
+}
+
+#[test]
+fn circle_has_no_vertices_and_compound_lists_its_parts() {
+    assert!(
+        Collider2D::Circle { radius: 1.0 }
+            .world_vertices(&Transform2D::identity())
+            .is_empty()
+    );
+
+    let unit = || Collider2D::Box {
+        half_extents: Vec2::new(0.5, 0.5),
+    };
+    let shape = Collider2D::Compound {
+        parts: vec![
+            (Transform2D::from_translation(Vec2::new(-1.0, 0.0)), unit()),
+            (Transform2D::from_translation(Vec2::new(1.0, 0.0)), unit()),
+            (Transform2D::identity(), Collider2D::Circle { radius: 0.5 }),
+        ],
+    };
+    let pts = shape.world_vertices(&Transform2D::from_pose(Vec2::new(0.0, 1.0), FRAC_PI_2));
+    assert_eq!(pts.len(), 8);
+    // The first part sits one unit below the body after the quarter turn.
+    let center = (pts[0] + pts[1] + pts[2] + pts[3]) * 0.25;
+    assert_approx_eq!(center, Vec2::new(0.0, 0.0), 1e-5);
+}